@@ -0,0 +1,1359 @@
+use crate::frontend::syntax::DataType;
+use crate::frontend::syntax::ast::*;
+use crate::frontend::semantic::resolver::{self, Resolution};
+use crate::frontend::semantic::type_checker::{self, Type};
+use crate::frontend::semantic::natives::Native;
+use crate::frontend::semantic::ENTRY_POINT;
+use crate::backend::interpreter::RuntimeError;
+use crate::backend::value::Value;
+
+use std::collections::{HashMap, VecDeque};
+
+/// number of hidden slots of a for loop: counter, end and step
+const FOR_LOOP_SLOTS: usize = 3;
+
+/// name of the function which initializes the global variables
+const INITIALIZER: &str = "<globals>";
+
+/// Single instruction of the stack machine.
+/// Operands are popped from the value stack and the result is pushed back onto it.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum Instruction {
+    /// pushes the constant with this index
+    Constant(usize),
+    /// pushes the value of the variable in this slot of the call frame
+    Load(usize),
+    /// pops a value and stores it in this slot of the call frame
+    Store(usize),
+    /// pushes the value of the global variable with this index,
+    /// fails if the declaration of the global was not executed yet
+    LoadGlobal(usize),
+    /// pops a value and stores it in the global variable with this index,
+    /// fails if the declaration of the global was not executed yet
+    StoreGlobal(usize),
+    /// pops a value and stores it as first value of the global variable with this index
+    InitGlobal(usize),
+    /// drops the topmost value
+    Pop,
+    /// continues at this instruction
+    Jump(usize),
+    /// pops a boolean and continues at this instruction if it is false
+    JumpIfFalse(usize),
+    /// calls the function with this index, the arguments are the topmost values
+    Call(usize),
+    /// calls the function value below this number of arguments with the values it captured,
+    /// fails if the function takes another number of arguments
+    CallValue(usize),
+    /// calls the function of the standard library, the arguments are the topmost values
+    CallNative(Native),
+    /// pops the values captured by the function with this index and pushes the function as value
+    MakeFunction(usize),
+    /// pops the returned value and returns to the calling function
+    Return,
+    /// returns to the calling function without a value
+    ReturnVoid,
+    AddInt,
+    SubInt,
+    MulInt,
+    DivInt,
+    ModInt,
+    NegInt,
+    AddFloat,
+    SubFloat,
+    MulFloat,
+    DivFloat,
+    ModFloat,
+    NegFloat,
+    /// pops two ints and pushes their sum and true, or only false if the sum overflows
+    CheckedAddInt,
+    /// appends the topmost string to the string below it
+    Concat,
+    /// inverts a boolean
+    Not,
+    /// compares two integers with a comparison operator like BinOp::Lt
+    CompareInt(BinOp),
+    CompareFloat(BinOp),
+    CompareString(BinOp),
+    /// compares two booleans, only BinOp::Eq and BinOp::Neq are allowed
+    CompareBoolean(BinOp),
+    /// pops this number of values and pushes a string with their texts joined,
+    /// fails if a value is not a string, a number or a boolean
+    Interpolate(usize),
+    /// pops this number of values and pushes an array with them as elements
+    MakeArray(usize),
+    /// pops an index and an array and pushes the element at the index
+    Index,
+    /// pops a value, an index and an array and stores the value at the index
+    StoreIndex,
+    /// pops an array and pushes its length
+    ArrayLen,
+    /// pops a value and an array and appends the value to the array
+    ArrayPush,
+    /// pops an array and pushes its removed last element
+    ArrayPop,
+    /// pops the values of a struct literal and pushes an instance built with the layout with this index
+    MakeStruct(usize),
+    /// pops an instance and pushes the value of the field at this position
+    GetField(usize),
+    /// pops a value and an instance and stores the value in the field at this position
+    SetField(usize),
+    /// pops the values of the fields and pushes the variant with the layout with this index
+    MakeVariant(usize),
+    /// pops an enum value and pushes true if it is the variant with the layout with this index
+    IsVariant(usize),
+    /// pops a variant of the layout with the first index and pushes the value of its field at the second position
+    VariantField(usize, usize),
+    /// stops the program with the RuntimeError with this index
+    Trap(usize),
+}
+
+/// Compiled function
+#[derive(PartialEq, Clone, Debug)]
+pub struct FunctionCode {
+    pub name: String,
+    /// number of arguments, the arguments are stored in the first slots
+    pub arity: usize,
+    /// number of variable slots in the call frame
+    pub slots: usize,
+    /// slots of the values captured by a lambda, in the order of the captures
+    pub captures: Vec<usize>,
+    /// type of the function used as a value
+    pub data_type: DataType,
+    pub code: Vec<Instruction>,
+}
+
+/// Layout of the instance created by a struct literal
+#[derive(PartialEq, Clone, Debug)]
+pub struct StructLayout {
+    pub name: String,
+    /// names of the fields in the order of the struct declaration
+    pub fields: Vec<String>,
+    /// position of every field value in the order the literal pushes them
+    pub order: Vec<usize>,
+}
+
+/// Variant of an enum created by Instruction::MakeVariant
+#[derive(PartialEq, Clone, Debug)]
+pub struct VariantLayout {
+    /// name of the enum
+    pub name: String,
+    pub variant: String,
+    /// number of fields
+    pub arity: usize,
+}
+
+/// Program compiled to bytecode
+#[derive(PartialEq, Clone, Debug)]
+pub struct Program {
+    pub functions: Vec<FunctionCode>,
+    /// every literal of the program
+    pub constants: Vec<Value>,
+    /// errors raised by Instruction::Trap
+    pub errors: Vec<RuntimeError>,
+    /// layouts of the struct literals used by Instruction::MakeStruct
+    pub structs: Vec<StructLayout>,
+    /// variants used by Instruction::MakeVariant and Instruction::IsVariant
+    pub variants: Vec<VariantLayout>,
+    /// index of the main function
+    pub entry: usize,
+    /// name of every global variable by its index
+    pub globals: Vec<String>,
+    /// index of the function which initializes the global variables before the main function
+    /// is called, None if the program has no global variables
+    pub initializer: Option<usize>,
+}
+
+/// Compiles the AbstractSyntaxTree to bytecode.
+/// Operations which would fail in the RuntimeInterpreter, like calls of undefined functions
+/// or operators on values of the wrong type, are compiled to traps. These raise the same
+/// RuntimeError when they are executed.
+pub fn compile(ast: &AbstractSyntaxTree) -> Result<Program, RuntimeError> {
+    let (resolution, _) = resolver::resolve(ast);
+    let mut compiler = Compiler::new(resolution);
+
+    let mut declarations = Vec::new();
+    let mut globals = Vec::new();
+    for stmt in &ast.nodes {
+        match &stmt.kind {
+            StatementKind::Expression(decl @ Expression { kind: ExpressionKind::FnDecl(..), .. }) => {
+                compiler.declare_function(decl);
+                declarations.push(decl);
+            },
+            StatementKind::Declaration(binding, expr) | StatementKind::Constant(binding, expr) => {
+                // the type of every global is known before the functions are compiled
+                compiler.define(binding);
+                globals.push((binding, expr));
+            },
+            StatementKind::Struct(name, fields) => compiler.declare_struct(name, fields),
+            StatementKind::Enum(name, variants) => compiler.declare_enum(name, variants),
+            StatementKind::Impl(_, functions) => {
+                for decl in functions {
+                    compiler.declare_function(decl);
+                    declarations.push(decl);
+                }
+            },
+            _ => return Err(RuntimeError::InvalidTopLevelStatement),
+        }
+    }
+    let entry = compiler.resolution.function(ENTRY_POINT)
+        .and_then(|id| compiler.function_indices.get(&id).copied())
+        .ok_or(RuntimeError::MissingMainFunction)?;
+    // the initializer follows the declared functions, the lambdas get the indices behind it
+    let initializer = if globals.is_empty() {
+        None
+    } else {
+        compiler.signatures.push(Signature { name: INITIALIZER.into(), args: Vec::new(), return_type: None, method: false });
+        Some(compiler.signatures.len() - 1)
+    };
+
+    let mut functions: Vec<FunctionCode> = declarations.into_iter()
+        .map(|decl| compiler.compile_function(decl))
+        .collect();
+    if initializer.is_some() {
+        functions.push(compiler.compile_initializer(&globals));
+    }
+    // lambdas are compiled in the order they were found, which is the order of their indices
+    while let Some(lambda) = compiler.lambdas.pop_front() {
+        functions.push(compiler.compile_lambda(lambda));
+    }
+    let globals = globals.iter().map(|(binding, _)| binding.symbol.clone()).collect();
+    let Compiler { constants, errors, layouts: structs, variants, .. } = compiler;
+    Ok(Program { functions, constants, errors, structs, variants, entry, globals, initializer })
+}
+
+/// Signature of a declared function
+struct Signature {
+    name: String,
+    args: Vec<DataType>,
+    return_type: Option<DataType>,
+    /// true if the first argument is the receiver "self" of a method
+    method: bool,
+}
+
+impl Signature {
+
+    /// returns the type of the function used as a value
+    fn data_type(&self) -> DataType {
+        DataType::Function(self.args.clone(), self.return_type.clone().map(Box::new))
+    }
+}
+
+/// Lambda which was found in a function and is compiled after it
+struct PendingLambda {
+    id: SymbolId,
+    args: Vec<VariableBinding>,
+    body: Block,
+}
+
+/// Jumps of the loop which is compiled at the moment, their targets
+/// are set after the body of the loop was compiled
+#[derive(Default)]
+struct LoopLabels {
+    /// jumps of break statements to the end of the loop
+    breaks: Vec<usize>,
+    /// jumps of continue statements to the next iteration
+    continues: Vec<usize>,
+    /// true for "loop", the breaks of while and for loops push no value
+    has_value: bool,
+    /// type of the value pushed by the first break
+    value: Option<Type>,
+}
+
+struct Compiler {
+    resolution: Resolution,
+    /// index of every function and lambda by the symbol of its declaration
+    function_indices: HashMap<SymbolId, usize>,
+    signatures: Vec<Signature>,
+    /// lambdas which were found but not compiled yet
+    lambdas: VecDeque<PendingLambda>,
+    /// type of every variable compiled so far
+    variables: HashMap<SymbolId, DataType>,
+    constants: Vec<Value>,
+    errors: Vec<RuntimeError>,
+    /// fields of every declared struct in the order of their declaration
+    structs: HashMap<String, Vec<(String, DataType)>>,
+    layouts: Vec<StructLayout>,
+    /// variants of every declared enum with the types of their fields in the order of their declaration
+    enums: HashMap<String, Vec<(String, Vec<DataType>)>>,
+    variants: Vec<VariantLayout>,
+    /// index of the function which is compiled at the moment
+    function: usize,
+    code: Vec<Instruction>,
+    loops: Vec<LoopLabels>,
+    /// number of slots the resolver gave the variables of the current function.
+    /// The counter, end and step of for loops and the values of matches are stored in the slots behind them
+    variable_slots: usize,
+    /// number of hidden slots used by the for loops and matches around the current expression
+    hidden_slots: usize,
+    max_hidden_slots: usize,
+}
+
+impl Compiler {
+
+    fn new(resolution: Resolution) -> Self {
+        Compiler {
+            resolution,
+            function_indices: HashMap::new(),
+            signatures: Vec::new(),
+            lambdas: VecDeque::new(),
+            variables: HashMap::new(),
+            constants: Vec::new(),
+            errors: Vec::new(),
+            structs: HashMap::new(),
+            layouts: Vec::new(),
+            enums: HashMap::new(),
+            variants: Vec::new(),
+            function: 0,
+            code: Vec::new(),
+            loops: Vec::new(),
+            variable_slots: 0,
+            hidden_slots: 0,
+            max_hidden_slots: 0,
+        }
+    }
+
+    /// declares a function with the name of its symbol, so functions of impl blocks have their full name
+    fn declare_function(&mut self, decl: &Expression) {
+        if let ExpressionKind::FnDecl(name, _, args, return_type) = &decl.kind {
+            let mut name = name.clone();
+            if let Some(id) = self.resolution.symbol(decl.id) {
+                self.function_indices.insert(id, self.signatures.len());
+                name = self.resolution.definition(id).name.clone();
+            }
+            let method = args.iter().flatten().next().is_some_and(|binding| binding.symbol == SELF);
+            let args = args.iter().flatten().map(|binding| binding.data_type.clone()).collect();
+            self.signatures.push(Signature { name, args, return_type: return_type.clone(), method });
+        }
+    }
+
+    fn declare_struct(&mut self, name: &str, fields: &[VariableBinding]) {
+        let fields = fields.iter().map(|field| (field.symbol.clone(), field.data_type.clone())).collect();
+        self.structs.insert(name.into(), fields);
+    }
+
+    fn declare_enum(&mut self, name: &str, variants: &[Variant]) {
+        let variants = variants.iter().map(|variant| (variant.name.clone(), variant.fields.clone())).collect();
+        self.enums.insert(name.into(), variants);
+    }
+
+    fn compile_function(&mut self, decl: &Expression) -> FunctionCode {
+        let (body, args) = match &decl.kind {
+            ExpressionKind::FnDecl(_, body, args, _) => (body, args.as_deref().unwrap_or_default()),
+            _ => unreachable!("only function declarations are compiled"),
+        };
+        let slots = self.resolution.symbol(decl.id)
+            .map(|id| match self.resolution.definition(id).kind {
+                resolver::SymbolKind::Function(slots) => slots,
+                resolver::SymbolKind::Variable(_) | resolver::SymbolKind::Global(_) => 0,
+            })
+            .unwrap_or(0);
+        self.compile_body(args, body, slots, Vec::new())
+    }
+
+    /// compiles a lambda found in a function which was compiled before.
+    /// The captured variables have the types of the variables they are copied from
+    fn compile_lambda(&mut self, lambda: PendingLambda) -> FunctionCode {
+        let slots = match self.resolution.definition(lambda.id).kind {
+            resolver::SymbolKind::Function(slots) => slots,
+            resolver::SymbolKind::Variable(_) | resolver::SymbolKind::Global(_) => 0,
+        };
+        let mut captures = Vec::new();
+        for capture in self.resolution.captures(lambda.id) {
+            if let Some(data_type) = self.variables.get(&capture.outer).cloned() {
+                self.variables.insert(capture.inner, data_type);
+            }
+            captures.push(self.resolution.variable_slot(capture.inner).expect("only variables are captured"));
+        }
+        self.compile_body(&lambda.args, &lambda.body, slots, captures)
+    }
+
+    /// compiles the body of the function with the index self.function
+    fn compile_body(&mut self, args: &[VariableBinding], body: &Block, slots: usize, captures: Vec<usize>) -> FunctionCode {
+        self.variable_slots = slots;
+        self.max_hidden_slots = 0;
+
+        for binding in args {
+            self.define(binding);
+        }
+        let found = self.compile_block(body);
+        let signature = &self.signatures[self.function];
+        let (name, data_type) = (signature.name.clone(), signature.data_type());
+        // the value of the body is only returned by functions with a return type
+        match (signature.return_type.clone(), found) {
+            (Some(return_type), found @ (Type::Data(_) | Type::EmptyArray)) => {
+                self.require(return_type, found);
+                self.emit(Instruction::Return);
+            },
+            (Some(_), _) => self.trap(RuntimeError::MissingReturnValue(name.clone())),
+            (None, found) => {
+                self.discard(found);
+                self.emit(Instruction::ReturnVoid);
+            },
+        }
+
+        let code = std::mem::take(&mut self.code);
+        self.function += 1;
+        let slots = slots + self.max_hidden_slots;
+        FunctionCode { name, arity: args.len(), slots, captures, data_type, code }
+    }
+
+    /// compiles a function without arguments which initializes every global variable
+    /// in the order of their declarations. It is compiled after every declared function.
+    fn compile_initializer(&mut self, globals: &[(&VariableBinding, &Expression)]) -> FunctionCode {
+        self.variable_slots = self.resolution.initializer_slots();
+        self.max_hidden_slots = 0;
+
+        for (binding, expr) in globals {
+            let found = self.compile_expression(expr);
+            self.require(binding.data_type.clone(), found);
+            let index = self.resolution.global(binding.id).expect("the resolver gives every global an index");
+            self.emit(Instruction::InitGlobal(index));
+        }
+        self.emit(Instruction::ReturnVoid);
+
+        let code = std::mem::take(&mut self.code);
+        let data_type = self.signatures[self.function].data_type();
+        self.function += 1;
+        let slots = self.variable_slots + self.max_hidden_slots;
+        FunctionCode { name: INITIALIZER.into(), arity: 0, slots, captures: Vec::new(), data_type, code }
+    }
+
+    /// compiles the statements of the block and its value and returns the type of the value.
+    /// A block which never completes has the type Unknown
+    fn compile_block(&mut self, block: &Block) -> Type {
+        for stmt in &block.statements {
+            self.compile_statement(stmt);
+        }
+        match &block.value {
+            Some(value) => self.compile_expression(value),
+            None if type_checker::diverges(block) => Type::Unknown,
+            None => Type::Void,
+        }
+    }
+
+    fn compile_statement(&mut self, stmt: &Statement) {
+        match &stmt.kind {
+            StatementKind::Declaration(binding, expr) | StatementKind::Constant(binding, expr) => {
+                let found = self.compile_expression(expr);
+                self.require(binding.data_type.clone(), found);
+                self.define(binding);
+                let slot = self.binding_slot(binding);
+                self.emit(Instruction::Store(slot));
+            },
+            // structs, enums and impl blocks can only be declared at the top level
+            StatementKind::Struct(..) | StatementKind::Enum(..) | StatementKind::Impl(..) => {},
+            StatementKind::Expression(expr) => {
+                let found = self.compile_expression(expr);
+                self.discard(found);
+            },
+        }
+    }
+
+    /// pops the value of an expression whose value is not used
+    fn discard(&mut self, found: Type) {
+        if let Type::Data(_) | Type::EmptyArray = found {
+            self.emit(Instruction::Pop);
+        }
+    }
+
+    /// the stack has to look the same after every branch, so the first branch decides
+    /// the type of the value. The value of a later branch is popped if the first
+    /// branch has none and a value of another type causes a trap
+    fn join_branch(&mut self, result: &mut Option<Type>, found: Type) {
+        match (&*result, &found) {
+            (None, _) | (Some(Type::Unknown), _) => *result = Some(found),
+            (Some(Type::Void), _) => self.discard(found),
+            (Some(Type::Data(data_type)), _) if !Type::Data(data_type.clone()).accepts(&found) => {
+                self.trap(RuntimeError::TypeMismatch(data_type.clone(), self.type_name(&found)));
+            },
+            _ => {},
+        }
+    }
+
+    /// compiles the expression and returns the type of the value it pushes.
+    /// Type::Void pushes nothing and Type::Unknown always ends with a trap.
+    fn compile_expression(&mut self, expr: &Expression) -> Type {
+        match &expr.kind {
+            ExpressionKind::FnCall(name, args) => self.compile_call(expr, name, args),
+            // functions can only be declared at the top level
+            ExpressionKind::FnDecl(..) => Type::Void,
+            ExpressionKind::Lambda(args, return_type, body) => self.compile_lambda_value(expr, args, return_type.as_ref(), body),
+            ExpressionKind::Call(callee, args) => {
+                let callee = self.compile_expression(callee);
+                let found: Vec<Type> = args.iter().map(|arg| self.compile_expression(arg)).collect();
+                self.compile_value_call(callee, found)
+            },
+            ExpressionKind::UnaryOp(op, operand) => {
+                let operand = self.compile_expression(operand);
+                let instruction = match (op, &operand) {
+                    (_, Type::Unknown) => return Type::Unknown,
+                    (UnOp::Negation, Type::Data(DataType::Boolean)) => Instruction::Not,
+                    (UnOp::Minus, Type::Data(DataType::Integer)) => Instruction::NegInt,
+                    (UnOp::Minus, Type::Data(DataType::Float)) => Instruction::NegFloat,
+                    _ => {
+                        self.trap(RuntimeError::InvalidOperand(*op, self.type_name(&operand)));
+                        return Type::Unknown;
+                    },
+                };
+                self.emit(instruction);
+                operand
+            },
+            ExpressionKind::BinaryOp(left, BinOp::And, right) => {
+                let left = self.compile_expression(left);
+                self.require(DataType::Boolean, left);
+                let jump_to_false = self.emit_jump(Instruction::JumpIfFalse);
+                let right = self.compile_expression(right);
+                self.require(DataType::Boolean, right);
+                let jump_to_end = self.emit_jump(Instruction::Jump);
+                self.patch(jump_to_false);
+                self.emit_constant(Value::Boolean(false));
+                self.patch(jump_to_end);
+                Type::Data(DataType::Boolean)
+            },
+            ExpressionKind::BinaryOp(left, BinOp::Or, right) => {
+                let left = self.compile_expression(left);
+                self.require(DataType::Boolean, left);
+                let jump_to_right = self.emit_jump(Instruction::JumpIfFalse);
+                self.emit_constant(Value::Boolean(true));
+                let jump_to_end = self.emit_jump(Instruction::Jump);
+                self.patch(jump_to_right);
+                let right = self.compile_expression(right);
+                self.require(DataType::Boolean, right);
+                self.patch(jump_to_end);
+                Type::Data(DataType::Boolean)
+            },
+            ExpressionKind::BinaryOp(left, op, right) => {
+                let left = self.compile_expression(left);
+                let right = self.compile_expression(right);
+                self.compile_binary_operation(*op, left, right)
+            },
+            ExpressionKind::If(condition, if_block, else_block) => {
+                let condition = self.compile_expression(condition);
+                self.require(DataType::Boolean, condition);
+                let jump_to_else = self.emit_jump(Instruction::JumpIfFalse);
+                let found = self.compile_block(if_block);
+                match else_block {
+                    Some(else_block) => {
+                        let mut result = Some(found);
+                        let jump_to_end = self.emit_jump(Instruction::Jump);
+                        self.patch(jump_to_else);
+                        let other = self.compile_block(else_block);
+                        self.join_branch(&mut result, other);
+                        self.patch(jump_to_end);
+                        result.unwrap_or(Type::Void)
+                    },
+                    // an if without an else has no value
+                    None => {
+                        self.discard(found);
+                        self.patch(jump_to_else);
+                        Type::Void
+                    },
+                }
+            },
+            ExpressionKind::Symbol(name) if self.resolution.symbol(expr.id).is_none() && self.is_enum_path(name) => {
+                self.compile_variant(name, &[])
+            },
+            // a function used as a value
+            ExpressionKind::Symbol(_) if self.function_index(expr.id).is_some() => {
+                let index = self.function_index(expr.id).expect("the index was checked before");
+                self.emit(Instruction::MakeFunction(index));
+                Type::Data(self.signatures[index].data_type())
+            },
+            ExpressionKind::Symbol(name) => {
+                let load = self.resolution.slot(expr.id).map(Instruction::Load)
+                    .or_else(|| self.resolution.global(expr.id).map(Instruction::LoadGlobal));
+                match (load, self.variable_type(expr.id)) {
+                    (Some(load), Some(data_type)) => {
+                        self.emit(load);
+                        Type::Data(data_type)
+                    },
+                    _ => {
+                        self.trap(RuntimeError::UndefinedVariable(name.clone()));
+                        Type::Unknown
+                    },
+                }
+            },
+            ExpressionKind::Assignment(name, value) => {
+                let found = self.compile_expression(value);
+                let store = self.resolution.slot(expr.id).map(Instruction::Store)
+                    .or_else(|| self.resolution.global(expr.id).map(Instruction::StoreGlobal));
+                match (store, self.variable_type(expr.id)) {
+                    (Some(store), Some(data_type)) => {
+                        self.require(data_type, found);
+                        self.emit(store);
+                    },
+                    _ => self.trap(RuntimeError::UndefinedVariable(name.clone())),
+                }
+                Type::Void
+            },
+            ExpressionKind::Literal(literal) => {
+                let value = Value::from_literal(literal);
+                let data_type = value.data_type().map(Type::Data).unwrap_or(Type::Void);
+                self.emit_constant(value);
+                data_type
+            },
+            ExpressionKind::Break(value) => {
+                self.compile_break(value.as_deref());
+                Type::Void
+            },
+            ExpressionKind::Continue => {
+                if self.loops.is_empty() {
+                    self.trap(RuntimeError::ContinueOutsideLoop);
+                } else {
+                    let jump = self.emit_jump(Instruction::Jump);
+                    if let Some(labels) = self.loops.last_mut() {
+                        labels.continues.push(jump);
+                    }
+                }
+                Type::Void
+            },
+            ExpressionKind::Return(value) => {
+                self.compile_return(value.as_deref());
+                Type::Void
+            },
+            ExpressionKind::WhileLoop(condition, block) => {
+                let start = self.code.len();
+                let condition = self.compile_expression(condition);
+                self.require(DataType::Boolean, condition);
+                let jump_to_end = self.emit_jump(Instruction::JumpIfFalse);
+                let labels = self.compile_loop_body(block, false);
+                self.emit(Instruction::Jump(start));
+                self.patch(jump_to_end);
+                self.patch_loop(labels, start);
+                Type::Void
+            },
+            ExpressionKind::Loop(block) => {
+                let start = self.code.len();
+                let labels = self.compile_loop_body(block, true);
+                self.emit(Instruction::Jump(start));
+                let value = labels.value.clone();
+                self.patch_loop(labels, start);
+                value.unwrap_or(Type::Void)
+            },
+            ExpressionKind::ForLoop(variable, start, end, step, block) => {
+                self.compile_for_loop(variable, start, end, step.as_deref(), block);
+                Type::Void
+            },
+            ExpressionKind::Array(elements) => self.compile_array(elements),
+            ExpressionKind::Index(array, index) => {
+                let array = self.compile_expression(array);
+                let index = self.compile_expression(index);
+                let element = match self.require_array(array) {
+                    Some(element) => element,
+                    None => return Type::Unknown,
+                };
+                self.require(DataType::Integer, index);
+                self.emit(Instruction::Index);
+                element
+            },
+            ExpressionKind::IndexAssignment(array, index, value) => {
+                let array = self.compile_expression(array);
+                let index = self.compile_expression(index);
+                let found = self.compile_expression(value);
+                if let Some(element) = self.require_array(array) {
+                    self.require(DataType::Integer, index);
+                    self.require_element(&element, &found);
+                    self.emit(Instruction::StoreIndex);
+                }
+                Type::Void
+            },
+            ExpressionKind::MethodCall(receiver, name, args) => self.compile_method_call(receiver, name, args),
+            ExpressionKind::StructLiteral(name, fields) => self.compile_struct_literal(name, fields),
+            ExpressionKind::Field(object, field) => {
+                let object = self.compile_expression(object);
+                match self.require_field(object, field) {
+                    Some((position, data_type)) => {
+                        self.emit(Instruction::GetField(position));
+                        Type::Data(data_type)
+                    },
+                    None => Type::Unknown,
+                }
+            },
+            ExpressionKind::FieldAssignment(object, field, value) => {
+                let object = self.compile_expression(object);
+                let found = self.compile_expression(value);
+                if let Some((position, data_type)) = self.require_field(object, field) {
+                    self.require(data_type, found);
+                    self.emit(Instruction::SetField(position));
+                }
+                Type::Void
+            },
+            ExpressionKind::Match(value, arms) => self.compile_match(value, arms),
+            ExpressionKind::Interpolation(parts) => self.compile_interpolation(parts),
+            ExpressionKind::Block(block) => self.compile_block(block),
+        }
+    }
+
+    /// checks if the name is a path like "Shape::Circle" which starts with the name of a declared enum
+    fn is_enum_path(&self, name: &str) -> bool {
+        split_associated_name(name).is_some_and(|(enum_name, _)| self.enums.contains_key(enum_name))
+    }
+
+    /// compiles the creation of a variant, the values of its fields are pushed in their order
+    fn compile_variant(&mut self, name: &str, args: &[Expression]) -> Type {
+        let (enum_name, variant) = split_associated_name(name).expect("the name is a path to a variant");
+        let fields = match self.enums[enum_name].iter().find(|(declared, _)| declared == variant) {
+            Some((_, fields)) => fields.clone(),
+            None => {
+                self.trap(RuntimeError::UndefinedVariant(enum_name.into(), variant.into()));
+                return Type::Unknown;
+            },
+        };
+        let found: Vec<Type> = args.iter().map(|arg| self.compile_expression(arg)).collect();
+        if fields.len() != found.len() {
+            self.trap(RuntimeError::WrongArgumentCount(name.into(), fields.len(), found.len()));
+            return Type::Unknown;
+        }
+        for (expected, found) in fields.into_iter().zip(found) {
+            self.require(expected, found);
+        }
+        let index = self.variant_layout(enum_name, variant, args.len());
+        self.emit(Instruction::MakeVariant(index));
+        Type::Data(DataType::Named(enum_name.into()))
+    }
+
+    /// compiles the arms of a match one after another, the pattern of every arm jumps to the
+    /// next arm if it does not match. The matched value is stored in a hidden slot, so every
+    /// pattern can load it again
+    fn compile_match(&mut self, value: &Expression, arms: &[MatchArm]) -> Type {
+        let matched = match self.compile_expression(value) {
+            Type::Unknown => return Type::Unknown,
+            Type::Void => {
+                self.emit_constant(Value::Void);
+                Type::Void
+            },
+            matched => matched,
+        };
+        let slot = self.variable_slots + self.hidden_slots;
+        self.hidden_slots += 1;
+        self.max_hidden_slots = self.max_hidden_slots.max(self.hidden_slots);
+        self.emit(Instruction::Store(slot));
+
+        let mut result: Option<Type> = None;
+        let mut jumps_to_end = Vec::new();
+        for arm in arms {
+            let mut jumps_to_next = Vec::new();
+            self.compile_pattern(&arm.pattern, slot, &mut Vec::new(), &matched, &mut jumps_to_next);
+            let found = self.compile_expression(&arm.value);
+            self.join_branch(&mut result, found);
+            jumps_to_end.push(self.emit_jump(Instruction::Jump));
+            for jump in jumps_to_next {
+                self.patch(jump);
+            }
+        }
+        self.trap(RuntimeError::NoMatchingArm);
+        for jump in jumps_to_end {
+            self.patch(jump);
+        }
+        self.hidden_slots -= 1;
+        result.unwrap_or(Type::Void)
+    }
+
+    /// compiles the test of a pattern against the value in the slot. The path contains the layout and
+    /// the position of the field of every variant around the pattern. Every emitted jump is taken if the pattern
+    /// does not match and is added to the jumps to the next arm
+    fn compile_pattern(&mut self, pattern: &Pattern, slot: usize, path: &mut Vec<(usize, usize)>, matched: &Type,
+                       jumps_to_next: &mut Vec<usize>) {
+        match &pattern.kind {
+            PatternKind::Wildcard => {},
+            PatternKind::Binding(_) => {
+                self.load_path(slot, path);
+                if let (Some(id), Type::Data(data_type)) = (self.resolution.symbol(pattern.id), matched) {
+                    self.variables.insert(id, data_type.clone());
+                }
+                let binding = self.resolution.slot(pattern.id).expect("the resolver gives every binding a slot");
+                self.emit(Instruction::Store(binding));
+            },
+            PatternKind::Literal(literal) => {
+                let literal = Value::from_literal(literal);
+                let instruction = match (matched, literal.data_type()) {
+                    (Type::Data(found), Some(expected)) if *found == expected => match expected {
+                        DataType::Integer => Instruction::CompareInt(BinOp::Eq),
+                        DataType::Float => Instruction::CompareFloat(BinOp::Eq),
+                        DataType::String => Instruction::CompareString(BinOp::Eq),
+                        _ => Instruction::CompareBoolean(BinOp::Eq),
+                    },
+                    (_, expected) => {
+                        let expected = expected.expect("a literal has a type");
+                        return self.trap(RuntimeError::TypeMismatch(expected, self.type_name(matched)));
+                    },
+                };
+                self.load_path(slot, path);
+                self.emit_constant(literal);
+                self.emit(instruction);
+                jumps_to_next.push(self.emit_jump(Instruction::JumpIfFalse));
+            },
+            PatternKind::Variant(enum_name, variant, fields) => {
+                let declared = match self.enums.get(enum_name) {
+                    Some(declared) => declared,
+                    None => return self.trap(RuntimeError::UndefinedType(enum_name.clone())),
+                };
+                let field_types = match declared.iter().find(|(declared, _)| declared == variant) {
+                    Some((_, field_types)) => field_types.clone(),
+                    None => return self.trap(RuntimeError::UndefinedVariant(enum_name.clone(), variant.clone())),
+                };
+                if field_types.len() != fields.len() {
+                    let name = associated_name(enum_name, variant);
+                    return self.trap(RuntimeError::WrongArgumentCount(name, field_types.len(), fields.len()));
+                }
+                let enum_type = DataType::Named(enum_name.clone());
+                if *matched != Type::Data(enum_type.clone()) {
+                    return self.trap(RuntimeError::TypeMismatch(enum_type, self.type_name(matched)));
+                }
+
+                let index = self.variant_layout(enum_name, variant, fields.len());
+                self.load_path(slot, path);
+                self.emit(Instruction::IsVariant(index));
+                jumps_to_next.push(self.emit_jump(Instruction::JumpIfFalse));
+                for (position, (field, data_type)) in fields.iter().zip(field_types).enumerate() {
+                    path.push((index, position));
+                    self.compile_pattern(field, slot, path, &Type::Data(data_type), jumps_to_next);
+                    path.pop();
+                }
+            },
+        }
+    }
+
+    /// pushes the value in the slot or the field of the variant in it at the end of the path
+    fn load_path(&mut self, slot: usize, path: &[(usize, usize)]) {
+        self.emit(Instruction::Load(slot));
+        for (layout, position) in path {
+            self.emit(Instruction::VariantField(*layout, *position));
+        }
+    }
+
+    /// returns the index of the layout of the variant, every variant has a single layout
+    fn variant_layout(&mut self, name: &str, variant: &str, arity: usize) -> usize {
+        let position = self.variants.iter().position(|layout| layout.name == name && layout.variant == variant);
+        match position {
+            Some(index) => index,
+            None => {
+                self.variants.push(VariantLayout { name: name.into(), variant: variant.into(), arity });
+                self.variants.len() - 1
+            },
+        }
+    }
+
+    /// compiles a struct literal, the values are pushed in the order of the literal
+    /// and sorted into the order of the declaration by the layout of the literal
+    fn compile_struct_literal(&mut self, name: &str, fields: &[(String, Expression)]) -> Type {
+        let declared = match self.structs.get(name) {
+            Some(declared) => declared.clone(),
+            None => {
+                self.trap(RuntimeError::UndefinedType(name.into()));
+                return Type::Unknown;
+            },
+        };
+        let mut order = Vec::with_capacity(fields.len());
+        for (field, value) in fields {
+            let found = self.compile_expression(value);
+            match declared.iter().position(|(declared, _)| declared == field) {
+                Some(position) => {
+                    self.require(declared[position].1.clone(), found);
+                    order.push(position);
+                },
+                None => self.trap(RuntimeError::UndefinedField(name.into(), field.clone())),
+            }
+        }
+        if let Some((missing, _)) = declared.iter().find(|(field, _)| fields.iter().all(|(given, _)| given != field)) {
+            self.trap(RuntimeError::MissingField(name.into(), missing.clone()));
+            return Type::Unknown;
+        }
+
+        let fields = declared.into_iter().map(|(field, _)| field).collect();
+        self.layouts.push(StructLayout { name: name.into(), fields, order });
+        self.emit(Instruction::MakeStruct(self.layouts.len() - 1));
+        Type::Data(DataType::Named(name.into()))
+    }
+
+    /// compiles the parts of an interpolated string, the text of each value is created at runtime
+    fn compile_interpolation(&mut self, parts: &[InterpolationPart]) -> Type {
+        for part in parts {
+            match part {
+                InterpolationPart::Text(text) => self.emit_constant(Value::String(text.clone())),
+                InterpolationPart::Value(value) => match self.compile_expression(value) {
+                    Type::Data(DataType::String) | Type::Data(DataType::Integer) |
+                    Type::Data(DataType::Float) | Type::Data(DataType::Boolean) | Type::Unknown => {},
+                    other => self.trap(RuntimeError::NotInterpolatable(self.type_name(&other))),
+                },
+            }
+        }
+        self.emit(Instruction::Interpolate(parts.len()));
+        Type::Data(DataType::String)
+    }
+
+    /// compiles an array literal, every element has to have the outermost type of the first element
+    fn compile_array(&mut self, elements: &[Expression]) -> Type {
+        let mut element_type: Option<Type> = None;
+        for element in elements {
+            let found = self.compile_expression(element);
+            match &element_type {
+                Some(first) => self.require_element(first, &found),
+                None if found == Type::Void => self.trap(RuntimeError::VoidArrayElement),
+                None => {},
+            }
+            // an element with a known type describes the type better than an empty array
+            if let None | Some(Type::EmptyArray) | Some(Type::Unknown) = element_type {
+                element_type = Some(found);
+            }
+        }
+        self.emit(Instruction::MakeArray(elements.len()));
+        match element_type {
+            Some(Type::Data(data_type)) => Type::Data(DataType::Array(Box::new(data_type))),
+            _ => Type::EmptyArray,
+        }
+    }
+
+    fn compile_method_call(&mut self, receiver: &Expression, name: &str, args: &[Expression]) -> Type {
+        let receiver = self.compile_expression(receiver);
+        let found: Vec<Type> = args.iter().map(|arg| self.compile_expression(arg)).collect();
+        if let Type::Data(DataType::Named(type_name)) = &receiver {
+            return self.compile_user_method_call(type_name, name, found);
+        }
+        let element = match &receiver {
+            Type::Data(DataType::Array(element)) => Type::Data((**element).clone()),
+            Type::EmptyArray => Type::Unknown,
+            Type::Unknown => return Type::Unknown,
+            other => {
+                self.trap(RuntimeError::UndefinedMethod(self.type_name(other), name.into()));
+                return Type::Unknown;
+            },
+        };
+        let (expected, result) = match type_checker::array_method(name, element.clone()) {
+            Some(method) => method,
+            None => {
+                self.trap(RuntimeError::UndefinedMethod(self.type_name(&receiver), name.into()));
+                return Type::Unknown;
+            },
+        };
+        if expected.len() != found.len() {
+            self.trap(RuntimeError::WrongArgumentCount(name.into(), expected.len(), found.len()));
+            return Type::Unknown;
+        }
+
+        let instruction = match name {
+            "len" => Instruction::ArrayLen,
+            "push" => {
+                self.require_element(&element, &found[0]);
+                Instruction::ArrayPush
+            },
+            _ => Instruction::ArrayPop,
+        };
+        self.emit(instruction);
+        result
+    }
+
+    /// calls a method of the impl block of the receiver type,
+    /// the receiver was pushed in front of the arguments
+    fn compile_user_method_call(&mut self, type_name: &str, name: &str, found: Vec<Type>) -> Type {
+        let index = self.resolution.method(type_name, name)
+            .and_then(|id| self.function_indices.get(&id).copied())
+            .filter(|index| self.signatures[*index].method);
+        let index = match index {
+            Some(index) => index,
+            None => {
+                let receiver = self.type_name(&Type::Data(DataType::Named(type_name.into())));
+                self.trap(RuntimeError::UndefinedMethod(receiver, name.into()));
+                return Type::Unknown;
+            },
+        };
+        let signature = &self.signatures[index];
+        let (expected, return_type) = (signature.args[1..].to_vec(), signature.return_type.clone());
+        if expected.len() != found.len() {
+            // like in the interpreter the receiver is counted as argument
+            let e = RuntimeError::WrongArgumentCount(signature.name.clone(), expected.len() + 1, found.len() + 1);
+            self.trap(e);
+            return Type::Unknown;
+        }
+        for (expected, found) in expected.into_iter().zip(found) {
+            self.require(expected, found);
+        }
+        self.emit(Instruction::Call(index));
+        Type::from(return_type)
+    }
+
+    /// compiles the creation of a lambda, the captured values are pushed in the order of the captures.
+    /// The body of the lambda is compiled after the current function
+    fn compile_lambda_value(&mut self, expr: &Expression, args: &[VariableBinding], return_type: Option<&DataType>,
+                            body: &Block) -> Type {
+        let id = self.resolution.symbol(expr.id).expect("the resolver gives every lambda a symbol");
+        let index = match self.function_indices.get(&id) {
+            Some(index) => *index,
+            None => {
+                let signature = Signature {
+                    name: LAMBDA.into(),
+                    args: args.iter().map(|binding| binding.data_type.clone()).collect(),
+                    return_type: return_type.cloned(),
+                    method: false,
+                };
+                self.signatures.push(signature);
+                self.function_indices.insert(id, self.signatures.len() - 1);
+                self.lambdas.push_back(PendingLambda { id, args: args.to_vec(), body: body.clone() });
+                self.signatures.len() - 1
+            },
+        };
+        let slots: Vec<usize> = self.resolution.captures(id).iter()
+            .map(|capture| self.resolution.variable_slot(capture.outer).expect("only variables are captured"))
+            .collect();
+        for slot in slots {
+            self.emit(Instruction::Load(slot));
+        }
+        self.emit(Instruction::MakeFunction(index));
+        Type::Data(self.signatures[index].data_type())
+    }
+
+    /// compiles the call of a function value whose arguments were pushed after it.
+    /// The number of arguments is checked when the call is executed, so the error
+    /// contains the name of the called function
+    fn compile_value_call(&mut self, callee: Type, found: Vec<Type>) -> Type {
+        match callee {
+            Type::Data(DataType::Function(expected, return_type)) => {
+                let argc = found.len();
+                if expected.len() == argc {
+                    for (expected, found) in expected.into_iter().zip(found) {
+                        self.require(expected, found);
+                    }
+                }
+                self.emit(Instruction::CallValue(argc));
+                Type::from(return_type.map(|return_type| *return_type))
+            },
+            Type::Unknown => Type::Unknown,
+            other => {
+                self.trap(RuntimeError::NotCallable(self.type_name(&other)));
+                Type::Unknown
+            },
+        }
+    }
+
+    fn compile_call(&mut self, expr: &Expression, name: &str, args: &[Expression]) -> Type {
+        // a variable which holds a function
+        if let (Some(_), None) = (self.resolution.symbol(expr.id), self.function_index(expr.id)) {
+            let callee = self.compile_expression(&Expression::new(expr.id, ExpressionKind::Symbol(name.into()), expr.span));
+            let found: Vec<Type> = args.iter().map(|arg| self.compile_expression(arg)).collect();
+            return self.compile_value_call(callee, found);
+        }
+        let index = match self.function_index(expr.id) {
+            Some(index) => index,
+            None if self.is_enum_path(name) => return self.compile_variant(name, args),
+            None if Native::lookup(name).is_some() => {
+                let native = Native::lookup(name).expect("the native was found before");
+                return self.compile_native_call(native, args);
+            },
+            None => {
+                self.trap(RuntimeError::UndefinedFunction(name.into()));
+                return Type::Unknown;
+            },
+        };
+        let found: Vec<Type> = args.iter().map(|arg| self.compile_expression(arg)).collect();
+
+        let signature = &self.signatures[index];
+        let (expected, return_type) = (signature.args.clone(), signature.return_type.clone());
+        if expected.len() != found.len() {
+            let e = RuntimeError::WrongArgumentCount(signature.name.clone(), expected.len(), found.len());
+            self.trap(e);
+            return Type::Unknown;
+        }
+        for (expected, found) in expected.into_iter().zip(found) {
+            self.require(expected, found);
+        }
+        self.emit(Instruction::Call(index));
+        Type::from(return_type)
+    }
+
+    /// compiles the call of a function of the standard library
+    fn compile_native_call(&mut self, native: Native, args: &[Expression]) -> Type {
+        let found: Vec<Type> = args.iter().map(|arg| self.compile_expression(arg)).collect();
+        let (expected, return_type) = native.overload(&found);
+        if expected.len() != found.len() {
+            self.trap(RuntimeError::WrongArgumentCount(native.name().into(), expected.len(), found.len()));
+            return Type::Unknown;
+        }
+        for (expected, found) in expected.into_iter().zip(found) {
+            self.require(expected, found);
+        }
+        self.emit(Instruction::CallNative(native));
+        Type::from(return_type)
+    }
+
+    fn compile_binary_operation(&mut self, op: BinOp, left: Type, right: Type) -> Type {
+        let (data_type, instruction) = match (op, &left, &right) {
+            (_, Type::Unknown, _) | (_, _, Type::Unknown) => return Type::Unknown,
+            (BinOp::Plus, Type::Data(DataType::Integer), Type::Data(DataType::Integer)) => (left.clone(), Instruction::AddInt),
+            (BinOp::Minus, Type::Data(DataType::Integer), Type::Data(DataType::Integer)) => (left.clone(), Instruction::SubInt),
+            (BinOp::Multi, Type::Data(DataType::Integer), Type::Data(DataType::Integer)) => (left.clone(), Instruction::MulInt),
+            (BinOp::Divide, Type::Data(DataType::Integer), Type::Data(DataType::Integer)) => (left.clone(), Instruction::DivInt),
+            (BinOp::Modulo, Type::Data(DataType::Integer), Type::Data(DataType::Integer)) => (left.clone(), Instruction::ModInt),
+            (BinOp::Plus, Type::Data(DataType::Float), Type::Data(DataType::Float)) => (left.clone(), Instruction::AddFloat),
+            (BinOp::Minus, Type::Data(DataType::Float), Type::Data(DataType::Float)) => (left.clone(), Instruction::SubFloat),
+            (BinOp::Multi, Type::Data(DataType::Float), Type::Data(DataType::Float)) => (left.clone(), Instruction::MulFloat),
+            (BinOp::Divide, Type::Data(DataType::Float), Type::Data(DataType::Float)) => (left.clone(), Instruction::DivFloat),
+            (BinOp::Modulo, Type::Data(DataType::Float), Type::Data(DataType::Float)) => (left.clone(), Instruction::ModFloat),
+            (BinOp::Plus, Type::Data(DataType::String), Type::Data(DataType::String)) => (left.clone(), Instruction::Concat),
+            (BinOp::Eq, Type::Data(DataType::Boolean), Type::Data(DataType::Boolean)) |
+            (BinOp::Neq, Type::Data(DataType::Boolean), Type::Data(DataType::Boolean)) => {
+                (Type::Data(DataType::Boolean), Instruction::CompareBoolean(op))
+            },
+            (BinOp::Eq, Type::Data(l), Type::Data(r)) | (BinOp::Neq, Type::Data(l), Type::Data(r)) |
+            (BinOp::Gt, Type::Data(l), Type::Data(r)) | (BinOp::Ge, Type::Data(l), Type::Data(r)) |
+            (BinOp::Lt, Type::Data(l), Type::Data(r)) | (BinOp::Le, Type::Data(l), Type::Data(r)) if l == r => {
+                let instruction = match l {
+                    DataType::Integer => Instruction::CompareInt(op),
+                    DataType::Float => Instruction::CompareFloat(op),
+                    DataType::String => Instruction::CompareString(op),
+                    DataType::Boolean | DataType::Array(_) | DataType::Named(_) | DataType::Function(..) => {
+                        return self.invalid_operands(op, left, right);
+                    },
+                };
+                (Type::Data(DataType::Boolean), instruction)
+            },
+            _ => return self.invalid_operands(op, left, right),
+        };
+        self.emit(instruction);
+        data_type
+    }
+
+    fn invalid_operands(&mut self, op: BinOp, left: Type, right: Type) -> Type {
+        self.trap(RuntimeError::InvalidOperands(op, self.type_name(&left), self.type_name(&right)));
+        Type::Unknown
+    }
+
+    fn compile_return(&mut self, value: Option<&Expression>) {
+        let signature = &self.signatures[self.function];
+        let (name, return_type) = (signature.name.clone(), signature.return_type.clone());
+        let found = value.map(|value| self.compile_expression(value));
+        match (return_type, found) {
+            (_, Some(Type::Unknown)) => {},
+            (None, None) | (None, Some(Type::Void)) => self.emit(Instruction::ReturnVoid),
+            (None, Some(_)) => self.trap(RuntimeError::UnexpectedReturnValue(name)),
+            (Some(_), None) | (Some(_), Some(Type::Void)) => self.trap(RuntimeError::MissingReturnValue(name)),
+            (Some(data_type), Some(found)) => {
+                self.require(data_type, found);
+                self.emit(Instruction::Return);
+            },
+        }
+    }
+
+    fn compile_for_loop(&mut self, variable: &VariableBinding, start: &Expression, end: &Expression,
+                        step: Option<&Expression>, block: &Block) {
+        let counter = self.variable_slots + self.hidden_slots;
+        let (end_slot, step_slot) = (counter + 1, counter + 2);
+        self.hidden_slots += FOR_LOOP_SLOTS;
+        self.max_hidden_slots = self.max_hidden_slots.max(self.hidden_slots);
+
+        for (bound, slot) in [(start, counter), (end, end_slot)].iter() {
+            let found = self.compile_expression(bound);
+            self.require(DataType::Integer, found);
+            self.emit(Instruction::Store(*slot));
+        }
+        match step {
+            Some(step) => {
+                let found = self.compile_expression(step);
+                self.require(DataType::Integer, found);
+            },
+            None => self.emit_constant(Value::Integer(1)),
+        }
+        self.emit(Instruction::Store(step_slot));
+        self.emit(Instruction::Load(step_slot));
+        self.emit_constant(Value::Integer(0));
+        self.emit(Instruction::CompareInt(BinOp::Eq));
+        let jump_to_start = self.emit_jump(Instruction::JumpIfFalse);
+        self.trap(RuntimeError::ZeroStep);
+        self.patch(jump_to_start);
+
+        // counts up to the end with a positive step and down with a negative step
+        let condition = self.code.len();
+        self.emit(Instruction::Load(step_slot));
+        self.emit_constant(Value::Integer(0));
+        self.emit(Instruction::CompareInt(BinOp::Gt));
+        let jump_to_down = self.emit_jump(Instruction::JumpIfFalse);
+        self.emit(Instruction::Load(counter));
+        self.emit(Instruction::Load(end_slot));
+        self.emit(Instruction::CompareInt(BinOp::Lt));
+        let jump_to_check = self.emit_jump(Instruction::Jump);
+        self.patch(jump_to_down);
+        self.emit(Instruction::Load(counter));
+        self.emit(Instruction::Load(end_slot));
+        self.emit(Instruction::CompareInt(BinOp::Gt));
+        self.patch(jump_to_check);
+        let jump_to_end = self.emit_jump(Instruction::JumpIfFalse);
+
+        self.emit(Instruction::Load(counter));
+        self.require(variable.data_type.clone(), Type::Data(DataType::Integer));
+        self.define(variable);
+        let slot = self.binding_slot(variable);
+        self.emit(Instruction::Store(slot));
+        let labels = self.compile_loop_body(block, false);
+
+        // the loop ends when the next value of the counter does not fit into an int
+        let increment = self.code.len();
+        self.emit(Instruction::Load(counter));
+        self.emit(Instruction::Load(step_slot));
+        self.emit(Instruction::CheckedAddInt);
+        let jump_on_overflow = self.emit_jump(Instruction::JumpIfFalse);
+        self.emit(Instruction::Store(counter));
+        self.emit(Instruction::Jump(condition));
+        self.patch(jump_to_end);
+        self.patch(jump_on_overflow);
+        self.patch_loop(labels, increment);
+        self.hidden_slots -= FOR_LOOP_SLOTS;
+    }
+
+    fn compile_loop_body(&mut self, block: &Block, has_value: bool) -> LoopLabels {
+        self.loops.push(LoopLabels { has_value, ..LoopLabels::default() });
+        let found = self.compile_block(block);
+        self.discard(found);
+        self.loops.pop().expect("loop labels were pushed before")
+    }
+
+    /// compiles a jump to the end of the innermost loop. The value of the break is left
+    /// on the stack for a "loop", the first break decides the type of its value
+    fn compile_break(&mut self, value: Option<&Expression>) {
+        let found = value.map_or(Type::Void, |value| self.compile_expression(value));
+        let mut labels = match self.loops.pop() {
+            Some(labels) => labels,
+            None => return self.trap(RuntimeError::BreakOutsideLoop),
+        };
+        if labels.has_value {
+            self.join_branch(&mut labels.value, found);
+        } else {
+            self.discard(found);
+        }
+        labels.breaks.push(self.emit_jump(Instruction::Jump));
+        self.loops.push(labels);
+    }
+
+    /// sets the target of every continue to the next iteration and of every break
+    /// to the next emitted instruction
+    fn patch_loop(&mut self, labels: LoopLabels, next_iteration: usize) {
+        for jump in labels.continues {
+            self.patch_to(jump, next_iteration);
+        }
+        for jump in labels.breaks {
+            self.patch(jump);
+        }
+    }
+
+    /// emits a trap if the found type is not the expected type
+    fn require(&mut self, expected: DataType, found: Type) {
+        if !Type::Data(expected.clone()).accepts(&found) {
+            self.trap(RuntimeError::TypeMismatch(expected, self.type_name(&found)));
+        }
+    }
+
+    /// emits a trap if the found type is not an array and returns the type of its elements
+    fn require_array(&mut self, found: Type) -> Option<Type> {
+        match found {
+            Type::Data(DataType::Array(element)) => Some(Type::Data(*element)),
+            Type::EmptyArray | Type::Unknown => Some(Type::Unknown),
+            other => {
+                self.trap(RuntimeError::NotIndexable(self.type_name(&other)));
+                None
+            },
+        }
+    }
+
+    /// emits a trap if a value of the found type can not be stored next to an element
+    /// of the array. Like in the interpreter only the outermost types are compared
+    fn require_element(&mut self, element: &Type, found: &Type) {
+        if *found == Type::Void {
+            self.trap(RuntimeError::VoidArrayElement);
+        } else if *element != Type::Unknown && *found != Type::Unknown && self.type_name(element) != self.type_name(found) {
+            self.trap(RuntimeError::ArrayElementMismatch(self.type_name(element), self.type_name(found)));
+        }
+    }
+
+    /// emits a trap if a value of the found type has no field with this name
+    /// and returns the position and the type of the field
+    fn require_field(&mut self, found: Type, field: &str) -> Option<(usize, DataType)> {
+        let name = match &found {
+            Type::Data(DataType::Named(name)) => name,
+            Type::Unknown => return None,
+            other => {
+                self.trap(RuntimeError::UndefinedField(self.type_name(other).into(), field.into()));
+                return None;
+            },
+        };
+        let position = self.structs.get(name)
+            .and_then(|fields| fields.iter().position(|(declared, _)| declared == field));
+        match position {
+            Some(position) => Some((position, self.structs[name][position].1.clone())),
+            None => {
+                self.trap(RuntimeError::UndefinedField(name.clone(), field.into()));
+                None
+            },
+        }
+    }
+
+    /// returns the index of the function the node refers to
+    fn function_index(&self, node: NodeId) -> Option<usize> {
+        self.resolution.symbol(node).and_then(|id| self.function_indices.get(&id).copied())
+    }
+
+    fn define(&mut self, binding: &VariableBinding) {
+        if let Some(id) = self.resolution.symbol(binding.id) {
+            self.variables.insert(id, binding.data_type.clone());
+        }
+    }
+
+    fn variable_type(&self, node: NodeId) -> Option<DataType> {
+        self.resolution.symbol(node).and_then(|id| self.variables.get(&id).cloned())
+    }
+
+    fn binding_slot(&self, binding: &VariableBinding) -> usize {
+        self.resolution.slot(binding.id).expect("the resolver gives every binding a slot")
+    }
+
+    fn emit(&mut self, instruction: Instruction) {
+        self.code.push(instruction);
+    }
+
+    fn emit_constant(&mut self, value: Value) {
+        let index = match self.constants.iter().position(|constant| constant == &value) {
+            Some(index) => index,
+            None => {
+                self.constants.push(value);
+                self.constants.len() - 1
+            },
+        };
+        self.emit(Instruction::Constant(index));
+    }
+
+    /// emits a jump with an unknown target, the target is set by patch
+    fn emit_jump(&mut self, jump: fn(usize) -> Instruction) -> usize {
+        self.emit(jump(usize::MAX));
+        self.code.len() - 1
+    }
+
+    /// sets the target of the jump to the next emitted instruction
+    fn patch(&mut self, jump: usize) {
+        self.patch_to(jump, self.code.len());
+    }
+
+    fn patch_to(&mut self, jump: usize, target: usize) {
+        self.code[jump] = match self.code[jump] {
+            Instruction::Jump(_) => Instruction::Jump(target),
+            Instruction::JumpIfFalse(_) => Instruction::JumpIfFalse(target),
+            other => unreachable!("{:?} is not a jump", other),
+        };
+    }
+
+    fn trap(&mut self, e: RuntimeError) {
+        self.errors.push(e);
+        self.emit(Instruction::Trap(self.errors.len() - 1));
+    }
+
+    /// name of the type like it is returned by Value::type_name
+    fn type_name(&self, found: &Type) -> &'static str {
+        match found {
+            Type::Data(DataType::Integer) => "int",
+            Type::Data(DataType::Float) => "float",
+            Type::Data(DataType::Boolean) => "boolean",
+            Type::Data(DataType::String) => "string",
+            Type::Data(DataType::Array(_)) | Type::EmptyArray => "array",
+            Type::Data(DataType::Named(name)) if self.enums.contains_key(name) => "enum",
+            Type::Data(DataType::Named(_)) => "struct",
+            Type::Data(DataType::Function(..)) => "fn",
+            Type::Void | Type::Unknown => "void",
+        }
+    }
+}
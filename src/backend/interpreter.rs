@@ -1,33 +1,1056 @@
+use crate::frontend::syntax::DataType;
+use crate::frontend::syntax::ast::*;
+use crate::frontend::semantic::resolver::{self, Resolution, SymbolKind};
+use crate::frontend::semantic::type_checker::{self, Type};
+use crate::frontend::semantic::natives::Native;
+use crate::frontend::semantic::ENTRY_POINT;
+use crate::backend::value::{self, Instance, Value};
 
-use frontend::syntax::{DataValue,DataType,ast::*};
-
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
+use std::rc::Rc;
+use std::fmt;
+
+/// Maximum number of nested function calls, deeper calls end with a StackOverflow
+pub const MAX_CALL_DEPTH: usize = 10_000;
+
+/// Upper bound of the stack used by one nested function call. A call needs several recursive
+/// calls of evaluate, a debug build uses about 21 KiB for a call in a few nested blocks
+const FRAME_STACK_BOUND: usize = 32 * 1024;
+
+/// Stack which stays free for the innermost call and the code which started the interpreter
+const STACK_RESERVE: usize = 1 << 20;
+
+/// Size of the stack of the thread which runs the interpreter, it is large enough for MAX_CALL_DEPTH nested calls
+pub const STACK_SIZE: usize = MAX_CALL_DEPTH * FRAME_STACK_BOUND + STACK_RESERVE;
+
+/// Stack size of a thread which was not started by with_interpreter_stack, the default of spawned threads
+const DEFAULT_STACK_SIZE: usize = 2 << 20;
+
+thread_local! {
+    /// size of the stack of the current thread
+    static THREAD_STACK_SIZE: Cell<usize> = const { Cell::new(DEFAULT_STACK_SIZE) };
+}
+
+/// runs the function on a thread with a stack of STACK_SIZE bytes and returns its result,
+/// on other threads the interpreter ends with a StackOverflow before MAX_CALL_DEPTH nested calls
+pub fn with_interpreter_stack<T: Send, F: FnOnce() -> T + Send>(f: F) -> T {
+    std::thread::scope(|scope| {
+        std::thread::Builder::new()
+            .name("interpreter".into())
+            .stack_size(STACK_SIZE)
+            .spawn_scoped(scope, || {
+                THREAD_STACK_SIZE.with(|size| size.set(STACK_SIZE));
+                f()
+            })
+            .expect("could not spawn the interpreter thread")
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+    })
+}
+
+/// address of the top of the stack of the current thread
+#[inline(never)]
+fn stack_address() -> usize {
+    let marker = 0u8;
+    &marker as *const u8 as usize
+}
 
 /// Takes an AbstractSyntaxTree and executes it at runtime.
 pub struct RuntimeInterpreter {
-    /// table of symbols and its values on the heap
-    symbol_table: HashMap<SymbolId, DataValue>,
+    /// every declared function by its symbol
+    functions: HashMap<SymbolId, Rc<Function>>,
+    /// fields of every declared struct by the name of the struct
+    structs: HashMap<String, Vec<VariableBinding>>,
+    /// variants of every declared enum by the name of the enum
+    enums: HashMap<String, Vec<Variant>>,
+    /// symbols and variable slots of every name in the AST
+    resolution: Resolution,
+    /// call stack, the last frame belongs to the currently executed function
+    frames: Vec<Frame>,
+    /// values of the global variables by their index, Void until their declaration was executed
+    globals: Vec<Value>,
+    /// stack address where the execution started and the number of bytes the calls may use below it
+    stack_base: usize,
+    stack_limit: usize,
     ast: AbstractSyntaxTree,
-    pos: NodeId,
 }
 
+/// A function which was declared by a FnDecl expression or a Lambda
+struct Function {
+    name: String,
+    args: Vec<VariableBinding>,
+    return_type: Option<DataType>,
+    body: Block,
+    /// number of variable slots in the call frame of this function
+    slots: usize,
+    /// slots of the values captured by a lambda, in the order of the captures
+    captures: Vec<usize>,
+}
+
+impl Function {
+
+    /// checks if the first argument is the receiver "self" of a method
+    fn is_method(&self) -> bool {
+        self.args.first().is_some_and(|binding| binding.symbol == SELF)
+    }
+
+    /// returns the type of the function used as a value
+    fn data_type(&self) -> DataType {
+        let args = self.args.iter().map(|binding| binding.data_type.clone()).collect();
+        DataType::Function(args, self.return_type.clone().map(Box::new))
+    }
+}
+
+/// Call frame of a single function call.
+/// Every variable of the function is stored in the slot the Resolver gave it.
+struct Frame {
+    slots: Vec<Value>,
+}
+
+/// Reasons why the normal execution of a block has to be interrupted.
+/// Break, Continue and Return are consumed by the enclosing loop or function call
+enum Unwind {
+    Break(Value),
+    Continue,
+    Return(Value),
+    Error(RuntimeError),
+}
+
+impl From<RuntimeError> for Unwind {
+    fn from(e: RuntimeError) -> Self {
+        Unwind::Error(e)
+    }
+}
+
+type Execution = Result<Value, Unwind>;
+
 impl RuntimeInterpreter {
 
-    /// creates a new RuntimeInterpreter Object with an AbstractSyntaxTree to execute
+    /// creates a new RuntimeInterpreter Object with an AbstractSyntaxTree to execute.
+    /// Names which can not be resolved only cause a RuntimeError when they are evaluated.
     pub fn new(ast: AbstractSyntaxTree) -> Self{
-        RuntimeInterpreter{symbol_table: HashMap::new(),ast,pos: 0}
+        let (resolution, _) = resolver::resolve(&ast);
+        let globals = vec![Value::Void; resolution.global_count()];
+        RuntimeInterpreter{functions: HashMap::new(), structs: HashMap::new(), enums: HashMap::new(), resolution, frames: Vec::new(), globals, stack_base: 0, stack_limit: 0, ast}
     }
 
     /// starts the RuntimeInterpreter and executes the program described by the AbstractSyntaxTree.
     /// This method is self consuming and the interpreter only stops after completed the execution or
-    /// an RuntimeError occurs
-    pub fn start(self) -> Result<(),RuntimeError> {
-        //TODO
-        Err(RuntimeError::NullPointerError)
+    /// an RuntimeError occurs. The global variables are initialized in the order of their
+    /// declarations before the main function is called. Returns the value returned by the main function.
+    pub fn start(mut self) -> Result<Value,RuntimeError> {
+        self.measure_stack();
+        let ast = std::mem::replace(&mut self.ast, AbstractSyntaxTree::new(Vec::new()));
+        let mut globals = Vec::new();
+        for stmt in &ast.nodes {
+            match &stmt.kind {
+                StatementKind::Expression(decl @ Expression { kind: ExpressionKind::FnDecl(..), .. }) => {
+                    self.declare_function(decl);
+                },
+                StatementKind::Declaration(binding, expr) | StatementKind::Constant(binding, expr) => {
+                    globals.push((binding, expr));
+                },
+                StatementKind::Struct(name, fields) => {
+                    self.structs.insert(name.clone(), fields.clone());
+                },
+                StatementKind::Enum(name, variants) => {
+                    self.enums.insert(name.clone(), variants.clone());
+                },
+                StatementKind::Impl(_, functions) => functions.iter().for_each(|decl| self.declare_function(decl)),
+                _ => return Err(RuntimeError::InvalidTopLevelStatement),
+            }
+        }
+
+        let main = match self.resolution.function(ENTRY_POINT).and_then(|id| self.functions.get(&id)) {
+            Some(f) => Rc::clone(f),
+            None => return Err(RuntimeError::MissingMainFunction),
+        };
+        // the values of the globals are evaluated in a call frame for the variables declared in them
+        self.frames.push(Frame { slots: vec![Value::Void; self.resolution.initializer_slots()] });
+        for (binding, expr) in globals {
+            self.initialize_global(binding, expr)?;
+        }
+        self.frames.pop();
+        self.call_function(&main, Vec::new(), Vec::new())
+    }
+
+    /// creates a RuntimeInterpreter for an interactive session without a program.
+    /// The inputs of the session are executed in a single call frame which lives
+    /// as long as the interpreter.
+    pub fn session() -> Self {
+        let frame = Frame { slots: Vec::new() };
+        let ast = AbstractSyntaxTree::new(Vec::new());
+        RuntimeInterpreter{functions: HashMap::new(), structs: HashMap::new(), enums: HashMap::new(), resolution: Resolution::default(), frames: vec![frame], globals: Vec::new(), stack_base: 0, stack_limit: 0, ast}
+    }
+
+    /// executes a single statement of an interactive session and returns the value of
+    /// an expression statement. The resolution has to contain every name of the statement
+    /// and the call frame of the session grows to the given number of slots. Variables and
+    /// constants are stored as globals, so the functions of the session can use them.
+    pub fn execute_input(&mut self, stmt: &Statement, resolution: Resolution, slots: usize) -> Result<Value,RuntimeError> {
+        self.resolution = resolution;
+        self.measure_stack();
+        let frame = self.current_frame()?;
+        if frame.slots.len() < slots {
+            frame.slots.resize(slots, Value::Void);
+        }
+        if self.globals.len() < self.resolution.global_count() {
+            self.globals.resize(self.resolution.global_count(), Value::Void);
+        }
+        let result = match &stmt.kind {
+            StatementKind::Expression(expr) => self.evaluate(expr),
+            StatementKind::Declaration(binding, expr) | StatementKind::Constant(binding, expr) => {
+                self.initialize_global(binding, expr).map(|_| Value::Void).map_err(Unwind::Error)
+            },
+            StatementKind::Struct(..) | StatementKind::Enum(..) | StatementKind::Impl(..) => self.execute_statement(stmt),
+        };
+        match result {
+            Ok(value) | Err(Unwind::Return(value)) => Ok(value),
+            Err(Unwind::Break(_)) => Err(RuntimeError::BreakOutsideLoop),
+            Err(Unwind::Continue) => Err(RuntimeError::ContinueOutsideLoop),
+            Err(Unwind::Error(e)) => Err(e),
+        }
+    }
+
+    /// remembers where the execution starts on the stack of the current thread, the nested calls
+    /// may use the stack of the thread without the STACK_RESERVE
+    fn measure_stack(&mut self) {
+        self.stack_base = stack_address();
+        self.stack_limit = THREAD_STACK_SIZE.with(Cell::get).saturating_sub(STACK_RESERVE);
+    }
+
+    /// registers a function under the symbol the Resolver gave its declaration.
+    /// The function is named like its symbol, so functions of impl blocks have their full name
+    fn declare_function(&mut self, decl: &Expression) {
+        if let ExpressionKind::FnDecl(_, body, args, return_type) = &decl.kind {
+            let id = match self.resolution.symbol(decl.id) {
+                Some(id) => id,
+                None => return,
+            };
+            let symbol = self.resolution.definition(id);
+            let slots = match symbol.kind {
+                SymbolKind::Function(slots) => slots,
+                SymbolKind::Variable(_) | SymbolKind::Global(_) => return,
+            };
+            let function = Function {
+                name: symbol.name.clone(),
+                args: args.clone().unwrap_or_default(),
+                return_type: return_type.clone(),
+                body: body.clone(),
+                slots,
+                captures: Vec::new(),
+            };
+            self.functions.insert(id, Rc::new(function));
+        }
+    }
+
+    /// evaluates the value of a global variable and stores it at the index of the global
+    fn initialize_global(&mut self, binding: &VariableBinding, expr: &Expression) -> Result<(),RuntimeError> {
+        let value = match self.evaluate(expr) {
+            Ok(value) => value,
+            Err(Unwind::Error(e)) => return Err(e),
+            Err(_) => return Err(RuntimeError::InvalidTopLevelStatement),
+        };
+        expect_type(&binding.data_type, &value)?;
+        let index = self.resolution.global(binding.id).expect("the resolver gives every global an index");
+        self.globals[index] = value;
+        Ok(())
+    }
+
+    /// binds the arguments to the parameters of the function, stores the values captured by a lambda
+    /// in their slots and executes its body
+    fn call_function(&mut self, function: &Function, args: Vec<Value>, captured: Vec<Value>) -> Result<Value,RuntimeError> {
+        let name = function.name.as_str();
+        if args.len() != function.args.len() {
+            return Err(RuntimeError::WrongArgumentCount(name.into(), function.args.len(), args.len()));
+        }
+        if self.frames.len() >= MAX_CALL_DEPTH || self.stack_base.abs_diff(stack_address()) > self.stack_limit {
+            return Err(RuntimeError::StackOverflow);
+        }
+
+        let mut frame = Frame { slots: vec![Value::Void; function.slots] };
+        for (binding, value) in function.args.iter().zip(args) {
+            expect_type(&binding.data_type, &value)?;
+            let slot = self.binding_slot(binding);
+            frame.slots[slot] = value;
+        }
+        for (slot, value) in function.captures.iter().zip(captured) {
+            frame.slots[*slot] = value;
+        }
+
+        self.frames.push(frame);
+        let result = self.execute_block(&function.body);
+        self.frames.pop();
+
+        let value = match result {
+            // the value of the body is only returned by functions with a return type
+            Ok(value) if function.return_type.is_some() => value,
+            Ok(_) => Value::Void,
+            Err(Unwind::Return(value)) => value,
+            Err(Unwind::Break(_)) => return Err(RuntimeError::BreakOutsideLoop),
+            Err(Unwind::Continue) => return Err(RuntimeError::ContinueOutsideLoop),
+            Err(Unwind::Error(e)) => return Err(e),
+        };
+
+        match (&function.return_type, &value) {
+            (Some(_), Value::Void) => Err(RuntimeError::MissingReturnValue(name.into())),
+            (Some(data_type), _) => {
+                expect_type(data_type, &value)?;
+                Ok(value)
+            },
+            (None, Value::Void) => Ok(value),
+            (None, _) => Err(RuntimeError::UnexpectedReturnValue(name.into())),
+        }
+    }
+
+    /// executes the statements of the block and evaluates its value
+    fn execute_block(&mut self, block: &Block) -> Execution {
+        block.statements.iter()
+            .try_for_each(|stmt| self.execute_statement(stmt).map(drop))?;
+        match &block.value {
+            Some(value) => self.evaluate(value),
+            None => Ok(Value::Void),
+        }
+    }
+
+    fn execute_statement(&mut self, stmt: &Statement) -> Execution {
+        match &stmt.kind {
+            StatementKind::Declaration(binding, expr) | StatementKind::Constant(binding, expr) => {
+                let value = self.evaluate(expr)?;
+                expect_type(&binding.data_type, &value)?;
+                let slot = self.binding_slot(binding);
+                self.current_frame()?.slots[slot] = value;
+                Ok(Value::Void)
+            },
+            StatementKind::Struct(name, fields) => {
+                self.structs.insert(name.clone(), fields.clone());
+                Ok(Value::Void)
+            },
+            StatementKind::Enum(name, variants) => {
+                self.enums.insert(name.clone(), variants.clone());
+                Ok(Value::Void)
+            },
+            StatementKind::Impl(_, functions) => {
+                functions.iter().for_each(|decl| self.declare_function(decl));
+                Ok(Value::Void)
+            },
+            StatementKind::Expression(expr) => self.evaluate(expr).map(|_| Value::Void),
+        }
+    }
+
+    fn evaluate(&mut self, expr: &Expression) -> Execution {
+        match &expr.kind {
+            ExpressionKind::FnCall(name, args) => self.evaluate_call(expr.id, name, args),
+            ExpressionKind::FnDecl(..) => {
+                self.declare_function(expr);
+                Ok(Value::Void)
+            },
+            ExpressionKind::Lambda(args, return_type, body) => self.evaluate_lambda(expr.id, args, return_type.as_ref(), body),
+            ExpressionKind::Call(callee, args) => self.evaluate_value_call(callee, args),
+            ExpressionKind::UnaryOp(op, operand) => {
+                let value = self.evaluate(operand)?;
+                Ok(unary_operation(*op, value)?)
+            },
+            ExpressionKind::BinaryOp(left, BinOp::And, right) => {
+                if self.evaluate_condition(left)? {
+                    Ok(Value::Boolean(self.evaluate_condition(right)?))
+                } else {
+                    Ok(Value::Boolean(false))
+                }
+            },
+            ExpressionKind::BinaryOp(left, BinOp::Or, right) => {
+                if self.evaluate_condition(left)? {
+                    Ok(Value::Boolean(true))
+                } else {
+                    Ok(Value::Boolean(self.evaluate_condition(right)?))
+                }
+            },
+            ExpressionKind::BinaryOp(left, op, right) => {
+                let left = self.evaluate(left)?;
+                let right = self.evaluate(right)?;
+                Ok(binary_operation(*op, left, right)?)
+            },
+            ExpressionKind::If(condition, if_block, else_block) => {
+                if self.evaluate_condition(condition)? {
+                    let value = self.execute_block(if_block)?;
+                    // an if without an else has no value
+                    Ok(if else_block.is_some() { value } else { Value::Void })
+                } else if let Some(else_block) = else_block {
+                    self.execute_block(else_block)
+                } else {
+                    Ok(Value::Void)
+                }
+            },
+            ExpressionKind::Symbol(name) => self.evaluate_symbol(expr.id, name),
+            ExpressionKind::Assignment(name, value) => self.assign_variable(expr.id, name, value),
+            ExpressionKind::Literal(literal) => Ok(Value::from_literal(literal)),
+            ExpressionKind::Break(value) => {
+                let value = match value {
+                    Some(value) => self.evaluate(value)?,
+                    None => Value::Void,
+                };
+                Err(Unwind::Break(value))
+            },
+            ExpressionKind::Continue => Err(Unwind::Continue),
+            ExpressionKind::Return(expr) => {
+                let value = match expr {
+                    Some(expr) => self.evaluate(expr)?,
+                    None => Value::Void,
+                };
+                Err(Unwind::Return(value))
+            },
+            ExpressionKind::WhileLoop(condition, block) => self.execute_loop(Some(condition), block),
+            ExpressionKind::Loop(block) => self.execute_loop(None, block),
+            ExpressionKind::ForLoop(variable, start, end, step, block) => {
+                self.execute_for_loop(variable, start, end, step.as_deref(), block)
+            },
+            ExpressionKind::Array(elements) => self.evaluate_array(elements),
+            ExpressionKind::Index(array, index) => self.read_index(array, index),
+            ExpressionKind::IndexAssignment(array, index, value) => self.assign_index(array, index, value),
+            ExpressionKind::MethodCall(receiver, name, args) => self.evaluate_method_call(receiver, name, args),
+            ExpressionKind::StructLiteral(name, fields) => self.evaluate_struct_literal(name, fields),
+            ExpressionKind::Field(object, field) => self.read_field(object, field),
+            ExpressionKind::FieldAssignment(object, field, value) => self.assign_field(object, field, value),
+            ExpressionKind::Match(value, arms) => self.evaluate_match(value, arms),
+            ExpressionKind::Interpolation(parts) => self.evaluate_interpolation(parts),
+            ExpressionKind::Block(block) => self.execute_block(block),
+        }
+    }
+
+    // the larger expressions are evaluated by their own methods,
+    // so the stack frame of the recursive evaluate stays small
+
+    fn evaluate_interpolation(&mut self, parts: &[InterpolationPart]) -> Execution {
+        let mut text = String::new();
+        for part in parts {
+            match part {
+                InterpolationPart::Text(s) => text.push_str(s),
+                InterpolationPart::Value(value) => text.push_str(&self.evaluate(value)?.interpolation_text()?),
+            }
+        }
+        Ok(Value::String(text))
+    }
+
+    fn evaluate_call(&mut self, node: NodeId, name: &str, args: &[Expression]) -> Execution {
+        // a variable which holds a function
+        if self.resolution.symbol(node).is_some_and(|id| !self.is_function(id)) {
+            let callee = self.lookup_variable(node, name)?.clone();
+            let values = self.evaluate_arguments(args)?;
+            return self.call_value(callee, values);
+        }
+        let function = match self.resolution.symbol(node).and_then(|id| self.functions.get(&id)) {
+            Some(f) => Rc::clone(f),
+            None if self.is_enum_path(name) => return self.evaluate_variant(name, args),
+            None => match Native::lookup(name) {
+                Some(native) => {
+                    let values = self.evaluate_arguments(args)?;
+                    return Ok(native.call(values)?);
+                },
+                None => return Err(RuntimeError::UndefinedFunction(name.into()).into()),
+            },
+        };
+        let values = self.evaluate_arguments(args)?;
+        Ok(self.call_function(&function, values, Vec::new())?)
+    }
+
+    fn evaluate_value_call(&mut self, callee: &Expression, args: &[Expression]) -> Execution {
+        let callee = self.evaluate(callee)?;
+        let values = self.evaluate_arguments(args)?;
+        self.call_value(callee, values)
+    }
+
+    /// calls a function value with the values captured by it
+    fn call_value(&mut self, callee: Value, args: Vec<Value>) -> Execution {
+        let closure = match callee {
+            Value::Function(closure) => closure,
+            other => return Err(RuntimeError::NotCallable(other.type_name()).into()),
+        };
+        let function = Rc::clone(&self.functions[&SymbolId::from(closure.function as u32)]);
+        Ok(self.call_function(&function, args, closure.captures.clone())?)
+    }
+
+    /// creates the value of a lambda with the current values of the variables it captures.
+    /// The function of the lambda is registered the first time the lambda is evaluated
+    fn evaluate_lambda(&mut self, node: NodeId, args: &[VariableBinding], return_type: Option<&DataType>, body: &Block) -> Execution {
+        let id = self.resolution.symbol(node).expect("the resolver gives every lambda a symbol");
+        let captures = self.resolution.captures(id);
+        let outer: Vec<usize> = captures.iter()
+            .map(|capture| self.resolution.variable_slot(capture.outer).expect("only variables are captured"))
+            .collect();
+        if !self.functions.contains_key(&id) {
+            let slots = match self.resolution.definition(id).kind {
+                SymbolKind::Function(slots) => slots,
+                SymbolKind::Variable(_) | SymbolKind::Global(_) => 0,
+            };
+            let function = Function {
+                name: LAMBDA.into(),
+                args: args.to_vec(),
+                return_type: return_type.cloned(),
+                body: body.clone(),
+                slots,
+                captures: captures.iter()
+                    .map(|capture| self.resolution.variable_slot(capture.inner).expect("only variables are captured"))
+                    .collect(),
+            };
+            self.functions.insert(id, Rc::new(function));
+        }
+        let frame = self.current_frame()?;
+        let values = outer.iter().map(|slot| frame.slots[*slot].clone()).collect();
+        Ok(self.function_value(id, values))
+    }
+
+    /// returns the value of a variable, of a function or of a variant without fields like "Shape::Empty"
+    fn evaluate_symbol(&mut self, node: NodeId, name: &str) -> Execution {
+        if self.resolution.symbol(node).is_none() && self.is_enum_path(name) {
+            return self.evaluate_variant(name, &[]);
+        }
+        if let Some(id) = self.resolution.symbol(node).filter(|id| self.functions.contains_key(id)) {
+            return Ok(self.function_value(id, Vec::new()));
+        }
+        let value = self.lookup_variable(node, name)?;
+        Ok(value.clone())
+    }
+
+    /// creates the value of the function or lambda with this symbol
+    fn function_value(&self, id: SymbolId, captures: Vec<Value>) -> Value {
+        let function = &self.functions[&id];
+        Value::function(function.name.clone(), function.data_type(), id.as_u32() as usize, captures)
+    }
+
+    fn is_function(&self, id: SymbolId) -> bool {
+        matches!(self.resolution.definition(id).kind, SymbolKind::Function(_))
+    }
+
+    /// checks if the name is a path like "Shape::Circle" which starts with the name of a declared enum
+    fn is_enum_path(&self, name: &str) -> bool {
+        split_associated_name(name).is_some_and(|(enum_name, _)| self.enums.contains_key(enum_name))
+    }
+
+    /// creates a variant of an enum, every field needs a value of its declared type
+    fn evaluate_variant(&mut self, name: &str, args: &[Expression]) -> Execution {
+        let (enum_name, variant) = split_associated_name(name).expect("the name is a path to a variant");
+        let fields = match self.enums[enum_name].iter().find(|declared| declared.name == variant) {
+            Some(declared) => declared.fields.clone(),
+            None => return Err(RuntimeError::UndefinedVariant(enum_name.into(), variant.into()).into()),
+        };
+        let values = self.evaluate_arguments(args)?;
+        if fields.len() != values.len() {
+            return Err(RuntimeError::WrongArgumentCount(name.into(), fields.len(), values.len()).into());
+        }
+        for (data_type, value) in fields.iter().zip(&values) {
+            expect_type(data_type, value)?;
+        }
+        Ok(Value::variant(enum_name.into(), variant.into(), values))
+    }
+
+    /// evaluates the value of the first arm whose pattern matches the value
+    fn evaluate_match(&mut self, value: &Expression, arms: &[MatchArm]) -> Execution {
+        let value = self.evaluate(value)?;
+        for arm in arms {
+            if self.match_pattern(&arm.pattern, &value)? {
+                return self.evaluate(&arm.value);
+            }
+        }
+        Err(RuntimeError::NoMatchingArm.into())
+    }
+
+    /// checks if the pattern matches the value and stores the matched values in the slots of its bindings
+    fn match_pattern(&mut self, pattern: &Pattern, value: &Value) -> Result<bool,RuntimeError> {
+        match &pattern.kind {
+            PatternKind::Wildcard => Ok(true),
+            PatternKind::Binding(_) => {
+                let slot = self.resolution.slot(pattern.id).expect("the resolver gives every binding a slot");
+                self.current_frame()?.slots[slot] = value.clone();
+                Ok(true)
+            },
+            PatternKind::Literal(literal) => {
+                let literal = Value::from_literal(literal);
+                if literal.type_name() != value.type_name() {
+                    let data_type = literal.data_type().expect("a literal has a type");
+                    return Err(RuntimeError::TypeMismatch(data_type, value.type_name()));
+                }
+                Ok(literal == *value)
+            },
+            PatternKind::Variant(enum_name, variant, fields) => {
+                let declared = self.enums.get(enum_name)
+                    .ok_or_else(|| RuntimeError::UndefinedType(enum_name.clone()))?
+                    .iter().find(|declared| declared.name == *variant)
+                    .ok_or_else(|| RuntimeError::UndefinedVariant(enum_name.clone(), variant.clone()))?;
+                if declared.fields.len() != fields.len() {
+                    let name = associated_name(enum_name, variant);
+                    return Err(RuntimeError::WrongArgumentCount(name, declared.fields.len(), fields.len()));
+                }
+                let matched = match value {
+                    Value::Enum(matched) if matched.name == *enum_name => Rc::clone(matched),
+                    other => return Err(RuntimeError::TypeMismatch(DataType::Named(enum_name.clone()), other.type_name())),
+                };
+                if matched.variant != *variant {
+                    return Ok(false);
+                }
+                for (field, value) in fields.iter().zip(&matched.fields) {
+                    if !self.match_pattern(field, value)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            },
+        }
+    }
+
+    fn evaluate_arguments(&mut self, args: &[Expression]) -> Result<Vec<Value>,Unwind> {
+        let mut values = Vec::with_capacity(args.len());
+        for arg in args {
+            values.push(self.evaluate(arg)?);
+        }
+        Ok(values)
+    }
+
+    /// calls a method of the impl block of a struct instance or an enum variant with the receiver as
+    /// first argument or a built-in method of an array
+    fn evaluate_method_call(&mut self, receiver: &Expression, name: &str, args: &[Expression]) -> Execution {
+        let receiver = self.evaluate(receiver)?;
+        let mut values = self.evaluate_arguments(args)?;
+        let type_name = match &receiver {
+            Value::Struct(instance) => instance.borrow().name.clone(),
+            Value::Enum(value) => value.name.clone(),
+            _ => return Ok(call_method(&receiver, name, values)?),
+        };
+        let method = match self.resolution.method(&type_name, name).and_then(|id| self.functions.get(&id)) {
+            Some(function) if function.is_method() => Rc::clone(function),
+            _ => return Err(RuntimeError::UndefinedMethod(receiver.type_name(), name.into()).into()),
+        };
+        values.insert(0, receiver);
+        Ok(self.call_function(&method, values, Vec::new())?)
+    }
+
+    fn assign_variable(&mut self, node: NodeId, name: &str, value: &Expression) -> Execution {
+        let value = self.evaluate(value)?;
+        let declared = self.resolution.symbol(node)
+            .and_then(|id| self.resolution.definition(id).data_type.clone());
+        let variable = self.lookup_variable(node, name)?;
+        if let Some(declared) = declared {
+            expect_type(&declared, &value)?;
+        }
+        *variable = value;
+        Ok(Value::Void)
+    }
+
+    /// executes a while loop with a condition or an endless loop without one.
+    /// Only the endless loop returns the value of its break
+    fn execute_loop(&mut self, condition: Option<&Expression>, block: &Block) -> Execution {
+        loop {
+            if let Some(condition) = condition {
+                if !self.evaluate_condition(condition)? {
+                    break;
+                }
+            }
+            match self.execute_block(block) {
+                Ok(_) | Err(Unwind::Continue) => continue,
+                Err(Unwind::Break(value)) if condition.is_none() => return Ok(value),
+                Err(Unwind::Break(_)) => break,
+                Err(unwind) => return Err(unwind),
+            }
+        }
+        Ok(Value::Void)
+    }
+
+    fn execute_for_loop(&mut self, variable: &VariableBinding, start: &Expression, end: &Expression,
+                        step: Option<&Expression>, block: &Block) -> Execution {
+        let mut counter = self.evaluate_integer(start)?;
+        let end = self.evaluate_integer(end)?;
+        let step = match step {
+            Some(step) => self.evaluate_integer(step)?,
+            None => 1,
+        };
+        if step == 0 {
+            return Err(RuntimeError::ZeroStep.into());
+        }
+        let slot = self.binding_slot(variable);
+        // the body can change the loop variable, but not the counter
+        while (step > 0 && counter < end) || (step < 0 && counter > end) {
+            expect_type(&variable.data_type, &Value::Integer(counter))?;
+            self.current_frame()?.slots[slot] = Value::Integer(counter);
+            match self.execute_block(block) {
+                Ok(_) | Err(Unwind::Continue) => {},
+                Err(Unwind::Break(_)) => break,
+                Err(unwind) => return Err(unwind),
+            }
+            // the loop ends when the next value of the counter does not fit into an int
+            match counter.checked_add(step) {
+                Some(next) => counter = next,
+                None => break,
+            }
+        }
+        Ok(Value::Void)
+    }
+
+    fn evaluate_array(&mut self, elements: &[Expression]) -> Execution {
+        let mut values: Vec<Value> = Vec::with_capacity(elements.len());
+        for element in elements {
+            let value = self.evaluate(element)?;
+            if let Some(first) = values.first() {
+                check_element(first, &value)?;
+            } else if value == Value::Void {
+                return Err(RuntimeError::VoidArrayElement.into());
+            }
+            values.push(value);
+        }
+        Ok(Value::array(values))
+    }
+
+    fn read_index(&mut self, array: &Expression, index: &Expression) -> Execution {
+        let array = self.evaluate(array)?;
+        let index = self.evaluate(index)?;
+        let elements = array_elements(&array)?.borrow();
+        let position = array_position(&index, elements.len())?;
+        Ok(elements[position].clone())
+    }
+
+    fn assign_index(&mut self, array: &Expression, index: &Expression, value: &Expression) -> Execution {
+        let array = self.evaluate(array)?;
+        let index = self.evaluate(index)?;
+        let value = self.evaluate(value)?;
+        let mut elements = array_elements(&array)?.borrow_mut();
+        let position = array_position(&index, elements.len())?;
+        check_element(&elements[position], &value)?;
+        elements[position] = value;
+        Ok(Value::Void)
+    }
+
+    fn read_field(&mut self, object: &Expression, field: &str) -> Execution {
+        let object = self.evaluate(object)?;
+        let instance = struct_instance(&object, field)?.borrow();
+        let position = field_position(&instance, field)?;
+        Ok(instance.fields[position].1.clone())
+    }
+
+    /// stores the value in the field of the struct instance, the value has to be of the declared type
+    fn assign_field(&mut self, object: &Expression, field: &str, value: &Expression) -> Execution {
+        let object = self.evaluate(object)?;
+        let value = self.evaluate(value)?;
+        let mut instance = struct_instance(&object, field)?.borrow_mut();
+        let position = field_position(&instance, field)?;
+        let declared = &self.structs[&instance.name][position];
+        expect_type(&declared.data_type, &value)?;
+        instance.fields[position].1 = value;
+        Ok(Value::Void)
+    }
+
+    /// creates an instance with the fields in the order of the struct declaration.
+    /// Every field has to be given with a value of its declared type
+    fn evaluate_struct_literal(&mut self, name: &str, fields: &[(String, Expression)]) -> Execution {
+        let declared = match self.structs.get(name) {
+            Some(declared) => declared.clone(),
+            None => return Err(RuntimeError::UndefinedType(name.into()).into()),
+        };
+        let mut values: Vec<(String, Value)> = declared.iter()
+            .map(|field| (field.symbol.clone(), Value::Void))
+            .collect();
+        for (field, expr) in fields {
+            let value = self.evaluate(expr)?;
+            let position = declared.iter().position(|declared| declared.symbol == *field)
+                .ok_or_else(|| RuntimeError::UndefinedField(name.into(), field.clone()))?;
+            expect_type(&declared[position].data_type, &value)?;
+            values[position].1 = value;
+        }
+        if let Some((missing, _)) = values.iter().find(|(_, value)| *value == Value::Void) {
+            return Err(RuntimeError::MissingField(name.into(), missing.clone()).into());
+        }
+        Ok(Value::instance(name.into(), values))
+    }
+
+    /// evaluates an expression which must result in a boolean
+    fn evaluate_condition(&mut self, expr: &Expression) -> Result<bool,Unwind> {
+        match self.evaluate(expr)? {
+            Value::Boolean(b) => Ok(b),
+            other => Err(RuntimeError::TypeMismatch(DataType::Boolean, other.type_name()).into()),
+        }
+    }
+
+    /// evaluates an expression which must result in an integer
+    fn evaluate_integer(&mut self, expr: &Expression) -> Result<i64,Unwind> {
+        match self.evaluate(expr)? {
+            Value::Integer(n) => Ok(n),
+            other => Err(RuntimeError::TypeMismatch(DataType::Integer, other.type_name()).into()),
+        }
+    }
+
+    /// returns the slot of the variable the node refers to in the current call frame
+    /// or the global variable the node refers to
+    fn lookup_variable(&mut self, node: NodeId, name: &str) -> Result<&mut Value,RuntimeError> {
+        if let Some(slot) = self.resolution.slot(node) {
+            return Ok(&mut self.current_frame()?.slots[slot]);
+        }
+        let index = self.resolution.global(node)
+            .ok_or_else(|| RuntimeError::UndefinedVariable(name.into()))?;
+        let value = &mut self.globals[index];
+        if *value == Value::Void {
+            return Err(RuntimeError::UninitializedGlobal(name.into()));
+        }
+        Ok(value)
+    }
+
+    /// returns the slot of a declared variable or function argument
+    fn binding_slot(&self, binding: &VariableBinding) -> usize {
+        self.resolution.slot(binding.id).expect("the resolver gives every binding a slot")
+    }
+
+    fn current_frame(&mut self) -> Result<&mut Frame,RuntimeError> {
+        self.frames.last_mut().ok_or(RuntimeError::InvalidTopLevelStatement)
+    }
+}
+
+/// checks if the value is of the expected DataType
+fn expect_type(expected: &DataType, value: &Value) -> Result<(),RuntimeError> {
+    if value.has_type(expected) {
+        Ok(())
+    } else {
+        Err(RuntimeError::TypeMismatch(expected.clone(), value.type_name()))
+    }
+}
+
+/// checks if the value can be stored in an array next to the element.
+/// Like the compiled bytecode only the outermost type of nested arrays is compared
+fn check_element(element: &Value, value: &Value) -> Result<(),RuntimeError> {
+    if *value == Value::Void {
+        Err(RuntimeError::VoidArrayElement)
+    } else if element.type_name() != value.type_name() {
+        Err(RuntimeError::ArrayElementMismatch(element.type_name(), value.type_name()))
+    } else {
+        Ok(())
+    }
+}
+
+fn array_elements(value: &Value) -> Result<&RefCell<Vec<Value>>,RuntimeError> {
+    match value {
+        Value::Array(elements) => Ok(elements),
+        other => Err(RuntimeError::NotIndexable(other.type_name())),
+    }
+}
+
+fn struct_instance<'a>(value: &'a Value, field: &str) -> Result<&'a RefCell<Instance>,RuntimeError> {
+    match value {
+        Value::Struct(instance) => Ok(instance),
+        other => Err(RuntimeError::UndefinedField(other.type_name().into(), field.into())),
+    }
+}
+
+fn field_position(instance: &Instance, field: &str) -> Result<usize,RuntimeError> {
+    instance.fields.iter().position(|(name, _)| name == field)
+        .ok_or_else(|| RuntimeError::UndefinedField(instance.name.clone(), field.into()))
+}
+
+fn array_position(index: &Value, len: usize) -> Result<usize,RuntimeError> {
+    match index {
+        Value::Integer(index) => value::array_position(*index, len),
+        other => Err(RuntimeError::TypeMismatch(DataType::Integer, other.type_name())),
     }
 }
 
-#[derive(Ord, PartialOrd, Eq, PartialEq,Copy, Clone,Hash,Debug)]
+/// calls a built-in method of an array
+fn call_method(receiver: &Value, name: &str, mut args: Vec<Value>) -> Result<Value,RuntimeError> {
+    let elements = match receiver {
+        Value::Array(elements) => elements,
+        other => return Err(RuntimeError::UndefinedMethod(other.type_name(), name.into())),
+    };
+    let (expected, _) = type_checker::array_method(name, Type::Unknown)
+        .ok_or_else(|| RuntimeError::UndefinedMethod(receiver.type_name(), name.into()))?;
+    if expected.len() != args.len() {
+        return Err(RuntimeError::WrongArgumentCount(name.into(), expected.len(), args.len()));
+    }
+
+    let mut elements = elements.borrow_mut();
+    match (name, args.pop()) {
+        ("len", _) => Ok(Value::Integer(elements.len() as i64)),
+        ("push", Some(value)) => {
+            match elements.first() {
+                Some(first) => check_element(first, &value)?,
+                None if value == Value::Void => return Err(RuntimeError::VoidArrayElement),
+                None => {},
+            }
+            elements.push(value);
+            Ok(Value::Void)
+        },
+        ("pop", _) => elements.pop().ok_or(RuntimeError::PopFromEmptyArray),
+        _ => unreachable!("array_method knows every method of arrays"),
+    }
+}
+
+fn unary_operation(op: UnOp, value: Value) -> Result<Value,RuntimeError> {
+    match (op, value) {
+        (UnOp::Negation, Value::Boolean(b)) => Ok(Value::Boolean(!b)),
+        (UnOp::Minus, Value::Integer(n)) => n.checked_neg().map(Value::Integer).ok_or(RuntimeError::IntegerOverflow),
+        (UnOp::Minus, Value::Float(n)) => Ok(Value::Float(-n)),
+        (op, value) => Err(RuntimeError::InvalidOperand(op, value.type_name())),
+    }
+}
+
+fn binary_operation(op: BinOp, left: Value, right: Value) -> Result<Value,RuntimeError> {
+    let value = match (op, left, right) {
+        (BinOp::Plus, Value::Integer(l), Value::Integer(r)) => Value::Integer(l.checked_add(r).ok_or(RuntimeError::IntegerOverflow)?),
+        (BinOp::Minus, Value::Integer(l), Value::Integer(r)) => Value::Integer(l.checked_sub(r).ok_or(RuntimeError::IntegerOverflow)?),
+        (BinOp::Multi, Value::Integer(l), Value::Integer(r)) => Value::Integer(l.checked_mul(r).ok_or(RuntimeError::IntegerOverflow)?),
+        (BinOp::Divide, Value::Integer(_), Value::Integer(0)) |
+        (BinOp::Modulo, Value::Integer(_), Value::Integer(0)) => return Err(RuntimeError::DivisionByZero),
+        (BinOp::Divide, Value::Integer(l), Value::Integer(r)) => Value::Integer(l.checked_div(r).ok_or(RuntimeError::IntegerOverflow)?),
+        (BinOp::Modulo, Value::Integer(l), Value::Integer(r)) => Value::Integer(l.checked_rem(r).ok_or(RuntimeError::IntegerOverflow)?),
+        (BinOp::Plus, Value::Float(l), Value::Float(r)) => Value::Float(l + r),
+        (BinOp::Minus, Value::Float(l), Value::Float(r)) => Value::Float(l - r),
+        (BinOp::Multi, Value::Float(l), Value::Float(r)) => Value::Float(l * r),
+        (BinOp::Divide, Value::Float(l), Value::Float(r)) => Value::Float(l / r),
+        (BinOp::Modulo, Value::Float(l), Value::Float(r)) => Value::Float(l % r),
+        (BinOp::Plus, Value::String(mut l), Value::String(r)) => {
+            l.push_str(&r);
+            Value::String(l)
+        },
+        (op, left, right) => return comparison(op, &left, &right),
+    };
+    Ok(value)
+}
+
+/// compares two values of the same type, booleans can only be checked for equality
+fn comparison(op: BinOp, left: &Value, right: &Value) -> Result<Value,RuntimeError> {
+    let invalid = || RuntimeError::InvalidOperands(op, left.type_name(), right.type_name());
+    if left.type_name() != right.type_name() || matches!(left, Value::Void | Value::Array(_) | Value::Struct(_) | Value::Enum(_) | Value::Function(_)) {
+        return Err(invalid());
+    }
+    let is_boolean = left.data_type() == Some(DataType::Boolean);
+    let result = match op {
+        BinOp::Eq => left == right,
+        BinOp::Neq => left != right,
+        BinOp::Gt if !is_boolean => left > right,
+        BinOp::Ge if !is_boolean => left >= right,
+        BinOp::Lt if !is_boolean => left < right,
+        BinOp::Le if !is_boolean => left <= right,
+        _ => return Err(invalid()),
+    };
+    Ok(Value::Boolean(result))
+}
+
+/// Enumeration of all errors which can occur while executing a program
+#[derive(Ord, PartialOrd, Eq, PartialEq, Clone, Hash, Debug)]
 pub enum RuntimeError {
-    NullPointerError
-}
\ No newline at end of file
+    /// the program contains no main function
+    MissingMainFunction,
+    /// only declarations of functions, global variables and constants are allowed outside of functions
+    InvalidTopLevelStatement,
+    /// call of a function which was never declared
+    UndefinedFunction(String),
+    /// access to a variable which was never declared in this scope
+    UndefinedVariable(String),
+    /// access to a global variable by a function which was called before the declaration
+    /// of the global was executed
+    UninitializedGlobal(String),
+    /// function name, expected number of arguments, found number of arguments
+    WrongArgumentCount(String, usize, usize),
+    /// Expected, Found
+    TypeMismatch(DataType, &'static str),
+    /// function with a return type ended without returning a value
+    MissingReturnValue(String),
+    /// function without a return type returned a value
+    UnexpectedReturnValue(String),
+    /// unary operator can not be applied on a value of this type
+    InvalidOperand(UnOp, &'static str),
+    /// binary operator can not be applied on values of these types
+    InvalidOperands(BinOp, &'static str, &'static str),
+    DivisionByZero,
+    IntegerOverflow,
+    BreakOutsideLoop,
+    ContinueOutsideLoop,
+    /// the step of a for loop is zero, so the loop would never end
+    ZeroStep,
+    /// too many nested function calls
+    StackOverflow,
+    /// index, length of the array
+    IndexOutOfBounds(i64, usize),
+    PopFromEmptyArray,
+    /// index expression on a value of this type
+    NotIndexable(&'static str),
+    /// type of the value, name of the method
+    UndefinedMethod(&'static str, String),
+    /// type of the elements in the array, type of the new element
+    ArrayElementMismatch(&'static str, &'static str),
+    /// element of an array without a value
+    VoidArrayElement,
+    /// name of a struct or enum which was never declared
+    UndefinedType(String),
+    /// name of the type, name of the field
+    UndefinedField(String, String),
+    /// name of the struct, name of the field without a value in the literal
+    MissingField(String, String),
+    /// name of the enum, name of the variant which was never declared
+    UndefinedVariant(String, String),
+    /// no arm of a match matches the value
+    NoMatchingArm,
+    /// call of a value of this type which is not a function
+    NotCallable(&'static str),
+    /// value of this type embedded in an interpolated string
+    NotInterpolatable(&'static str),
+    /// start, end and length of the string of a substring
+    InvalidRange(i64, i64, usize),
+    /// "pow" with a negative exponent
+    NegativeExponent(i64),
+    /// value which can not be converted, name of the type it should be converted into
+    InvalidConversion(String, &'static str),
+    /// reading from stdin or writing to stdout failed
+    InputOutput(String),
+    /// stdout was closed by its reader like `head` in `paper_castle run script.pc | head`,
+    /// it ends the program like an error, but it is not reported
+    OutputClosed,
+    /// message of an "assert" whose condition is false
+    AssertionFailed(String),
+    /// "exit" was called with this exit code, it ends the program like an error
+    Exit(i64),
+}
+
+/// writes the error as a message for the user
+impl fmt::Display for RuntimeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RuntimeError::MissingMainFunction => write!(f, "the program has no `{}` function", ENTRY_POINT),
+            RuntimeError::InvalidTopLevelStatement => f.write_str("only functions, variables and constants can be declared outside of functions"),
+            RuntimeError::UndefinedFunction(name) => write!(f, "call of the undefined function `{}`", name),
+            RuntimeError::UndefinedVariable(name) => write!(f, "use of the undefined variable `{}`", name),
+            RuntimeError::UninitializedGlobal(name) => write!(f, "use of the global variable `{}` before it is initialized", name),
+            RuntimeError::WrongArgumentCount(name, expected, found) => {
+                write!(f, "function `{}` takes {} arguments but {} were given", name, expected, found)
+            },
+            RuntimeError::TypeMismatch(expected, found) => write!(f, "expected a value of type `{}`, found `{}`", expected, found),
+            RuntimeError::MissingReturnValue(name) => write!(f, "function `{}` ended without returning a value", name),
+            RuntimeError::UnexpectedReturnValue(name) => write!(f, "function `{}` has no return type but returned a value", name),
+            RuntimeError::InvalidOperand(op, found) => write!(f, "`{}` can not be applied on a value of type `{}`", op, found),
+            RuntimeError::InvalidOperands(op, left, right) => write!(f, "`{}` can not be applied on `{}` and `{}`", op, left, right),
+            RuntimeError::DivisionByZero => f.write_str("division by zero"),
+            RuntimeError::IntegerOverflow => f.write_str("integer overflow"),
+            RuntimeError::BreakOutsideLoop => f.write_str("`break` outside of a loop"),
+            RuntimeError::ContinueOutsideLoop => f.write_str("`continue` outside of a loop"),
+            RuntimeError::ZeroStep => f.write_str("the step of a for loop is zero"),
+            RuntimeError::StackOverflow => f.write_str("stack overflow, too many nested function calls"),
+            RuntimeError::IndexOutOfBounds(index, len) => write!(f, "index out of bounds, the length is {} but the index is {}", len, index),
+            RuntimeError::PopFromEmptyArray => f.write_str("`pop` on an empty array"),
+            RuntimeError::NotIndexable(found) => write!(f, "a value of type `{}` can not be indexed", found),
+            RuntimeError::UndefinedMethod(found, name) => write!(f, "no method `{}` for a value of type `{}`", name, found),
+            RuntimeError::ArrayElementMismatch(expected, found) => {
+                write!(f, "an array with elements of type `{}` can not contain a value of type `{}`", expected, found)
+            },
+            RuntimeError::VoidArrayElement => f.write_str("an array element has no value"),
+            RuntimeError::UndefinedType(name) => write!(f, "use of the undefined type `{}`", name),
+            RuntimeError::UndefinedField(found, name) => write!(f, "no field `{}` on a value of type `{}`", name, found),
+            RuntimeError::MissingField(name, field) => write!(f, "the literal of `{}` has no value for the field `{}`", name, field),
+            RuntimeError::UndefinedVariant(name, variant) => write!(f, "the enum `{}` has no variant `{}`", name, variant),
+            RuntimeError::NoMatchingArm => f.write_str("no arm of the match matches the value"),
+            RuntimeError::NotCallable(found) => write!(f, "a value of type `{}` can not be called", found),
+            RuntimeError::NotInterpolatable(found) => write!(f, "a value of type `{}` can not be embedded in a string", found),
+            RuntimeError::InvalidRange(start, end, len) => {
+                write!(f, "invalid range {}..{} of a string with {} characters", start, end, len)
+            },
+            RuntimeError::NegativeExponent(exponent) => write!(f, "`pow` with the negative exponent {}", exponent),
+            RuntimeError::InvalidConversion(value, name) => write!(f, "`{}` can not be converted into a value of type `{}`", value, name),
+            RuntimeError::InputOutput(message) => write!(f, "input or output failed: {}", message),
+            RuntimeError::OutputClosed => f.write_str("the output was closed by its reader"),
+            RuntimeError::AssertionFailed(message) => write!(f, "assertion failed: {}", message),
+            RuntimeError::Exit(code) => write!(f, "the program exited with the code {}", code),
+        }
+    }
+}
@@ -1,4 +1,7 @@
+pub mod interpreter;
+pub mod value;
+pub mod bytecode;
+pub mod vm;
+pub mod stdlib;
 //#[allow(dead_code)]
-//pub mod interpreter;
-//#[allow(dead_code)]
-//pub mod memory;
\ No newline at end of file
+//pub mod memory;
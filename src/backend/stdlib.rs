@@ -0,0 +1,128 @@
+use crate::frontend::semantic::natives::Native;
+use crate::backend::interpreter::RuntimeError;
+use crate::backend::value::Value;
+
+use std::convert::TryFrom;
+use std::io::{self, Write};
+
+/// The functions of the standard library are declared in the frontend, which checks their calls
+impl Native {
+
+    /// calls the function with the arguments, their number and types are checked before.
+    /// Returns Void if the function has no return type
+    pub fn call(self, args: Vec<Value>) -> Result<Value,RuntimeError> {
+        let signatures = self.signatures();
+        let (expected, _) = signatures.iter()
+            .find(|(expected, _)| expected.len() == args.len() && expected.iter().zip(&args).all(|(expected, found)| found.has_type(expected)))
+            .unwrap_or(&signatures[0])
+            .clone();
+        if expected.len() != args.len() {
+            return Err(RuntimeError::WrongArgumentCount(self.name().into(), expected.len(), args.len()));
+        }
+        if let Some((expected, found)) = expected.into_iter().zip(&args).find(|(expected, found)| !found.has_type(expected)) {
+            return Err(RuntimeError::TypeMismatch(expected, found.type_name()));
+        }
+
+        let value = match (self, args.as_slice()) {
+            (Native::Print, [Value::String(s)]) => {
+                let mut stdout = io::stdout().lock();
+                write!(stdout, "{}", s).map_err(output_error)?;
+                // the text printed so far is shown before the program waits for input
+                stdout.flush().map_err(output_error)?;
+                Value::Void
+            },
+            (Native::Println, [Value::String(s)]) => {
+                writeln!(io::stdout().lock(), "{}", s).map_err(output_error)?;
+                Value::Void
+            },
+            (Native::ReadLine, []) => Value::String(read_line()?),
+            (Native::Length, [Value::String(s)]) => Value::Integer(s.chars().count() as i64),
+            (Native::Substring, [Value::String(s), Value::Integer(start), Value::Integer(end)]) => {
+                Value::String(substring(s, *start, *end)?)
+            },
+            (Native::Concat, [Value::String(first), Value::String(second)]) => Value::String(format!("{}{}", first, second)),
+            (Native::Split, [Value::String(s), Value::String(separator)]) => {
+                // an empty separator splits the string into its characters
+                let parts: Vec<Value> = if separator.is_empty() {
+                    s.chars().map(|c| Value::String(c.to_string())).collect()
+                } else {
+                    s.split(separator.as_str()).map(|part| Value::String(part.into())).collect()
+                };
+                Value::array(parts)
+            },
+            (Native::Trim, [Value::String(s)]) => Value::String(s.trim().into()),
+            (Native::Abs, [Value::Integer(n)]) => Value::Integer(n.checked_abs().ok_or(RuntimeError::IntegerOverflow)?),
+            (Native::Abs, [Value::Float(n)]) => Value::Float(n.abs()),
+            (Native::Min, [Value::Integer(a), Value::Integer(b)]) => Value::Integer(*a.min(b)),
+            (Native::Min, [Value::Float(a), Value::Float(b)]) => Value::Float(a.min(*b)),
+            (Native::Max, [Value::Integer(a), Value::Integer(b)]) => Value::Integer(*a.max(b)),
+            (Native::Max, [Value::Float(a), Value::Float(b)]) => Value::Float(a.max(*b)),
+            (Native::Pow, [Value::Integer(base), Value::Integer(exponent)]) => {
+                if *exponent < 0 {
+                    return Err(RuntimeError::NegativeExponent(*exponent));
+                }
+                let exponent = u32::try_from(*exponent).map_err(|_| RuntimeError::IntegerOverflow)?;
+                Value::Integer(base.checked_pow(exponent).ok_or(RuntimeError::IntegerOverflow)?)
+            },
+            (Native::Pow, [Value::Float(base), Value::Float(exponent)]) => Value::Float(base.powf(*exponent)),
+            (Native::Sqrt, [Value::Float(n)]) => Value::Float(n.sqrt()),
+            (Native::IntToFloat, [Value::Integer(n)]) => Value::Float(*n as f64),
+            (Native::FloatToInt, [value @ Value::Float(n)]) => {
+                // the fractional part is cut off, but the integral part has to fit into an int
+                let truncated = n.trunc();
+                if !(truncated >= i64::MIN as f64 && truncated < i64::MAX as f64) {
+                    return Err(RuntimeError::InvalidConversion(value.to_string(), "int"));
+                }
+                Value::Integer(truncated as i64)
+            },
+            (Native::IntToString, [value @ Value::Integer(_)]) |
+            (Native::FloatToString, [value @ Value::Float(_)]) |
+            (Native::BooleanToString, [value @ Value::Boolean(_)]) => Value::String(value.to_string()),
+            (Native::ParseInt, [Value::String(s)]) => {
+                Value::Integer(s.trim().parse().map_err(|_| RuntimeError::InvalidConversion(s.clone(), "int"))?)
+            },
+            (Native::ParseFloat, [Value::String(s)]) => {
+                Value::Float(s.trim().parse().map_err(|_| RuntimeError::InvalidConversion(s.clone(), "float"))?)
+            },
+            (Native::ParseBoolean, [Value::String(s)]) => {
+                Value::Boolean(s.trim().parse().map_err(|_| RuntimeError::InvalidConversion(s.clone(), "boolean"))?)
+            },
+            (Native::Assert, [Value::Boolean(condition), Value::String(message)]) => {
+                if !condition {
+                    return Err(RuntimeError::AssertionFailed(message.clone()));
+                }
+                Value::Void
+            },
+            (Native::Exit, [Value::Integer(code)]) => return Err(RuntimeError::Exit(*code)),
+            _ => unreachable!("the types of the arguments of `{}` were checked before", self.name()),
+        };
+        Ok(value)
+    }
+}
+
+/// returns the characters of the string from the start to the end, without the end
+fn substring(s: &str, start: i64, end: i64) -> Result<String,RuntimeError> {
+    let len = s.chars().count();
+    if start < 0 || start > end || end as usize > len {
+        return Err(RuntimeError::InvalidRange(start, end, len));
+    }
+    Ok(s.chars().skip(start as usize).take((end - start) as usize).collect())
+}
+
+/// reads a line from stdin without its line break, the line is empty at the end of stdin
+fn read_line() -> Result<String,RuntimeError> {
+    let mut line = String::new();
+    io::stdin().read_line(&mut line).map_err(|e| RuntimeError::InputOutput(e.to_string()))?;
+    let len = line.trim_end_matches(['\n', '\r']).len();
+    line.truncate(len);
+    Ok(line)
+}
+
+/// a stdout which was closed by its reader ends the program quietly, every other failed write is an error
+fn output_error(e: io::Error) -> RuntimeError {
+    if e.kind() == io::ErrorKind::BrokenPipe {
+        RuntimeError::OutputClosed
+    } else {
+        RuntimeError::InputOutput(e.to_string())
+    }
+}
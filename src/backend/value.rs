@@ -0,0 +1,207 @@
+use crate::frontend::syntax::{DataType, DataValue};
+use crate::backend::interpreter::RuntimeError;
+
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+/// Represents a value at runtime.
+/// In contrast to DataValue which only describes literals in the source code,
+/// a Value is already converted into its native representation
+#[derive(PartialEq, PartialOrd, Clone, Debug)]
+pub enum Value {
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    String(String),
+    /// elements of an array, every copy of the value refers to the same elements
+    Array(Rc<RefCell<Vec<Value>>>),
+    /// instance of a struct, every copy of the value refers to the same fields
+    Struct(Rc<RefCell<Instance>>),
+    /// variant of an enum, its fields can not be changed
+    Enum(Rc<EnumValue>),
+    /// function or lambda which can be called, a lambda contains the values it captured
+    Function(Rc<Closure>),
+    /// result of an expression or function which produces no value
+    Void,
+}
+
+/// Fields of a struct instance in the order of the struct declaration
+#[derive(PartialEq, PartialOrd, Clone, Debug)]
+pub struct Instance {
+    pub name: String,
+    pub fields: Vec<(String, Value)>,
+}
+
+/// Variant of an enum with the values of its fields
+#[derive(PartialEq, PartialOrd, Clone, Debug)]
+pub struct EnumValue {
+    pub name: String,
+    pub variant: String,
+    pub fields: Vec<Value>,
+}
+
+/// Function used as a value with the values of the variables a lambda captured
+#[derive(PartialEq, PartialOrd, Clone, Debug)]
+pub struct Closure {
+    pub name: String,
+    /// type of the function like "fn(int): int"
+    pub data_type: DataType,
+    /// identifies the code of the function in the backend which created the value
+    pub function: usize,
+    /// values of the captured variables in the order of the captures of the lambda
+    pub captures: Vec<Value>,
+}
+
+impl Value {
+
+    /// converts an literal from the AbstractSyntaxTree into its runtime value
+    pub fn from_literal(literal: &DataValue) -> Value {
+        match literal {
+            DataValue::Integer(n) => Value::Integer(*n),
+            DataValue::Float(n) => Value::Float(*n),
+            DataValue::Boolean(b) => Value::Boolean(*b),
+            DataValue::String(s) => Value::String(s.clone()),
+        }
+    }
+
+    /// creates an array value with these elements
+    pub fn array(elements: Vec<Value>) -> Value {
+        Value::Array(Rc::new(RefCell::new(elements)))
+    }
+
+    /// creates an instance of the struct with these fields
+    pub fn instance(name: String, fields: Vec<(String, Value)>) -> Value {
+        Value::Struct(Rc::new(RefCell::new(Instance { name, fields })))
+    }
+
+    /// creates the variant of the enum with the values of its fields
+    pub fn variant(name: String, variant: String, fields: Vec<Value>) -> Value {
+        Value::Enum(Rc::new(EnumValue { name, variant, fields }))
+    }
+
+    /// creates a function value, the captured values are stored in the call frame of every call
+    pub fn function(name: String, data_type: DataType, function: usize, captures: Vec<Value>) -> Value {
+        Value::Function(Rc::new(Closure { name, data_type, function, captures }))
+    }
+
+    /// returns the DataType of this value or None if this value is Void or an array,
+    /// the element type of an empty array is unknown at runtime. A struct instance or an enum
+    /// variant is of the type named like its declaration
+    pub fn data_type(&self) -> Option<DataType> {
+        match self {
+            Value::Integer(_) => Some(DataType::Integer),
+            Value::Float(_) => Some(DataType::Float),
+            Value::Boolean(_) => Some(DataType::Boolean),
+            Value::String(_) => Some(DataType::String),
+            Value::Struct(instance) => Some(DataType::Named(instance.borrow().name.clone())),
+            Value::Enum(value) => Some(DataType::Named(value.name.clone())),
+            Value::Function(closure) => Some(closure.data_type.clone()),
+            Value::Array(_) | Value::Void => None,
+        }
+    }
+
+    /// checks if the value is of the DataType, every element of an array has to be of the element type
+    pub fn has_type(&self, expected: &DataType) -> bool {
+        match (self, expected) {
+            (Value::Array(elements), DataType::Array(element)) => {
+                elements.borrow().iter().all(|value| value.has_type(element))
+            },
+            _ => self.data_type().as_ref() == Some(expected),
+        }
+    }
+
+    /// returns the name of the type of this value like it is written in the source code
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Integer(_) => "int",
+            Value::Float(_) => "float",
+            Value::Boolean(_) => "boolean",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Struct(_) => "struct",
+            Value::Enum(_) => "enum",
+            Value::Function(_) => "fn",
+            Value::Void => "void",
+        }
+    }
+
+    /// returns the text of the value embedded in an interpolated string,
+    /// only strings, numbers and booleans can be embedded
+    pub fn interpolation_text(&self) -> Result<String,RuntimeError> {
+        match self {
+            Value::String(s) => Ok(s.clone()),
+            Value::Integer(_) | Value::Float(_) | Value::Boolean(_) => Ok(self.to_string()),
+            other => Err(RuntimeError::NotInterpolatable(other.type_name())),
+        }
+    }
+}
+
+/// converts the index into a position in an array with this length
+pub fn array_position(index: i64, len: usize) -> Result<usize,RuntimeError> {
+    if index >= 0 && (index as usize) < len {
+        Ok(index as usize)
+    } else {
+        Err(RuntimeError::IndexOutOfBounds(index, len))
+    }
+}
+
+/// writes the value like a literal in the source code, strings are written without quotes
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Integer(n) => write!(f, "{}", n),
+            Value::Float(n) => write!(f, "{:?}", n),
+            Value::Boolean(b) => write!(f, "{}", b),
+            Value::String(s) => f.write_str(s),
+            Value::Array(elements) => {
+                f.write_str("[")?;
+                for (i, element) in elements.borrow().iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", element)?;
+                }
+                f.write_str("]")
+            },
+            Value::Struct(instance) => {
+                let instance = instance.borrow();
+                write!(f, "{} {{ ", instance.name)?;
+                for (i, (name, value)) in instance.fields.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}: {}", name, value)?;
+                }
+                f.write_str(" }")
+            },
+            Value::Enum(value) => {
+                write!(f, "{}::{}", value.name, value.variant)?;
+                if !value.fields.is_empty() {
+                    f.write_str("(")?;
+                    for (i, field) in value.fields.iter().enumerate() {
+                        if i > 0 {
+                            f.write_str(", ")?;
+                        }
+                        write!(f, "{}", field)?;
+                    }
+                    f.write_str(")")?;
+                }
+                Ok(())
+            },
+            // a function is written like its signature with the types of its arguments
+            Value::Function(closure) => match &closure.data_type {
+                DataType::Function(args, return_type) => {
+                    let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+                    write!(f, "fn {}({})", closure.name, args.join(", "))?;
+                    match return_type {
+                        Some(return_type) => write!(f, ": {}", return_type),
+                        None => Ok(()),
+                    }
+                },
+                other => write!(f, "fn {}: {}", closure.name, other),
+            },
+            Value::Void => f.write_str("void"),
+        }
+    }
+}
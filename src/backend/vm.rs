@@ -0,0 +1,390 @@
+use crate::frontend::syntax::DataType;
+use crate::frontend::syntax::ast::BinOp;
+use crate::backend::bytecode::{Instruction, Program};
+use crate::backend::interpreter::RuntimeError;
+use crate::backend::value::{self, EnumValue, Instance, Value};
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Maximum number of nested function calls, deeper calls end with a StackOverflow.
+/// The call frames are stored on the heap, so the vm allows deeper calls than the tree walking interpreter
+pub const MAX_FRAMES: usize = 100_000;
+
+/// Initializes the global variables, executes the main function of the compiled program and returns its value
+pub fn run(program: &Program) -> Result<Value, RuntimeError> {
+    VirtualMachine::new(program).run()
+}
+
+/// State of a calling function while the called function is executed
+struct CallFrame {
+    function: usize,
+    /// next instruction after the call
+    ip: usize,
+    /// index of the first slot of the function in the value stack
+    base: usize,
+}
+
+/// Stack machine which executes a compiled Program.
+/// The slots of every function are stored in the value stack below
+/// the temporary values of the function.
+pub struct VirtualMachine<'a> {
+    program: &'a Program,
+    stack: Vec<Value>,
+    frames: Vec<CallFrame>,
+    /// values of the global variables, Void until they are initialized
+    globals: Vec<Value>,
+}
+
+impl<'a> VirtualMachine<'a> {
+
+    pub fn new(program: &'a Program) -> Self {
+        let globals = vec![Value::Void; program.globals.len()];
+        VirtualMachine { program, stack: Vec::with_capacity(256), frames: Vec::new(), globals }
+    }
+
+    /// executes the program until the main function returns or a RuntimeError occurs.
+    /// The global variables are initialized before the main function is called.
+    pub fn run(mut self) -> Result<Value, RuntimeError> {
+        if let Some(initializer) = self.program.initializer {
+            self.execute(initializer)?;
+        }
+        let main = &self.program.functions[self.program.entry];
+        if main.arity != 0 {
+            return Err(RuntimeError::WrongArgumentCount(main.name.clone(), main.arity, 0));
+        }
+        self.execute(self.program.entry)
+    }
+
+    /// executes a function without arguments until it returns
+    fn execute(&mut self, mut function: usize) -> Result<Value, RuntimeError> {
+        let program = self.program;
+        let mut code = &program.functions[function];
+        let mut base = 0;
+        let mut ip = 0;
+        self.stack.resize(code.slots, Value::Void);
+
+        loop {
+            let instruction = code.code[ip];
+            ip += 1;
+            match instruction {
+                Instruction::Constant(index) => self.stack.push(program.constants[index].clone()),
+                Instruction::Load(slot) => {
+                    let value = self.stack[base + slot].clone();
+                    self.stack.push(value);
+                },
+                Instruction::Store(slot) => {
+                    let value = self.pop();
+                    self.stack[base + slot] = value;
+                },
+                Instruction::LoadGlobal(index) => {
+                    let value = self.global(index)?.clone();
+                    self.stack.push(value);
+                },
+                Instruction::StoreGlobal(index) => {
+                    self.global(index)?;
+                    let value = self.pop();
+                    self.globals[index] = value;
+                },
+                Instruction::InitGlobal(index) => {
+                    let value = self.pop();
+                    self.globals[index] = value;
+                },
+                Instruction::Pop => { self.pop(); },
+                Instruction::Jump(target) => ip = target,
+                Instruction::JumpIfFalse(target) => {
+                    if !self.pop_boolean() {
+                        ip = target;
+                    }
+                },
+                Instruction::Call(index) => {
+                    if self.frames.len() + 1 >= MAX_FRAMES {
+                        return Err(RuntimeError::StackOverflow);
+                    }
+                    self.frames.push(CallFrame { function, ip, base });
+                    function = index;
+                    code = &program.functions[function];
+                    base = self.stack.len() - code.arity;
+                    ip = 0;
+                    self.stack.resize(base + code.slots, Value::Void);
+                },
+                Instruction::CallValue(argc) => {
+                    let closure = match self.stack.remove(self.stack.len() - argc - 1) {
+                        Value::Function(closure) => closure,
+                        other => unreachable!("expected fn, found {}", other.type_name()),
+                    };
+                    let arity = program.functions[closure.function].arity;
+                    if arity != argc {
+                        return Err(RuntimeError::WrongArgumentCount(closure.name.clone(), arity, argc));
+                    }
+                    if self.frames.len() + 1 >= MAX_FRAMES {
+                        return Err(RuntimeError::StackOverflow);
+                    }
+                    self.frames.push(CallFrame { function, ip, base });
+                    function = closure.function;
+                    code = &program.functions[function];
+                    base = self.stack.len() - code.arity;
+                    ip = 0;
+                    self.stack.resize(base + code.slots, Value::Void);
+                    for (slot, value) in code.captures.iter().zip(&closure.captures) {
+                        self.stack[base + slot] = value.clone();
+                    }
+                },
+                Instruction::CallNative(native) => {
+                    let args = self.stack.split_off(self.stack.len() - native.arity());
+                    let value = native.call(args)?;
+                    if value != Value::Void {
+                        self.stack.push(value);
+                    }
+                },
+                Instruction::MakeFunction(index) => {
+                    let function = &program.functions[index];
+                    let captures = self.stack.split_off(self.stack.len() - function.captures.len());
+                    self.stack.push(Value::function(function.name.clone(), function.data_type.clone(), index, captures));
+                },
+                Instruction::Return | Instruction::ReturnVoid => {
+                    let value = match instruction {
+                        Instruction::Return => self.pop(),
+                        _ => Value::Void,
+                    };
+                    self.stack.truncate(base);
+                    let caller = match self.frames.pop() {
+                        Some(caller) => caller,
+                        None => return Ok(value),
+                    };
+                    if instruction == Instruction::Return {
+                        self.stack.push(value);
+                    }
+                    function = caller.function;
+                    code = &program.functions[function];
+                    ip = caller.ip;
+                    base = caller.base;
+                },
+                Instruction::AddInt => self.integer_operation(i64::checked_add)?,
+                Instruction::SubInt => self.integer_operation(i64::checked_sub)?,
+                Instruction::CheckedAddInt => {
+                    let right = self.pop_integer();
+                    let left = self.pop_integer();
+                    let sum = left.checked_add(right);
+                    if let Some(sum) = sum {
+                        self.stack.push(Value::Integer(sum));
+                    }
+                    self.stack.push(Value::Boolean(sum.is_some()));
+                },
+                Instruction::MulInt => self.integer_operation(i64::checked_mul)?,
+                Instruction::DivInt | Instruction::ModInt => {
+                    if self.stack.last() == Some(&Value::Integer(0)) {
+                        return Err(RuntimeError::DivisionByZero);
+                    }
+                    let operation = if instruction == Instruction::DivInt { i64::checked_div } else { i64::checked_rem };
+                    self.integer_operation(operation)?
+                },
+                Instruction::NegInt => {
+                    let n = self.pop_integer();
+                    let result = n.checked_neg().ok_or(RuntimeError::IntegerOverflow)?;
+                    self.stack.push(Value::Integer(result));
+                },
+                Instruction::AddFloat => self.float_operation(|l, r| l + r),
+                Instruction::SubFloat => self.float_operation(|l, r| l - r),
+                Instruction::MulFloat => self.float_operation(|l, r| l * r),
+                Instruction::DivFloat => self.float_operation(|l, r| l / r),
+                Instruction::ModFloat => self.float_operation(|l, r| l % r),
+                Instruction::NegFloat => {
+                    let n = self.pop_float();
+                    self.stack.push(Value::Float(-n));
+                },
+                Instruction::Concat => {
+                    let right = self.pop_string();
+                    let mut left = self.pop_string();
+                    left.push_str(&right);
+                    self.stack.push(Value::String(left));
+                },
+                Instruction::Not => {
+                    let b = self.pop_boolean();
+                    self.stack.push(Value::Boolean(!b));
+                },
+                Instruction::CompareInt(op) => {
+                    let right = self.pop_integer();
+                    let left = self.pop_integer();
+                    self.stack.push(Value::Boolean(compare(op, &left, &right)));
+                },
+                Instruction::CompareFloat(op) => {
+                    let right = self.pop_float();
+                    let left = self.pop_float();
+                    self.stack.push(Value::Boolean(compare(op, &left, &right)));
+                },
+                Instruction::CompareString(op) => {
+                    let right = self.pop_string();
+                    let left = self.pop_string();
+                    self.stack.push(Value::Boolean(compare(op, &left, &right)));
+                },
+                Instruction::CompareBoolean(op) => {
+                    let right = self.pop_boolean();
+                    let left = self.pop_boolean();
+                    self.stack.push(Value::Boolean(compare(op, &left, &right)));
+                },
+                Instruction::Interpolate(len) => {
+                    let mut text = String::new();
+                    for value in self.stack.split_off(self.stack.len() - len) {
+                        text.push_str(&value.interpolation_text()?);
+                    }
+                    self.stack.push(Value::String(text));
+                },
+                Instruction::MakeArray(len) => {
+                    let elements = self.stack.split_off(self.stack.len() - len);
+                    self.stack.push(Value::array(elements));
+                },
+                Instruction::Index => {
+                    let index = self.pop_integer();
+                    let array = self.pop_array();
+                    let elements = array.borrow();
+                    let value = elements[value::array_position(index, elements.len())?].clone();
+                    self.stack.push(value);
+                },
+                Instruction::StoreIndex => {
+                    let value = self.pop();
+                    let index = self.pop_integer();
+                    let array = self.pop_array();
+                    let mut elements = array.borrow_mut();
+                    let position = value::array_position(index, elements.len())?;
+                    elements[position] = value;
+                },
+                Instruction::ArrayLen => {
+                    let len = self.pop_array().borrow().len();
+                    self.stack.push(Value::Integer(len as i64));
+                },
+                Instruction::ArrayPush => {
+                    let value = self.pop();
+                    self.pop_array().borrow_mut().push(value);
+                },
+                Instruction::ArrayPop => {
+                    let value = self.pop_array().borrow_mut().pop().ok_or(RuntimeError::PopFromEmptyArray)?;
+                    self.stack.push(value);
+                },
+                Instruction::MakeStruct(index) => {
+                    let layout = &program.structs[index];
+                    let values = self.stack.split_off(self.stack.len() - layout.order.len());
+                    let mut fields: Vec<(String, Value)> = layout.fields.iter()
+                        .map(|field| (field.clone(), Value::Void))
+                        .collect();
+                    for (position, value) in layout.order.iter().zip(values) {
+                        fields[*position].1 = value;
+                    }
+                    self.stack.push(Value::instance(layout.name.clone(), fields));
+                },
+                Instruction::GetField(position) => {
+                    let value = self.pop_struct().borrow().fields[position].1.clone();
+                    self.stack.push(value);
+                },
+                Instruction::SetField(position) => {
+                    let value = self.pop();
+                    self.pop_struct().borrow_mut().fields[position].1 = value;
+                },
+                Instruction::MakeVariant(index) => {
+                    let layout = &program.variants[index];
+                    let fields = self.stack.split_off(self.stack.len() - layout.arity);
+                    self.stack.push(Value::variant(layout.name.clone(), layout.variant.clone(), fields));
+                },
+                Instruction::IsVariant(index) => {
+                    let layout = &program.variants[index];
+                    let value = self.pop_enum(&layout.name)?;
+                    self.stack.push(Value::Boolean(value.variant == layout.variant));
+                },
+                Instruction::VariantField(index, position) => {
+                    let value = self.pop_enum(&program.variants[index].name)?.fields[position].clone();
+                    self.stack.push(value);
+                },
+                Instruction::Trap(index) => return Err(program.errors[index].clone()),
+            }
+        }
+    }
+
+    /// returns the global variable or an error if it was not initialized yet
+    fn global(&self, index: usize) -> Result<&Value, RuntimeError> {
+        match &self.globals[index] {
+            Value::Void => Err(RuntimeError::UninitializedGlobal(self.program.globals[index].clone())),
+            value => Ok(value),
+        }
+    }
+
+    fn integer_operation(&mut self, operation: fn(i64, i64) -> Option<i64>) -> Result<(), RuntimeError> {
+        let right = self.pop_integer();
+        let left = self.pop_integer();
+        let result = operation(left, right).ok_or(RuntimeError::IntegerOverflow)?;
+        self.stack.push(Value::Integer(result));
+        Ok(())
+    }
+
+    fn float_operation(&mut self, operation: fn(f64, f64) -> f64) {
+        let right = self.pop_float();
+        let left = self.pop_float();
+        self.stack.push(Value::Float(operation(left, right)));
+    }
+
+    fn pop(&mut self) -> Value {
+        self.stack.pop().expect("the compiler keeps the value stack balanced")
+    }
+
+    fn pop_integer(&mut self) -> i64 {
+        match self.pop() {
+            Value::Integer(n) => n,
+            other => unreachable!("expected int, found {}", other.type_name()),
+        }
+    }
+
+    fn pop_float(&mut self) -> f64 {
+        match self.pop() {
+            Value::Float(n) => n,
+            other => unreachable!("expected float, found {}", other.type_name()),
+        }
+    }
+
+    fn pop_boolean(&mut self) -> bool {
+        match self.pop() {
+            Value::Boolean(b) => b,
+            other => unreachable!("expected boolean, found {}", other.type_name()),
+        }
+    }
+
+    fn pop_string(&mut self) -> String {
+        match self.pop() {
+            Value::String(s) => s,
+            other => unreachable!("expected string, found {}", other.type_name()),
+        }
+    }
+
+    fn pop_array(&mut self) -> Rc<RefCell<Vec<Value>>> {
+        match self.pop() {
+            Value::Array(elements) => elements,
+            other => unreachable!("expected array, found {}", other.type_name()),
+        }
+    }
+
+    fn pop_struct(&mut self) -> Rc<RefCell<Instance>> {
+        match self.pop() {
+            Value::Struct(instance) => instance,
+            other => unreachable!("expected struct, found {}", other.type_name()),
+        }
+    }
+
+    /// pops a value of the enum with this name, a value of another type is a RuntimeError
+    fn pop_enum(&mut self, name: &str) -> Result<Rc<EnumValue>,RuntimeError> {
+        match self.pop() {
+            Value::Enum(value) if value.name == name => Ok(value),
+            other => Err(RuntimeError::TypeMismatch(DataType::Named(name.into()), other.type_name())),
+        }
+    }
+}
+
+/// applies a comparison operator on two values of the same type
+fn compare<T: PartialOrd>(op: BinOp, left: &T, right: &T) -> bool {
+    match op {
+        BinOp::Eq => left == right,
+        BinOp::Neq => left != right,
+        BinOp::Gt => left > right,
+        BinOp::Ge => left >= right,
+        BinOp::Lt => left < right,
+        BinOp::Le => left <= right,
+        other => unreachable!("{} is not a comparison", other),
+    }
+}
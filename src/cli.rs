@@ -0,0 +1,168 @@
+use std::convert::TryFrom;
+use std::io::{self, Read};
+
+use crate::frontend::syntax::token::Token;
+
+/// the program finished without an error
+pub const EXIT_SUCCESS: i32 = 0;
+/// the arguments could not be parsed
+pub const EXIT_USAGE: i32 = 64;
+/// the source code contains a character or string the lexer can not read
+pub const EXIT_LEXER_ERROR: i32 = 65;
+/// the source code does not match the grammar
+pub const EXIT_PARSER_ERROR: i32 = 66;
+/// the resolver or the type checker found an error
+pub const EXIT_CHECK_ERROR: i32 = 67;
+/// the program failed while it was executed
+pub const EXIT_RUNTIME_ERROR: i32 = 70;
+/// the source file could not be read or the output could not be written
+pub const EXIT_IO_ERROR: i32 = 74;
+
+pub const USAGE: &str = "\
+usage: paper_castle <command> <file>
+
+commands:
+    run <file> [--interpret]   executes the main function of the script,
+                               an int returned by main is the exit code,
+                               an int outside of 0 to 255 exits with 255
+    check <file>               reports every error and warning of the script
+    tokens <file>              prints every token with its position
+    ast <file>                 prints the parsed abstract syntax tree as formatted source code
+    repl                       starts an interactive session
+    help                       prints this message
+
+use - as file to read the script from stdin.
+run executes the script on the virtual machine, --interpret uses the tree walking interpreter.
+
+exit codes:
+    64  invalid arguments
+    65  lexer error
+    66  parser error
+    67  name resolution or type error
+    70  runtime error
+    74  the file could not be read or the output could not be written";
+
+/// returns the exit code of a script which returns the int from main or calls exit with it.
+/// Only the codes 0 to 255 reach the calling process, so every other int exits with 255
+pub fn exit_code(code: i64) -> i32 {
+    u8::try_from(code).map_or(255, i32::from)
+}
+
+/// Command which is selected by the arguments of the binary
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub enum Command {
+    /// lex, parse, check and execute the script
+    Run { input: Input, interpret: bool },
+    /// report every diagnostic without executing the script
+    Check(Input),
+    /// print the tokens of the script
+    Tokens(Input),
+    /// print the abstract syntax tree of the script
+    Ast(Input),
+    /// start an interactive session
+    Repl,
+    Help,
+}
+
+/// source of the script
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub enum Input {
+    File(String),
+    /// the script is read from stdin, selected by "-"
+    Stdin,
+}
+
+impl Input {
+
+    fn from_argument(argument: String) -> Input {
+        if argument == "-" { Input::Stdin } else { Input::File(argument) }
+    }
+
+    /// returns the name which is shown in diagnostics
+    pub fn name(&self) -> &str {
+        match self {
+            Input::File(path) => path,
+            Input::Stdin => "<stdin>",
+        }
+    }
+
+    /// reads the whole script
+    pub fn read(&self) -> std::io::Result<String> {
+        match self {
+            Input::File(path) => std::fs::read_to_string(path),
+            Input::Stdin => {
+                let mut src = String::new();
+                std::io::stdin().read_to_string(&mut src)?;
+                Ok(src)
+            },
+        }
+    }
+}
+
+/// parses the arguments without the name of the binary into a Command.
+/// Returns a message which describes the mistake if the arguments are invalid
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Command, String> {
+    let mut args = args.into_iter();
+    let command = match args.next() {
+        Some(command) => command,
+        None => return Err("missing command".into()),
+    };
+    if command == "help" || command == "--help" || command == "-h" {
+        return Ok(Command::Help);
+    }
+    if command == "repl" {
+        return match args.next() {
+            Some(arg) => Err(format!("unexpected argument `{}`", arg)),
+            None => Ok(Command::Repl),
+        };
+    }
+
+    let mut file = None;
+    let mut interpret = false;
+    for arg in args {
+        if arg == "--interpret" && command == "run" {
+            interpret = true;
+        } else if arg.starts_with("--") {
+            return Err(format!("unknown option `{}` for `{}`", arg, command));
+        } else if file.is_none() {
+            file = Some(Input::from_argument(arg));
+        } else {
+            return Err(format!("unexpected argument `{}`", arg));
+        }
+    }
+    let input = match file {
+        Some(input) => input,
+        None if is_command(&command) => return Err(format!("missing file for `{}`", command)),
+        None => return Err(format!("unknown command `{}`", command)),
+    };
+
+    match command.as_str() {
+        "run" => Ok(Command::Run { input, interpret }),
+        "check" => Ok(Command::Check(input)),
+        "tokens" => Ok(Command::Tokens(input)),
+        "ast" => Ok(Command::Ast(input)),
+        _ => Err(format!("unknown command `{}`", command)),
+    }
+}
+
+/// handles a failed write to stdout. A reader which closed stdout, like `head` in
+/// `paper_castle tokens script.pc | head`, needs no more output, so the command ends quietly
+pub fn output_failed(e: io::Error) -> Result<(), i32> {
+    if e.kind() == io::ErrorKind::BrokenPipe {
+        return Ok(());
+    }
+    eprintln!("error: could not write the output: {}", e);
+    Err(EXIT_IO_ERROR)
+}
+
+/// describes the token with its position, kind and value for the tokens command
+pub fn describe_token(token: &Token) -> String {
+    let span = token.span();
+    let position = format!("{}:{}", span.line, span.column);
+    let kind = format!("{:?}", token.kind());
+    format!("{:<9} {:<30} {:?}", position, kind, token.value())
+}
+
+fn is_command(name: &str) -> bool {
+    matches!(name, "run" | "check" | "tokens" | "ast")
+}
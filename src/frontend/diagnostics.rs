@@ -0,0 +1,407 @@
+use std::fmt::Write;
+use std::io::IsTerminal;
+
+use crate::frontend::lexer::LexerError;
+use crate::frontend::parser::token_pattern::ParseError;
+use crate::frontend::semantic::natives::Native;
+use crate::frontend::semantic::resolver::{ResolveError, ResolveErrorKind};
+use crate::frontend::semantic::type_checker::{TypeError, TypeErrorKind};
+use crate::frontend::semantic::ENTRY_POINT;
+use crate::frontend::syntax::span::Span;
+use crate::frontend::syntax::token::{Token, TokenType};
+
+const RED: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[1;33m";
+const BLUE: &str = "\x1b[1;34m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+/// Defines how a Diagnostic is rendered
+#[derive(Eq, PartialEq, Copy, Clone, Hash, Debug)]
+pub enum Style {
+    /// plain text without any escape sequences
+    Plain,
+    /// text highlighted with ANSI colour codes for terminals
+    Colored,
+}
+
+impl Style {
+
+    /// returns Colored if stderr is a terminal, otherwise Plain
+    pub fn for_stderr() -> Style {
+        if std::io::stderr().is_terminal() { Style::Colored } else { Style::Plain }
+    }
+}
+
+/// How serious a reported problem is
+#[derive(Eq, PartialEq, Copy, Clone, Hash, Debug)]
+pub enum Severity {
+    /// the program can not be executed
+    Error,
+    /// the program can be executed, but probably does not what was intended
+    Warning,
+}
+
+/// Error report which points to the position in the source code where the error occurred.
+///
+/// ```text
+/// error: unexpected `}`
+///  --> main.pc:2:17
+///   |
+/// 2 |     let x: int = }
+///   |                  ^ expected one of `(`, identifier, literal
+///   |
+///   = help: ...
+/// ```
+#[derive(Eq, PartialEq, Clone, Hash, Debug)]
+pub struct Diagnostic {
+    severity: Severity,
+    message: String,
+    span: Span,
+    /// short text which is printed next to the underlined source code
+    label: Option<String>,
+    help: Vec<String>,
+}
+
+impl Diagnostic {
+
+    /// creates a new error report for the given range of the source code
+    pub fn error(message: String, span: Span) -> Self {
+        Diagnostic { severity: Severity::Error, message, span, label: None, help: Vec::new() }
+    }
+
+    /// creates a new warning for the given range of the source code
+    pub fn warning(message: String, span: Span) -> Self {
+        Diagnostic { severity: Severity::Warning, message, span, label: None, help: Vec::new() }
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// true if both diagnostics report the same problem at the same place, only their notes may differ
+    pub fn duplicates(&self, other: &Diagnostic) -> bool {
+        self.span == other.span && self.message == other.message
+    }
+
+    /// sets the text printed next to the underlined source code
+    pub fn with_label(mut self, label: String) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    /// adds a help note which is printed below the source code
+    pub fn with_help(mut self, help: String) -> Self {
+        self.help.push(help);
+        self
+    }
+
+    /// renders this diagnostic as compiler style report.
+    /// src has to be the source code the span of this diagnostic refers to.
+    pub fn render(&self, file_name: &str, src: &str, style: Style) -> String {
+        let (highlight, gutter, bold, reset) = match (style, self.severity) {
+            (Style::Plain, _) => ("", "", "", ""),
+            (Style::Colored, Severity::Error) => (RED, BLUE, BOLD, RESET),
+            (Style::Colored, Severity::Warning) => (YELLOW, BLUE, BOLD, RESET),
+        };
+        let title = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+
+        let line = src.split('\n').nth(self.span.line.saturating_sub(1)).unwrap_or("");
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        let line_number = self.span.line.to_string();
+        let indent = " ".repeat(line_number.len());
+
+        // keep tabs, so the underline is aligned with the printed source line
+        let prefix: String = line.chars()
+            .take(self.span.column.saturating_sub(1))
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        // spans over multiple lines are only underlined until the end of the first line
+        let underlined = src.get(self.span.start..self.span.end)
+            .map(|s| s.chars().take_while(|c| c != &'\n' && c != &'\r').count())
+            .unwrap_or(0)
+            .max(1);
+
+        let mut report = String::new();
+        let _ = writeln!(report, "{}{}{}{}: {}{}", highlight, title, reset, bold, self.message, reset);
+        let _ = writeln!(report, "{}{}-->{} {}:{}:{}", indent, gutter, reset, file_name, self.span.line, self.span.column);
+        let _ = writeln!(report, "{} {}|{}", indent, gutter, reset);
+        let _ = writeln!(report, "{}{} |{} {}", gutter, line_number, reset, line);
+        let _ = write!(report, "{} {}|{} {}{}{}{}", indent, gutter, reset, prefix, highlight, "^".repeat(underlined), reset);
+        if let Some(label) = &self.label {
+            let _ = write!(report, " {}{}{}", highlight, label, reset);
+        }
+        report.push('\n');
+        if !self.help.is_empty() {
+            let _ = writeln!(report, "{} {}|{}", indent, gutter, reset);
+        }
+        for help in &self.help {
+            let _ = writeln!(report, "{} {}={} {}help{}: {}", indent, gutter, reset, bold, reset, help);
+        }
+        report
+    }
+}
+
+impl From<&LexerError> for Diagnostic {
+    fn from(e: &LexerError) -> Self {
+        match e {
+            LexerError::UnexpectedEndOfString(span) => {
+                Diagnostic::error("unterminated string literal".into(), *span)
+                    .with_label("string starts here".into())
+                    .with_help("add a closing `\"` at the end of the string".into())
+            },
+            LexerError::UnknownCharacter(c, span) => {
+                Diagnostic::error(format!("unknown character `{}`", c), *span)
+                    .with_label("this character is not part of the language".into())
+            },
+            LexerError::InvalidEscape('u', span) => {
+                Diagnostic::error("invalid unicode escape".into(), *span)
+                    .with_help("write the code of the character with one to six hexadecimal digits like `\\u{1F427}`".into())
+            },
+            LexerError::InvalidEscape(c, span) => {
+                Diagnostic::error(format!("unknown escape sequence `\\{}`", c), *span)
+                    .with_help("valid escapes are `\\n`, `\\t`, `\\r`, `\\\"`, `\\\\`, `\\{`, `\\}` and `\\u{…}`, a raw string like `r\"…\"` has no escapes".into())
+            },
+            LexerError::MalformedNumber(reason, span) => {
+                Diagnostic::error("malformed number literal".into(), *span)
+                    .with_label((*reason).into())
+            },
+            LexerError::UnclosedInterpolation(span) => {
+                Diagnostic::error("unclosed `{` in a string literal".into(), *span)
+                    .with_label("this bracket starts an embedded expression which is never closed".into())
+                    .with_help("write `\\{` to put a `{` into the string, or close the expression with `}`".into())
+            },
+        }
+    }
+}
+
+impl From<&ParseError> for Diagnostic {
+    fn from(e: &ParseError) -> Self {
+        match e {
+            ParseError::WrongToken(found, expected) => {
+                let diagnostic = Diagnostic::error(format!("unexpected {}", describe(found)), found.span())
+                    .with_label(expected_one_of(expected));
+                if expected == &[TokenType::SeparatorSemiColon] {
+                    diagnostic.with_help("every statement has to end with `;`".into())
+                } else {
+                    diagnostic
+                }
+            },
+            ParseError::GrammarMistake(span, description) => Diagnostic::error((*description).into(), *span),
+            ParseError::UnexpectedEndOfStream(span) => {
+                Diagnostic::error("unexpected end of input".into(), *span)
+                    .with_label("no more tokens after this one".into())
+                    .with_help("the token source ended without an end of file token".into())
+            },
+            ParseError::Lexer(e) => Diagnostic::from(e),
+            ParseError::IntegerOutOfRange(literal) => {
+                Diagnostic::error(format!("integer literal `{}` is out of range", literal.value()), literal.span())
+                    .with_help(format!("an `int` is a number from {} to {}", i64::MIN, i64::MAX))
+            },
+            ParseError::FloatOutOfRange(literal) => {
+                Diagnostic::error(format!("float literal `{}` is out of range", literal.value()), literal.span())
+                    .with_help(format!("a `float` can not be larger than {:e} or closer to zero than {:e}", f64::MAX, f64::from_bits(1)))
+            },
+        }
+    }
+}
+
+/// a function of the standard library can only be called, so the help
+/// shows the lambda which turns it into a value, like `fn(a: int): int { abs(a) }`
+fn native_wrapper_help(native: Native) -> String {
+    let (args, return_type) = native.signatures().remove(0);
+    let names: Vec<String> = (b'a'..).zip(&args).map(|(name, _)| (name as char).to_string()).collect();
+    let params: Vec<String> = names.iter().zip(&args).map(|(name, data_type)| format!("{}: {}", name, data_type)).collect();
+    let return_type = return_type.map(|data_type| format!(": {}", data_type)).unwrap_or_default();
+    format!("`{}` is a function of the standard library and can not be used as a value, wrap it in a lambda like `fn({}){} {{ {}({}) }}`",
+            native.name(), params.join(", "), return_type, native.name(), names.join(", "))
+}
+
+impl From<&ResolveError> for Diagnostic {
+    fn from(e: &ResolveError) -> Self {
+        match &e.kind {
+            ResolveErrorKind::UndefinedVariable(name) => {
+                let diagnostic = Diagnostic::error(format!("cannot find variable `{}` in this scope", name), e.span)
+                    .with_label("not found in this scope".into());
+                match Native::lookup(name) {
+                    Some(native) => diagnostic.with_help(native_wrapper_help(native)),
+                    None => diagnostic,
+                }
+            },
+            ResolveErrorKind::UndefinedFunction(name) => {
+                Diagnostic::error(format!("cannot find function `{}`", name), e.span)
+                    .with_label("not declared anywhere in the program".into())
+            },
+            ResolveErrorKind::DuplicateFunction(name, first) => {
+                Diagnostic::error(format!("the function `{}` is defined multiple times", name), e.span)
+                    .with_label(format!("`{}` redefined here", name))
+                    .with_help(format!("the first definition of `{}` is on line {}", name, first.line))
+            },
+            ResolveErrorKind::ShadowedVariable(name, shadowed) => {
+                Diagnostic::warning(format!("variable `{}` shadows a previous declaration", name), e.span)
+                    .with_label(format!("`{}` is declared again here", name))
+                    .with_help(format!("the previous declaration of `{}` is on line {}", name, shadowed.line))
+            },
+            ResolveErrorKind::DuplicateParameter(name, first) => {
+                Diagnostic::error(format!("the parameter `{}` is declared multiple times", name), e.span)
+                    .with_label(format!("`{}` is used as name of another parameter", name))
+                    .with_help(format!("the first parameter `{}` is on line {}, give every parameter its own name", name, first.line))
+            },
+            ResolveErrorKind::DuplicateGlobal(name, first) => {
+                Diagnostic::error(format!("the global variable `{}` is defined multiple times", name), e.span)
+                    .with_label(format!("`{}` redefined here", name))
+                    .with_help(format!("the first definition of `{}` is on line {}", name, first.line))
+            },
+            ResolveErrorKind::AssignmentToConstant(name, declaration) => {
+                Diagnostic::error(format!("cannot assign twice to the constant `{}`", name), e.span)
+                    .with_label("cannot assign to a constant".into())
+                    .with_help(format!("declare `{}` with `let` on line {} to make it variable", name, declaration.line))
+            },
+            ResolveErrorKind::AssignmentToCapture(name, declaration) => {
+                Diagnostic::error(format!("cannot assign to the captured variable `{}`", name), e.span)
+                    .with_label("a lambda only has a copy of this variable".into())
+                    .with_help(format!("`{}` is declared on line {}, declare a new variable in the lambda instead", name, declaration.line))
+            },
+            ResolveErrorKind::NonConstantValue => {
+                Diagnostic::error("the value of a constant is not constant".into(), e.span)
+                    .with_label("only literals, operators and other constants are allowed here".into())
+            },
+        }
+    }
+}
+
+impl From<&TypeError> for Diagnostic {
+    fn from(e: &TypeError) -> Self {
+        match &e.kind {
+            TypeErrorKind::Mismatch(expected, found) => {
+                Diagnostic::error("mismatched types".into(), e.span)
+                    .with_label(format!("expected `{}`, found `{}`", expected, found))
+            },
+            TypeErrorKind::UndefinedVariable(name) => {
+                Diagnostic::error(format!("cannot find variable `{}` in this scope", name), e.span)
+                    .with_label("not found in this scope".into())
+            },
+            TypeErrorKind::UndefinedFunction(name) => {
+                Diagnostic::error(format!("cannot find function `{}`", name), e.span)
+                    .with_label("not declared anywhere in the program".into())
+            },
+            TypeErrorKind::WrongArgumentCount(name, expected, found) => {
+                Diagnostic::error(format!("function `{}` takes {} argument(s) but {} were supplied", name, expected, found), e.span)
+            },
+            TypeErrorKind::InvalidOperand(op, operand) => {
+                Diagnostic::error(format!("cannot apply unary operator `{}` to type `{}`", op, operand), e.span)
+            },
+            TypeErrorKind::InvalidOperands(op, left, right) => {
+                Diagnostic::error(format!("cannot apply `{}` to `{}` and `{}`", op, left, right), e.span)
+                    .with_label(format!("no implementation for `{} {} {}`", left, op, right))
+            },
+            TypeErrorKind::MissingReturn(name, return_type) => {
+                Diagnostic::error(format!("function `{}` does not return a value on every path", name), e.span)
+                    .with_help(format!("add a `return` of type `{}` at the end of the function", return_type))
+            },
+            TypeErrorKind::MissingReturnValue(return_type) => {
+                Diagnostic::error("`return` without a value".into(), e.span)
+                    .with_label(format!("expected a value of type `{}`", return_type))
+            },
+            TypeErrorKind::UnexpectedReturnValue(name) => {
+                Diagnostic::error(format!("function `{}` has no return type but returns a value", name), e.span)
+                    .with_help("declare the return type after the argument list, like `fn f(): int`".into())
+            },
+            TypeErrorKind::BreakOutsideLoop => {
+                Diagnostic::error("`break` outside of a loop".into(), e.span)
+                    .with_label("cannot `break` outside of a loop".into())
+            },
+            TypeErrorKind::BreakWithValue => {
+                Diagnostic::error("`break` with a value inside of a `while` or `for` loop".into(), e.span)
+                    .with_help("only `loop` can produce a value".into())
+            },
+            TypeErrorKind::ContinueOutsideLoop => {
+                Diagnostic::error("`continue` outside of a loop".into(), e.span)
+                    .with_label("cannot `continue` outside of a loop".into())
+            },
+            TypeErrorKind::ArrayElementMismatch(first, found) => {
+                Diagnostic::error("mismatched array element types".into(), e.span)
+                    .with_label(format!("expected `{}` like the first element, found `{}`", first, found))
+            },
+            TypeErrorKind::VoidArrayElement => {
+                Diagnostic::error("array element without a value".into(), e.span)
+                    .with_label("this expression produces no value".into())
+            },
+            TypeErrorKind::NotIndexable(found) => {
+                Diagnostic::error(format!("cannot index into a value of type `{}`", found), e.span)
+                    .with_help("only arrays can be indexed".into())
+            },
+            TypeErrorKind::UndefinedMethod(found, name) => {
+                Diagnostic::error(format!("no method named `{}` found for type `{}`", name, found), e.span)
+                    .with_help("arrays have the methods `len`, `push` and `pop`".into())
+            },
+            TypeErrorKind::UndefinedType(name) => {
+                Diagnostic::error(format!("cannot find type `{}`", name), e.span)
+                    .with_label("no struct or enum with this name is declared".into())
+            },
+            TypeErrorKind::MissingMainFunction => {
+                Diagnostic::error(format!("the program has no `{}` function", ENTRY_POINT), e.span)
+                    .with_label("the program has nothing to execute".into())
+                    .with_help(format!("declare `fn {}() {{}}`, the program starts with it", ENTRY_POINT))
+            },
+            TypeErrorKind::DuplicateType(name, first) => {
+                Diagnostic::error(format!("the type `{}` is defined multiple times", name), e.span)
+                    .with_label(format!("`{}` redefined here", name))
+                    .with_help(format!("the first definition of `{}` is on line {}", name, first.line))
+            },
+            TypeErrorKind::UndefinedField(found, name) => {
+                Diagnostic::error(format!("no field `{}` on type `{}`", name, found), e.span)
+            },
+            TypeErrorKind::MissingField(name, field) => {
+                Diagnostic::error(format!("missing field `{}` in the literal of `{}`", field, name), e.span)
+                    .with_label(format!("every field of `{}` needs a value", name))
+            },
+            TypeErrorKind::UndefinedVariant(name, variant) => {
+                Diagnostic::error(format!("no variant named `{}` found for enum `{}`", variant, name), e.span)
+            },
+            TypeErrorKind::NonExhaustiveMatch(witness) => {
+                Diagnostic::error(format!("non-exhaustive patterns: `{}` not covered", witness), e.span)
+                    .with_help("add an arm for this value or a `_` arm which matches every value".into())
+            },
+            TypeErrorKind::NotCallable(found) => {
+                Diagnostic::error(format!("cannot call a value of type `{}`", found), e.span)
+                    .with_help("only functions and lambdas can be called".into())
+            },
+            TypeErrorKind::NotInterpolatable(found) => {
+                Diagnostic::error(format!("cannot embed a value of type `{}` in a string", found), e.span)
+                    .with_help("only strings, numbers and booleans can be embedded".into())
+            },
+            TypeErrorKind::BranchMismatch(first, found) => {
+                Diagnostic::error("the branches have incompatible types".into(), e.span)
+                    .with_label(format!("expected `{}` like the first branch, found `{}`", first, found))
+            },
+        }
+    }
+}
+
+/// describes the found token, identifiers and literals are printed with their value
+fn describe(token: &Token) -> String {
+    match token.kind() {
+        TokenType::Identifier => format!("identifier `{}`", token.value()),
+        TokenType::LiteralInteger | TokenType::LiteralFloat => format!("{} `{}`", token.kind(), token.value()),
+        TokenType::LiteralString => format!("string literal \"{}\"", token.value()),
+        kind => kind.to_string(),
+    }
+}
+
+/// builds a text like "expected one of `,`, `)`" from the expected token types
+fn expected_one_of(expected: &[TokenType]) -> String {
+    let names: Vec<String> = expected.iter().map(|t| t.to_string()).collect();
+    match names.len() {
+        0 => "unexpected token".into(),
+        1 => format!("expected {}", names[0]),
+        _ => format!("expected one of {}", names.join(", ")),
+    }
+}
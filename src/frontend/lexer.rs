@@ -1,153 +1,295 @@
 use std::str::Chars;
 use std::iter::Peekable;
-use std::sync::mpsc::{channel, Sender, SendError};
-use std::thread::{JoinHandle};
+use std::sync::mpsc::channel;
 use crate::frontend::syntax::token::{TokenStream, Token, TokenType};
+use crate::frontend::syntax::span::Span;
 
-/// Lexer for splitting the source code into a vec of tokens
-pub struct Lexer;
+/// Lexer for splitting the source code into tokens.
+/// The Lexer is an iterator which reads one token after another from the borrowed source code.
+/// The last token is always an EoF token, unless a LexerError occurs.
+/// After the EoF token or an error the iterator returns None.
+pub struct Lexer<'a> {
+    cursor: Cursor<'a>,
+    /// interpolated strings whose embedded expression is read at the moment, the innermost is the last one
+    interpolations: Vec<Interpolation>,
+    finished: bool,
+}
+
+/// String like "value is {x + 1}" whose embedded expression is split into tokens
+#[derive(Copy, Clone, Debug)]
+struct Interpolation {
+    /// position of the opening quote
+    start: Mark,
+    /// opening bracket of the embedded expression which is read at the moment
+    bracket: Span,
+    /// number of open curly brackets inside of the embedded expression
+    depth: usize,
+}
 
-impl Lexer {
+impl<'a> Lexer<'a> {
 
+    pub fn new(src: &'a str) -> Self {
+        Lexer { cursor: Cursor::new(src), interpolations: Vec::new(), finished: false }
+    }
 
-    /// splits the input String into tokens.
-    /// Returns a TokenStream for receiving any produced token and a JoinHandle.
-    /// The JoinHandle returns the State of the Lexer after finishing.
-    /// The listener of the TokenStream will never received an error, if something fails
-    /// in this function, then the Error will be returned over the JoinHandle
-    pub fn tokenize(src: String) -> (TokenStream,JoinHandle<Result<(),LexerError>>) {
+    /// splits the input String on a separate thread into tokens.
+    /// Returns a TokenStream which receives every produced token and the LexerError,
+    /// if the lexer fails.
+    #[allow(dead_code)]
+    pub fn tokenize(src: String) -> TokenStream {
         let (tx,rx) = channel();
 
-        let handle = std::thread::Builder::new()
+        std::thread::Builder::new()
             .name("lexer_thread".into())
-            .spawn(move || Lexer::tokenize_inner(src,tx))
+            .spawn(move || {
+                for result in Lexer::new(&src) {
+                    // the receiver was dropped and no longer needs any token
+                    if tx.send(result).is_err() {
+                        break;
+                    }
+                }
+            })
             .expect("could not spawn lexer thread");
-        //let handle = spawn(move || {
-        //   Lexer::tokenize_inner(src,tx)
-        //});
 
-        (TokenStream::new(rx),handle)
+        TokenStream::new(rx)
     }
 
-    /// splits src into Tokens.
-    fn tokenize_inner(src: String,tx: Sender<Token>) -> Result<(),LexerError> {
-        
-        let mut iter = src.chars().into_iter().peekable();
+    /// reads the next token from the source code
+    fn read_token(&mut self) -> Result<Token,LexerError> {
+        let cursor = &mut self.cursor;
         loop {
 
-            let c = iter.next();
+            let start = cursor.mark();
+            let c = cursor.next();
             if c.is_none(){
                 break;
             }
             let c = c.unwrap();
             if c.is_whitespace() {continue;}
             if c == '#' {
-                Lexer::skip_comment(&mut iter);
+                Lexer::skip_comment(cursor);
                 continue;
             }
+            if c == '.' && cursor.peek().eq(&Some('.')) {
+                cursor.next();
+                return Ok(Token::new(TokenType::SeparatorRange,"..".into(),cursor.span_from(start)));
+            }
+            if c == ':' && cursor.peek().eq(&Some(':')) {
+                cursor.next();
+                return Ok(Token::new(TokenType::SeparatorPath,"::".into(),cursor.span_from(start)));
+            }
+            // a single underscore is the wildcard pattern, followed by letters it starts a name like _unused
+            if c == '_' && !cursor.peek().is_some_and(|next| next.is_alphanumeric() || next == '_') {
+                return Ok(Token::new(TokenType::Underscore,c.to_string(),cursor.span_from(start)));
+            }
+            // brackets inside of an embedded expression are counted, so the bracket
+            // which closes the expression continues the string
+            match (c, self.interpolations.last_mut()) {
+                ('{', Some(open)) => open.depth += 1,
+                ('}', Some(open)) if open.depth == 0 => return Lexer::read_string(cursor, start, &mut self.interpolations, true),
+                ('}', Some(open)) => open.depth -= 1,
+                _ => {},
+            }
             if is_separator(&c) {
                 let ttype = separator_to_token_type(&c);
-                tx.send(Token::new(ttype,c.to_string(),0))?;
-                continue;
+                return Ok(Token::new(ttype,c.to_string(),cursor.span_from(start)));
             }
             if is_operator(&c) {
-                let token = if c == '=' && iter.peek().eq(&Some(&'=')){
-                    let mut equal = c.to_string();
-                    equal.push(iter.next().unwrap());
-                    Token::new(TokenType::OperatorEqual,equal,0)
-                } else if c == '!' && iter.peek().eq(&Some(&'=')) {
-                    let mut  not_equal = c.to_string();
-                    not_equal.push(iter.next().unwrap());
-                    Token::new(TokenType::OperatorNotEqual,not_equal,0)
-                }else {
+                let second = cursor.peek();
+                let token = if let Some(kind) = second.and_then(|second| two_char_operator(c, second)) {
+                    let mut operator = c.to_string();
+                    operator.push(cursor.next().unwrap());
+                    Token::new(kind,operator,cursor.span_from(start))
+                } else if c == '&' || c == '|' {
+                    // only the doubled forms && and || are operators
+                    return Err(LexerError::UnknownCharacter(c, cursor.span_from(start)));
+                } else {
                     let kind = operator_to_token_type(&c);
-                    Token::new(kind,c.to_string(),0)
+                    Token::new(kind,c.to_string(),cursor.span_from(start))
                 };
-                tx.send(token)?;
-                continue;
+                return Ok(token);
             }
             if c == '"' {
-                let result = Lexer::read_string(&mut iter);
-                if result.is_ok(){
-                    tx.send(result.unwrap())?;
-                }
-                else { return Err(result.unwrap_err()) }
-                continue;
+                return Lexer::read_string(cursor, start, &mut self.interpolations, false);
             }
 
-            if c.is_numeric(){
-                let mut s = c.to_string();
-                loop{
-                    let read_next = {
-                        let peek = iter.peek();
-                        if peek.is_none() { break; }
-                        let next_char = peek.unwrap();
-                        next_char.is_alphanumeric() || next_char == &'.'
-                    };
-                    if read_next{
-                        s.push(iter.next().unwrap());
-                    } else { break; }
-                }
-                if s.contains("."){
-                    let token = Token::new(TokenType::LiteralFloat,s,0);
-                    tx.send(token)?;
-                }else {
-                    let token = Token::new(TokenType::LiteralInteger, s,0);
-                    tx.send(token)?;
-                }
-                continue;
+            if c.is_ascii_digit(){
+                return Lexer::read_number(cursor, c, start);
             }
 
-            if c.is_alphabetic() {
+            // a raw string like r"C:\path" contains no escape sequences
+            if c == 'r' && cursor.peek().eq(&Some('"')) {
+                cursor.next();
+                return Lexer::read_raw_string(cursor, start);
+            }
+
+            if c.is_alphabetic() || c == '_' {
                 let mut s = String::new();
                 s.push(c);
-                let result = Lexer::read_identifier(&mut iter, s);
-                if result.is_err(){return Err(result.unwrap_err())}
-                tx.send(result.unwrap())?;
-                continue;
+                return Lexer::read_identifier(cursor, s, start);
             }
 
             //our char seems to be something else
-           return Err(LexerError::UnknownCharacter(c));
+           return Err(LexerError::UnknownCharacter(c, cursor.span_from(start)));
         }
 
-        tx.send(Token::new(TokenType::EoF,"".into(),0))?;
-        Ok(())
+        if let Some(open) = self.interpolations.last() {
+            return Err(LexerError::UnclosedInterpolation(open.bracket));
+        }
+        let eof = cursor.mark();
+        Ok(Token::new(TokenType::EoF,"".into(),cursor.span_from(eof)))
     }
 
-    fn read_string(iter: &mut Peekable<Chars>) -> Result<Token,LexerError> {
+    /// reads the text of a string literal behind its opening quote, or behind the closing bracket
+    /// of an embedded expression if the string is continued. The text ends at the closing quote or
+    /// at the opening bracket of the next embedded expression, so an interpolated string is split into
+    /// an InterpolationStart, the tokens of every expression, InterpolationMiddles between them and an InterpolationEnd.
+    /// The value of the token contains the characters of the escape sequences instead of the sequences.
+    /// A string can span multiple lines, a backslash at the end of a line removes the line break
+    /// and the indentation of the next line
+    fn read_string(cursor: &mut Cursor, start: Mark, interpolations: &mut Vec<Interpolation>, continued: bool) -> Result<Token,LexerError> {
+        let string_start = if continued { interpolations.last().map_or(start, |open| open.start) } else { start };
         let mut string = String::new();
         loop {
-            let n = iter.next();
-            if n.is_none(){return Err(LexerError::UnexpectedEndOfString);}
-            let n = n.unwrap();
-            if n == '"' { return Ok(Token::new(TokenType::LiteralString,string,0));}
-            string.push(n);
+            let escape = cursor.mark();
+            match cursor.next() {
+                // a string inside of an embedded expression which reaches the end is most likely
+                // the rest of a string like "{" whose bracket was meant as text
+                None => return Err(match interpolations.last() {
+                    Some(open) if !continued => LexerError::UnclosedInterpolation(open.bracket),
+                    _ => LexerError::UnexpectedEndOfString(cursor.span_from(string_start)),
+                }),
+                Some('"') => {
+                    let kind = if continued {
+                        interpolations.pop();
+                        TokenType::InterpolationEnd
+                    } else {
+                        TokenType::LiteralString
+                    };
+                    return Ok(Token::new(kind,string,cursor.span_from(start)));
+                },
+                Some('{') => {
+                    let bracket = cursor.span_from(escape);
+                    let kind = match interpolations.last_mut() {
+                        Some(open) if continued => {
+                            open.bracket = bracket;
+                            TokenType::InterpolationMiddle
+                        },
+                        _ => {
+                            interpolations.push(Interpolation { start, bracket, depth: 0 });
+                            TokenType::InterpolationStart
+                        },
+                    };
+                    return Ok(Token::new(kind,string,cursor.span_from(start)));
+                },
+                Some('\\') => Lexer::read_escape(cursor, escape, &mut string)?,
+                Some(c) => string.push(c),
+            }
+        }
+    }
+
+    /// reads the escape sequence behind a backslash and appends its character to the string.
+    /// A missing character is reported as end of the string by the caller
+    fn read_escape(cursor: &mut Cursor, escape: Mark, string: &mut String) -> Result<(),LexerError> {
+        let c = match cursor.next() {
+            Some(c) => c,
+            None => return Ok(()),
         };
+        match c {
+            'n' => string.push('\n'),
+            't' => string.push('\t'),
+            'r' => string.push('\r'),
+            '"' => string.push('"'),
+            '\\' => string.push('\\'),
+            '{' => string.push('{'),
+            '}' => string.push('}'),
+            'u' => string.push(Lexer::read_unicode(cursor, escape)?),
+            c if is_newline(&c) => {
+                while cursor.peek().is_some_and(char::is_whitespace) {
+                    cursor.next();
+                }
+            },
+            c => return Err(LexerError::InvalidEscape(c, cursor.span_from(escape))),
+        }
+        Ok(())
+    }
+
+    /// reads the hexadecimal code of a unicode escape like \u{1F427} behind the u
+    fn read_unicode(cursor: &mut Cursor, escape: Mark) -> Result<char,LexerError> {
+        if cursor.peek() != Some('{') {
+            return Err(LexerError::InvalidEscape('u', cursor.span_from(escape)));
+        }
+        cursor.next();
+        let mut digits = String::new();
+        while let Some(c) = cursor.peek().filter(char::is_ascii_hexdigit) {
+            digits.push(c);
+            cursor.next();
+        }
+        let closed = cursor.peek() == Some('}');
+        if closed {
+            cursor.next();
+        }
+        let code = if closed && (1..=6).contains(&digits.len()) { u32::from_str_radix(&digits, 16).ok() } else { None };
+        code.and_then(char::from_u32).ok_or_else(|| LexerError::InvalidEscape('u', cursor.span_from(escape)))
+    }
+
+    /// reads a raw string behind its opening quote, every character until the closing quote belongs to it
+    fn read_raw_string(cursor: &mut Cursor, start: Mark) -> Result<Token,LexerError> {
+        let mut string = String::new();
+        loop {
+            match cursor.next() {
+                None => return Err(LexerError::UnexpectedEndOfString(cursor.span_from(start))),
+                Some('"') => return Ok(Token::new(TokenType::LiteralString,string,cursor.span_from(start))),
+                Some(c) => string.push(c),
+            }
+        }
+    }
+
+    /// reads a number literal like 42, 1_000, 0xFF, 0b1010, 0o17, 1.5 or 1e-9 which starts with this digit.
+    /// Every letter, digit, underscore and dot behind the digit belongs to the literal,
+    /// so a malformed literal like 12abc or 1.2.3 is reported as a whole.
+    /// The value of the token is the literal like it is written in the source code
+    fn read_number(cursor: &mut Cursor, first: char, start: Mark) -> Result<Token,LexerError> {
+        let mut s = first.to_string();
+        loop {
+            match cursor.peek() {
+                Some(c) if c.is_alphanumeric() || c == '_' => {},
+                // the dots of a range like 0..10 do not belong to the number
+                Some('.') if cursor.peek_second() != Some('.') => {},
+                // the sign of an exponent like 1e-9
+                Some('+') | Some('-') if s.ends_with(['e', 'E']) && split_radix(&s).0 == 10 => {},
+                _ => break,
+            }
+            s.push(cursor.next().unwrap());
+        }
+        match number_kind(&s) {
+            Ok(kind) => Ok(Token::new(kind,s,cursor.span_from(start))),
+            Err(reason) => Err(LexerError::MalformedNumber(reason, cursor.span_from(start))),
+        }
     }
 
     /// reads identifier or keyword Token from iterator
-    fn read_identifier(iter: &mut Peekable<Chars>, mut s: String) -> Result<Token,LexerError> {
+    fn read_identifier(cursor: &mut Cursor, mut s: String, start: Mark) -> Result<Token,LexerError> {
         loop {
             let read_next = {
-                let peek = iter.peek();
+                let peek = cursor.peek();
                 if peek.is_none() { break; }
                 let c = peek.unwrap();
-                c.is_alphanumeric()
+                c.is_alphanumeric() || c == '_'
             };
             if read_next{
-                s.push(iter.next().unwrap());
+                s.push(cursor.next().unwrap());
             } else {
                 break;
             }
         }
-        let kind = match_keyword(&*s);
-        return Ok(Token::new(kind,s,0));
+        let kind = match_keyword(&s);
+        Ok(Token::new(kind,s,cursor.span_from(start)))
     }
 
-    fn skip_comment(iter: &mut Peekable<Chars>){
+    fn skip_comment(cursor: &mut Cursor){
         loop{
-            let c = iter.next();
+            let c = cursor.next();
             if c.is_none(){break;}
             let c = c.unwrap();
             if is_newline(&c){break;}
@@ -155,29 +297,196 @@ impl Lexer {
     }
 }
 
+/// Position of the cursor, which can be used later to create the span of a token
+#[derive(Copy, Clone, Debug)]
+struct Mark {
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+/// Iterator over the characters of the source code which keeps track of
+/// the current byte offset, line and column.
+struct Cursor<'a> {
+    chars: Peekable<Chars<'a>>,
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+impl <'a>Cursor<'a> {
+
+    fn new(src: &'a str) -> Self {
+        Cursor { chars: src.chars().peekable(), offset: 0, line: 1, column: 1 }
+    }
+
+    /// reads the next char and moves the position behind this char
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        self.offset += c.len_utf8();
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    /// returns the char after the next char without moving the position
+    fn peek_second(&self) -> Option<char> {
+        self.chars.clone().nth(1)
+    }
+
+    /// returns the current position
+    fn mark(&self) -> Mark {
+        Mark { offset: self.offset, line: self.line, column: self.column }
+    }
+
+    /// returns the span from the marked position to the current position
+    fn span_from(&self, start: Mark) -> Span {
+        Span::new(start.offset, self.offset, start.line, start.column)
+    }
+}
+
+impl<'a> Iterator for Lexer<'a> {
+    type Item = Result<Token,LexerError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let result = self.read_token();
+        self.finished = match &result {
+            Ok(token) => token.kind() == TokenType::EoF,
+            Err(_) => true,
+        };
+        Some(result)
+    }
+}
+
 #[derive(Eq, PartialEq,Copy, Clone,Ord, PartialOrd,Hash,Debug)]
 pub enum LexerError{
-    UnexpectedEndOfString,
-    UnknownCharacter(char),
-    ClosedTokenStream,
+    /// string literal without closing quote, the span starts at the opening quote
+    UnexpectedEndOfString(Span),
+    UnknownCharacter(char,Span),
+    /// backslash in a string followed by this character which is no valid escape sequence,
+    /// the character is 'u' for an invalid unicode escape. The span contains the whole sequence
+    InvalidEscape(char,Span),
+    /// number literal like 12abc or 0b102 which is not a valid number, contains the reason
+    MalformedNumber(&'static str,Span),
+    /// embedded expression of a string which is never closed, the span contains its opening bracket
+    UnclosedInterpolation(Span),
+}
+
+impl LexerError {
+
+    /// returns the position of the error in the source code
+    pub fn span(&self) -> Span {
+        match self {
+            LexerError::UnexpectedEndOfString(span) => *span,
+            LexerError::UnknownCharacter(_, span) => *span,
+            LexerError::InvalidEscape(_, span) => *span,
+            LexerError::MalformedNumber(_, span) => *span,
+            LexerError::UnclosedInterpolation(span) => *span,
+        }
+    }
 }
 
-impl From<SendError<Token>> for LexerError {
-    fn from(_: SendError<Token>) -> Self {
-        LexerError::ClosedTokenStream
+/// returns the radix of the number literal and its digits behind the prefix
+/// like 0x for hexadecimal, 0b for binary and 0o for octal numbers
+pub fn split_radix(literal: &str) -> (u32, &str) {
+    match literal.get(..2) {
+        Some("0x") => (16, &literal[2..]),
+        Some("0b") => (2, &literal[2..]),
+        Some("0o") => (8, &literal[2..]),
+        _ => (10, literal),
     }
 }
 
+/// checks the number literal and returns if it is an integer or a float literal,
+/// returns the reason if the literal is malformed
+fn number_kind(literal: &str) -> Result<TokenType,&'static str> {
+    let (radix, digits) = split_radix(literal);
+    if radix != 10 {
+        check_digits(digits, radix)?;
+        return Ok(TokenType::LiteralInteger);
+    }
+    let (mantissa, exponent) = match literal.find(['e', 'E']) {
+        Some(position) => (&literal[..position], Some(&literal[position + 1..])),
+        None => (literal, None),
+    };
+    let (integral, fraction) = match mantissa.split_once('.') {
+        Some((integral, fraction)) => (integral, Some(fraction)),
+        None => (mantissa, None),
+    };
+    check_digits(integral, 10)?;
+    match fraction {
+        // a float like 9. can be written without fraction, but not in front of an exponent
+        Some("") if exponent.is_some() => return Err("expected digits behind the decimal point"),
+        Some("") | None => {},
+        Some(fraction) => check_digits(fraction, 10)?,
+    }
+    if let Some(exponent) = exponent {
+        check_digits(exponent.strip_prefix(['+', '-']).unwrap_or(exponent), 10)?;
+    }
+    if fraction.is_some() || exponent.is_some() {
+        Ok(TokenType::LiteralFloat)
+    } else {
+        Ok(TokenType::LiteralInteger)
+    }
+}
+
+/// checks that the digits are valid in the radix and every underscore stands between two digits
+fn check_digits(digits: &str, radix: u32) -> Result<(),&'static str> {
+    if digits.is_empty() {
+        return Err("expected digits");
+    }
+    if digits.starts_with('_') || digits.ends_with('_') || digits.contains("__") {
+        return Err("`_` has to stand between two digits");
+    }
+    if digits.chars().any(|c| c != '_' && !c.is_digit(radix)) {
+        return Err(match radix {
+            2 => "a binary number only contains the digits 0 and 1",
+            8 => "an octal number only contains the digits 0 to 7",
+            16 => "a hexadecimal number only contains the digits 0 to 9 and a to f",
+            _ => "a decimal number only contains the digits 0 to 9",
+        });
+    }
+    Ok(())
+}
+
 /// language operators like +
 fn is_operator(c: &char) -> bool {
     c == &'+' ||
         c == &'-' ||
         c == &'*' ||
         c == &'/' ||
+        c == &'%' ||
         c == &'=' ||
         c == &'<' ||
         c == &'>' ||
-        c == &'!'
+        c == &'!' ||
+        c == &'&' ||
+        c == &'|'
+}
+
+/// operators which are written with two characters like <= or &&
+fn two_char_operator(first: char, second: char) -> Option<TokenType> {
+    match (first, second) {
+        ('=', '=') => Some(TokenType::OperatorEqual),
+        ('=', '>') => Some(TokenType::SeparatorArrow),
+        ('!', '=') => Some(TokenType::OperatorNotEqual),
+        ('<', '=') => Some(TokenType::OperatorLessOrEqual),
+        ('>', '=') => Some(TokenType::OperatorGreaterOrEqual),
+        ('&', '&') => Some(TokenType::And),
+        ('|', '|') => Some(TokenType::Or),
+        _ => None,
+    }
 }
 
 fn operator_to_token_type(c: &char) -> TokenType{
@@ -186,6 +495,7 @@ fn operator_to_token_type(c: &char) -> TokenType{
         '-' => TokenType::OperatorMinus,
         '*' => TokenType::OperatorMultiplication,
         '/' => TokenType::OperatorDivide,
+        '%' => TokenType::OperatorModulo,
         '=' => TokenType::Assign,
         '!' => TokenType::OperatorNegation,
         '<' => TokenType::OperatorLessThen,
@@ -198,6 +508,8 @@ fn operator_to_token_type(c: &char) -> TokenType{
 fn is_separator(c: &char) -> bool{
     c == &'{' ||
         c == &'}' ||
+        c == &'[' ||
+        c == &']' ||
         c == &'(' ||
         c == &')' ||
         c == &';' ||
@@ -210,6 +522,8 @@ fn separator_to_token_type(c: &char) -> TokenType {
     match c {
         '{' => TokenType::SeparatorCurvedBracketOpen,
         '}' => TokenType::SeparatorCurvedBracketClosed,
+        '[' => TokenType::SeparatorSquareBracketOpen,
+        ']' => TokenType::SeparatorSquareBracketClosed,
         '(' => TokenType::SeparatorBracketOpen,
         ')' => TokenType::SeparatorBracketClose,
         ';' => TokenType::SeparatorSemiColon,
@@ -225,7 +539,14 @@ fn separator_to_token_type(c: &char) -> TokenType {
 fn match_keyword(value: &str) -> TokenType{
     match value {
         "let" => TokenType::Let,
+        "const" => TokenType::Const,
+        "struct" => TokenType::Struct,
+        "enum" => TokenType::Enum,
+        "impl" => TokenType::Impl,
+        "self" => TokenType::SelfValue,
         "for" => TokenType::For,
+        "in" => TokenType::In,
+        "step" => TokenType::Step,
         "loop" => TokenType::Loop,
         "break" => TokenType::Break,
         "continue" => TokenType::Continue,
@@ -234,6 +555,7 @@ fn match_keyword(value: &str) -> TokenType{
         "fn" => TokenType::Fn,
         "if" => TokenType::If,
         "else" => TokenType::Else,
+        "match" => TokenType::Match,
         "boolean" => TokenType::Boolean,
         "true" => TokenType::BooleanTrue,
         "false" => TokenType::BooleanFalse,
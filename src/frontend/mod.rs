@@ -1,3 +1,5 @@
 pub mod syntax;
 pub mod lexer;
-pub mod parser;
\ No newline at end of file
+pub mod parser;
+pub mod diagnostics;
+pub mod semantic;
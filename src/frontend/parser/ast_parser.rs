@@ -1,27 +1,60 @@
+use std::convert::TryFrom;
 use std::collections::VecDeque;
 
 use crate::frontend::parser::token_pattern::ParseError;
-use crate::frontend::syntax::ast::{AbstractSyntaxTree, Block, Expression, Statement, StatementKind, VariableBinding, BinOp, UnOp};
+use crate::frontend::syntax::ast::{AbstractSyntaxTree, Block, Expression, ExpressionKind, Statement, StatementKind, VariableBinding, BinOp, UnOp, NodeId, SELF, associated_name};
+use crate::frontend::syntax::ast::{InterpolationPart, MatchArm, Pattern, PatternKind, Variant};
+use crate::frontend::syntax::span::Span;
 use crate::frontend::syntax::{DataType, DataValue};
-use crate::frontend::syntax::token::{Token, TokenStream, TokenType};
+use crate::frontend::syntax::token::{Token, TokenType};
+use crate::frontend::lexer::{LexerError, split_radix};
 
 const TOKEN_STACK_SIZE: usize = 3;
 
-pub struct ASTParser{
+/// Parser which builds the AbstractSyntaxTree from any source of tokens,
+/// like the Lexer itself or the TokenStream of the lexer thread
+pub struct ASTParser<S: Iterator<Item = Result<Token,LexerError>>>{
     queue: VecDeque<Token>,
-    stream: TokenStream,
+    stream: S,
+    /// span of the last token returned by next()
+    previous_span: Span,
+    /// true after the EoF token was put into the queue
+    eof_reached: bool,
+    /// errors which were found and recovered from
+    errors: Vec<ParseError>,
+    /// id of the next created Expression or VariableBinding
+    next_id: NodeId,
 }
 
-impl ASTParser {
+impl<S: Iterator<Item = Result<Token,LexerError>>> ASTParser<S> {
 
-    pub fn new(stream: TokenStream) -> Self {
+    pub fn new(stream: S) -> Self {
         ASTParser {
             queue: VecDeque::with_capacity(TOKEN_STACK_SIZE),
             stream,
+            previous_span: Span::default(),
+            eof_reached: false,
+            errors: Vec::new(),
+            next_id: NodeId::default(),
         }
     }
 
-    pub fn parse(mut self) -> Result<AbstractSyntaxTree, ParseError> {
+    /// parses the whole TokenStream and returns the AbstractSyntaxTree or
+    /// every ParseError found in the TokenStream
+    pub fn parse(self) -> Result<AbstractSyntaxTree, Vec<ParseError>> {
+        let (ast, errors) = self.parse_with_recovery();
+        if errors.is_empty() {
+            Ok(ast)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// parses the whole TokenStream and continues after a ParseError.
+    /// The top level contains function, struct and enum declarations, impl blocks and the declarations of global variables and constants.
+    /// Returns the partial AbstractSyntaxTree which contains everything that could be parsed
+    /// and every ParseError in the order they occurred.
+    pub fn parse_with_recovery(mut self) -> (AbstractSyntaxTree, Vec<ParseError>) {
         self.init_stack();
 
         let mut statements = Vec::new();
@@ -29,40 +62,230 @@ impl ASTParser {
             if self.lookup_next().kind() == TokenType::EoF {
                 break;
             }
-            let function = self.parse_fn()?;
-            statements.push(function);
+            let item = match self.lookup_next().kind() {
+                TokenType::Fn => self.parse_fn(),
+                TokenType::Let => self.parse_let_stmt(),
+                TokenType::Const => self.parse_const_stmt(),
+                TokenType::Struct => self.parse_struct(),
+                TokenType::Enum => self.parse_enum(),
+                TokenType::Impl => self.parse_impl(),
+                _ => Err(self.unexpected(vec![TokenType::Fn, TokenType::Let, TokenType::Const, TokenType::Struct,
+                                              TokenType::Enum, TokenType::Impl])),
+            };
+            match item {
+                Ok(item) => statements.push(item),
+                Err(e) => {
+                    self.errors.push(e);
+                    self.synchronize_item();
+                },
+            }
         }
 
         let ast = AbstractSyntaxTree::new(statements);
-        Ok(ast)
+        (ast, self.take_errors())
+    }
+
+    /// parses a single function, struct or enum declaration, impl block, statement or expression like the input of an
+    /// interactive session. The semicolon behind an expression is optional.
+    /// Returns the statement and the first unused NodeId, so the ids of the next input can
+    /// continue after the ids of this input.
+    pub fn parse_statement(mut self) -> Result<(Statement, NodeId), Vec<ParseError>> {
+        self.init_stack();
+        let result = match self.lookup_next().kind() {
+            // "fn(" starts a lambda
+            TokenType::Fn if self.lookup_kind(1) != TokenType::SeparatorBracketOpen => self.parse_fn(),
+            TokenType::Struct => self.parse_struct(),
+            TokenType::Enum => self.parse_enum(),
+            TokenType::Impl => self.parse_impl(),
+            TokenType::Let | TokenType::Const | TokenType::For | TokenType::While |
+            TokenType::Break | TokenType::Continue | TokenType::Return => self.parse_stmt(),
+            _ => self.parse_expression().map(|expr| {
+                if self.match_next(TokenType::SeparatorSemiColon) {
+                    self.consume_next_token();
+                }
+                let span = self.span_from(expr.span);
+                Statement::new(StatementKind::Expression(expr), span)
+            }),
+        };
+        let stmt = match result {
+            Ok(stmt) if !self.match_next(TokenType::EoF) => {
+                let error = self.unexpected(vec![TokenType::EoF]);
+                self.errors.push(error);
+                Some(stmt)
+            },
+            Ok(stmt) => Some(stmt),
+            Err(e) => {
+                self.errors.push(e);
+                None
+            },
+        };
+
+        let errors = self.take_errors();
+        match stmt {
+            Some(stmt) if errors.is_empty() => Ok((stmt, self.next_id)),
+            _ => Err(errors),
+        }
+    }
+
+    /// starts the NodeIds of the parsed nodes with this id instead of the default id
+    pub fn with_first_id(mut self, id: NodeId) -> Self {
+        self.next_id = id;
+        self
+    }
+
+    /// returns every error found while parsing.
+    /// The source ends at a lexer error, so every error behind it is caused
+    /// by the missing tokens and not by a mistake in the source code.
+    fn take_errors(&mut self) -> Vec<ParseError> {
+        let lexer_error = self.errors.iter().find_map(|e| match e {
+            ParseError::Lexer(e) => Some(e.span()),
+            _ => None,
+        });
+        if let Some(lexer_error) = lexer_error {
+            self.errors.retain(|e| matches!(e, ParseError::Lexer(_)) || e.span().start < lexer_error.start);
+        }
+        std::mem::take(&mut self.errors)
     }
 
     /// we initialise our stack with the next 3 tokens
     fn init_stack(&mut self){
         for _ in 0..TOKEN_STACK_SIZE {
-            match self.stream.next() {
-                None => {break;},
-                Some(t) => { self.queue.push_back(t); },
-            };
+            self.pull();
         }
     }
 
-    /// Returns next token from the stack, panics if read after EOF
-    fn next(&mut self) -> Token {
+    /// reads the next token from the token source into the queue.
+    /// If the lexer failed or the source ends without an EoF token,
+    /// an EoF token is inserted, so the queue always ends with an EoF token.
+    fn pull(&mut self) {
+        if self.eof_reached {
+            return;
+        }
         match self.stream.next() {
-            None => {},
-            Some(token) => {
+            Some(Ok(token)) => {
+                self.eof_reached = token.kind() == TokenType::EoF;
                 self.queue.push_back(token);
             },
+            Some(Err(e)) => {
+                self.eof_reached = true;
+                self.errors.push(ParseError::Lexer(e));
+                self.queue.push_back(Token::new(TokenType::EoF, "".into(), e.span()));
+            },
+            None => {
+                self.eof_reached = true;
+                let span = self.queue.back().map(|t| t.span()).unwrap_or(self.previous_span);
+                self.errors.push(ParseError::UnexpectedEndOfStream(span));
+                self.queue.push_back(Token::new(TokenType::EoF, "".into(), span));
+            },
+        }
+    }
+
+    /// Returns next token from the stack.
+    /// The EoF token is never removed, so it is returned again on every call after the end.
+    fn next(&mut self) -> Token {
+        self.pull();
+        let token = match self.queue.front() {
+            Some(eof) if eof.kind() == TokenType::EoF => eof.clone(),
+            _ => self.queue.pop_front().expect("the queue always ends with an EoF token"),
         };
+        self.previous_span = token.span();
+        token
+    }
 
-        self.queue.pop_front().expect("called next after EOF")
+    /// returns the span from start until the end of the last consumed token
+    fn span_from(&self, start: Span) -> Span {
+        start.to(self.previous_span)
     }
 
     /// lookahead for the next token on the stack.
-    /// panics if look after EOF
     fn lookup_next(&mut self) -> &Token {
-        self.queue.front().expect("called lookup_next after EOF")
+        self.queue.front().expect("the queue always ends with an EoF token")
+    }
+
+    /// returns the kind of the token n positions behind the next token.
+    /// The stack holds TOKEN_STACK_SIZE tokens, tokens behind the end of the stack are EoF
+    fn lookup_kind(&self, n: usize) -> TokenType {
+        self.queue.get(n).map(|token| token.kind()).unwrap_or(TokenType::EoF)
+    }
+
+    /// creates a WrongToken error with the next token without consuming it,
+    /// so the error recovery decides which tokens are skipped
+    fn unexpected(&mut self, expected: Vec<TokenType>) -> ParseError {
+        ParseError::WrongToken(self.lookup_next().clone(), expected)
+    }
+
+    /// skips every token after a ParseError until the next top level declaration.
+    /// Stops in front of every 'fn', 'struct', 'enum' and 'impl' and in front of 'let' and 'const' outside of blocks,
+    /// so the declarations in the body of a broken function are skipped.
+    fn synchronize_item(&mut self) {
+        let mut depth = 0;
+        loop {
+            match self.lookup_next().kind() {
+                TokenType::EoF | TokenType::Struct | TokenType::Enum | TokenType::Impl => return,
+                // "fn(" starts a lambda
+                TokenType::Fn if self.lookup_kind(1) != TokenType::SeparatorBracketOpen => return,
+                TokenType::Let | TokenType::Const if depth == 0 => return,
+                TokenType::SeparatorCurvedBracketOpen => depth += 1,
+                TokenType::SeparatorCurvedBracketClosed if depth > 0 => depth -= 1,
+                _ => {},
+            }
+            self.consume_next_token();
+        }
+    }
+
+    /// skips every token after a ParseError until the next statement begins.
+    /// Stops after the next ';' or in front of '}', 'fn' and EoF.
+    /// Blocks which begin while skipping are skipped completely.
+    fn synchronize_statement(&mut self) {
+        let mut depth = 0;
+        loop {
+            match self.lookup_next().kind() {
+                TokenType::EoF | TokenType::Fn if self.lookup_kind(1) != TokenType::SeparatorBracketOpen => return,
+                TokenType::SeparatorSemiColon if depth == 0 => {
+                    self.consume_next_token();
+                    return;
+                },
+                TokenType::SeparatorCurvedBracketClosed if depth == 0 => return,
+                TokenType::SeparatorCurvedBracketClosed => {
+                    depth -= 1;
+                    self.consume_next_token();
+                    if depth == 0 {
+                        return;
+                    }
+                },
+                TokenType::SeparatorCurvedBracketOpen => {
+                    depth += 1;
+                    self.consume_next_token();
+                },
+                _ => self.consume_next_token(),
+            }
+        }
+    }
+
+    /// returns an unused NodeId
+    fn node_id(&mut self) -> NodeId {
+        let id = self.next_id;
+        self.next_id = id.successor();
+        id
+    }
+
+    /// creates a new Expression with an unique NodeId
+    fn expression(&mut self, kind: ExpressionKind, span: Span) -> Expression {
+        Expression::new(self.node_id(), kind, span)
+    }
+
+    /// creates a binary operator expression which spans from the left to the right operand
+    fn binary_op(&mut self, left: Expression, op: BinOp, right: Expression) -> Expression {
+        let span = left.span.to(right.span);
+        self.expression(ExpressionKind::BinaryOp(Box::from(left), op, Box::from(right)), span)
+    }
+
+    /// consumes the next token if it is one of the given operators and returns its BinOp
+    fn match_operator(&mut self, operators: &[(TokenType, BinOp)]) -> Option<BinOp> {
+        let kind = self.lookup_next().kind();
+        let op = operators.iter().find(|(token, _)| *token == kind).map(|(_, op)| *op)?;
+        self.consume_next_token();
+        Some(op)
     }
 
     fn match_next(&mut self,token_kind: TokenType) -> bool {
@@ -71,41 +294,150 @@ impl ASTParser {
 
     /// parses a single function to an Statement
     fn parse_fn(&mut self) -> Result<Statement,ParseError> {
-        let token = self.next();
-        if token.kind() != TokenType::Fn {
-            return Err(ParseError::WrongToken(token,vec![TokenType::Fn]));
-        }
-
-        if self.lookup_next().kind() != TokenType::Identifier {
-            return Err(ParseError::WrongToken(self.next(),vec![TokenType::Identifier]));
-        }
+        let fn_stmt_expr = self.parse_fn_decl(None)?;
+        let span = fn_stmt_expr.span;
+        let fn_stmt = Statement::new(StatementKind::Expression(fn_stmt_expr), span);
+        Ok(fn_stmt)
+    }
 
+    /// parses a function declaration. Functions in the impl block of a type can
+    /// take the receiver "self" as first argument
+    fn parse_fn_decl(&mut self, impl_type: Option<&str>) -> Result<Expression,ParseError> {
+        let start = self.lookup_next().span();
+        self.expect_nxt_and_consume(TokenType::Fn)?;
+        self.expect_nxt(TokenType::Identifier)?;
         let fn_name = self.next().move_value();
         //expecting parenthesis
-        if self.lookup_next().kind() != TokenType::SeparatorBracketOpen {
-            return Err(ParseError::WrongToken(self.next(), vec![TokenType::SeparatorBracketOpen]));
-        }
-        let args = self.parse_arg_list()?;
+        self.expect_nxt(TokenType::SeparatorBracketOpen)?;
+        let args = self.parse_arg_list(impl_type)?;
 
         let return_type = self.parse_return_type()?;
 
         //parsing the function body
         let block = self.parse_block_stmt()?;
         let opt_args = if args.is_empty() { None} else { Some(args) };
-        let fn_stmt_expr = Expression::FnDecl(fn_name, block, opt_args, return_type);
-        let fn_stmt = Statement::new(StatementKind::Expression(fn_stmt_expr));
-        Ok(fn_stmt)
+        let span = self.span_from(start);
+        Ok(self.expression(ExpressionKind::FnDecl(fn_name, block, opt_args, return_type), span))
+    }
+
+    /// parses the functions of a type like "impl Point { fn length(self): float {...} }"
+    fn parse_impl(&mut self) -> Result<Statement,ParseError> {
+        let start = self.lookup_next().span();
+        self.expect_nxt_and_consume(TokenType::Impl)?;
+        self.expect_nxt(TokenType::Identifier)?;
+        let name = self.next().move_value();
+        self.expect_nxt_and_consume(TokenType::SeparatorCurvedBracketOpen)?;
+        let mut functions = Vec::new();
+        while !self.match_next(TokenType::SeparatorCurvedBracketClosed) {
+            if !self.match_next(TokenType::Fn) {
+                return Err(self.unexpected(vec![TokenType::Fn, TokenType::SeparatorCurvedBracketClosed]));
+            }
+            functions.push(self.parse_fn_decl(Some(&name))?);
+        }
+        self.expect_nxt_and_consume(TokenType::SeparatorCurvedBracketClosed)?;
+        let span = self.span_from(start);
+        Ok(Statement::new(StatementKind::Impl(name, functions), span))
     }
 
-    /// reads from the Tokenstream to read the argument list from a function signature
-    fn parse_arg_list(&mut self) -> Result<Vec<VariableBinding>,ParseError>{
-        let next = self.next();
-        if next.kind() != TokenType::SeparatorBracketOpen {
-            return Err(ParseError::WrongToken(next,vec![TokenType::SeparatorBracketOpen]))
+    /// parses a struct declaration like "struct Point { x: float, y: float }".
+    /// A struct has at least one field, so "Name {}" is never a struct literal
+    fn parse_struct(&mut self) -> Result<Statement,ParseError> {
+        let start = self.lookup_next().span();
+        self.expect_nxt_and_consume(TokenType::Struct)?;
+        self.expect_nxt(TokenType::Identifier)?;
+        let name = self.next().move_value();
+        self.expect_nxt_and_consume(TokenType::SeparatorCurvedBracketOpen)?;
+        let mut fields: Vec<VariableBinding> = Vec::new();
+        while !self.match_next(TokenType::SeparatorCurvedBracketClosed) {
+            let field = self.parse_argument()?;
+            // the rest of the declaration is still valid, so parsing continues after the error
+            if fields.iter().any(|other| other.symbol == field.symbol) {
+                self.errors.push(ParseError::GrammarMistake(field.span, "the field is declared twice"));
+            } else {
+                fields.push(field);
+            }
+            if self.match_next(TokenType::SeparatorComma) {
+                self.consume_next_token();
+                continue;
+            }
+            if !self.match_next(TokenType::SeparatorCurvedBracketClosed) {
+                return Err(self.unexpected(vec![TokenType::SeparatorComma, TokenType::SeparatorCurvedBracketClosed]));
+            }
         }
+        self.expect_nxt_and_consume(TokenType::SeparatorCurvedBracketClosed)?;
+        let span = self.span_from(start);
+        if fields.is_empty() {
+            return Err(ParseError::GrammarMistake(span, "a struct needs at least one field"));
+        }
+        Ok(Statement::new(StatementKind::Struct(name, fields), span))
+    }
+
+    /// parses an enum declaration like "enum Shape { Circle(float), Rect(float, float), Empty }"
+    fn parse_enum(&mut self) -> Result<Statement,ParseError> {
+        let start = self.lookup_next().span();
+        self.expect_nxt_and_consume(TokenType::Enum)?;
+        self.expect_nxt(TokenType::Identifier)?;
+        let name = self.next().move_value();
+        self.expect_nxt_and_consume(TokenType::SeparatorCurvedBracketOpen)?;
+        let mut variants: Vec<Variant> = Vec::new();
+        while !self.match_next(TokenType::SeparatorCurvedBracketClosed) {
+            self.expect_nxt(TokenType::Identifier)?;
+            let variant = self.next();
+            let mut fields = Vec::new();
+            if self.match_next(TokenType::SeparatorBracketOpen) {
+                self.consume_next_token(); //Consume the opening (
+                while !self.match_next(TokenType::SeparatorBracketClose) {
+                    fields.push(self.parse_datatype()?);
+                    if self.match_next(TokenType::SeparatorComma) {
+                        self.consume_next_token();
+                        continue;
+                    }
+                    if !self.match_next(TokenType::SeparatorBracketClose) {
+                        return Err(self.unexpected(vec![TokenType::SeparatorComma, TokenType::SeparatorBracketClose]));
+                    }
+                }
+                self.expect_nxt_and_consume(TokenType::SeparatorBracketClose)?;
+            }
+            let span = self.span_from(variant.span());
+            // the rest of the declaration is still valid, so parsing continues after the error
+            if variants.iter().any(|other| other.name == variant.value()) {
+                self.errors.push(ParseError::GrammarMistake(span, "the variant is declared twice"));
+            } else {
+                variants.push(Variant { name: variant.move_value(), fields, span });
+            }
+            if self.match_next(TokenType::SeparatorComma) {
+                self.consume_next_token();
+                continue;
+            }
+            if !self.match_next(TokenType::SeparatorCurvedBracketClosed) {
+                return Err(self.unexpected(vec![TokenType::SeparatorComma, TokenType::SeparatorCurvedBracketClosed]));
+            }
+        }
+        self.expect_nxt_and_consume(TokenType::SeparatorCurvedBracketClosed)?;
+        let span = self.span_from(start);
+        if variants.is_empty() {
+            return Err(ParseError::GrammarMistake(span, "an enum needs at least one variant"));
+        }
+        Ok(Statement::new(StatementKind::Enum(name, variants), span))
+    }
+
+    /// reads from the Tokenstream to read the argument list from a function signature.
+    /// The first argument of a function in an impl block can be "self" without a type
+    fn parse_arg_list(&mut self, impl_type: Option<&str>) -> Result<Vec<VariableBinding>,ParseError>{
+        self.expect_nxt_and_consume(TokenType::SeparatorBracketOpen)?;
         let mut args = Vec::new();
         while self.lookup_next().kind() != TokenType::SeparatorBracketClose {
-            let arg = self.parse_argument()?;
+            let arg = match impl_type {
+                Some(impl_type) if args.is_empty() && self.match_next(TokenType::SelfValue) => {
+                    let span = self.next().span();
+                    VariableBinding::new(self.node_id(), DataType::Named(impl_type.into()), SELF.into(), span)
+                },
+                _ if self.match_next(TokenType::SelfValue) => {
+                    let span = self.lookup_next().span();
+                    return Err(ParseError::GrammarMistake(span, "`self` is only allowed as the first argument of a method"));
+                },
+                _ => self.parse_argument()?,
+            };
             args.push(arg);
             let next = self.lookup_next().kind();
             match next {
@@ -115,7 +447,7 @@ impl ASTParser {
                     continue;
                 },
                 TokenType::SeparatorBracketClose => {break;}
-                _=> {return Err(ParseError::WrongToken(self.next(), vec![TokenType::SeparatorComma,TokenType::SeparatorBracketClose]));}
+                _=> {return Err(self.unexpected(vec![TokenType::SeparatorComma,TokenType::SeparatorBracketClose]));}
             };
         }
         self.expect_nxt_and_consume(TokenType::SeparatorBracketClose)?;
@@ -131,7 +463,7 @@ impl ASTParser {
     /// returns a ParseError::WrongToken if the types dont match
     fn expect_nxt(&mut self, expect: TokenType) -> Result<(),ParseError> {
         if self.lookup_next().kind() != expect {
-            return Err(ParseError::WrongToken(self.next(),vec![expect]));
+            return Err(self.unexpected(vec![expect]));
         }
         Ok(())
     }
@@ -153,97 +485,83 @@ impl ASTParser {
         if self.match_next(TokenType::Assign) {
             self.consume_next_token(); //Consume the Assigment
             let value_expr = self.assignment()?;
-            if let Expression::Symbol(var) = expr {
-                return Ok(Expression::Assignment(var, Box::from(value_expr)));
-            }else {
-                return Err(ParseError::GrammarMistake("Expected symbol left on the assignment"));
-            }
+            let span = expr.span.to(value_expr.span);
+            let kind = match expr.kind {
+                ExpressionKind::Symbol(var) => ExpressionKind::Assignment(var, Box::from(value_expr)),
+                ExpressionKind::Index(array, index) => ExpressionKind::IndexAssignment(array, index, Box::from(value_expr)),
+                ExpressionKind::Field(object, field) => ExpressionKind::FieldAssignment(object, field, Box::from(value_expr)),
+                _ => return Err(ParseError::GrammarMistake(expr.span, "Expected symbol, array element or field left on the assignment")),
+            };
+            return Ok(self.expression(kind, span));
         }
         Ok(expr)
     }
 
     fn or(&mut self) -> Result<Expression,ParseError>{
-        let expr = self.and()?;
-        if self.match_next(TokenType::Or) {
-            self.consume_next_token();
-            let right_expr = self.and()?;
-            let or = Expression::BinaryOp(Box::from(expr),BinOp::Or, Box::from(right_expr));
-            return Ok(or);
+        let mut expr = self.and()?;
+        while let Some(op) = self.match_operator(&[(TokenType::Or, BinOp::Or)]) {
+            let right = self.and()?;
+            expr = self.binary_op(expr, op, right);
         }
         Ok(expr)
     }
 
     fn and(&mut self) -> Result<Expression,ParseError> {
-        let expr = self.equality()?;
-        if self.match_next(TokenType::And) {
-            self.consume_next_token();
-            let right_expr = self.equality()?;
-            let and_expr = Expression::BinaryOp( Box::from(expr),BinOp::And, Box::from(right_expr));
-            return Ok(and_expr);
+        let mut expr = self.equality()?;
+        while let Some(op) = self.match_operator(&[(TokenType::And, BinOp::And)]) {
+            let right = self.equality()?;
+            expr = self.binary_op(expr, op, right);
         }
         Ok(expr)
     }
 
     fn equality(&mut self) -> Result<Expression,ParseError> {
-        let expr = self.comparison()?;
-        let not_equal = self.match_next(TokenType::OperatorNotEqual);
-        let equal = self.match_next(TokenType::OperatorEqual);
-        if not_equal || equal {
-            self.consume_next_token();
-            let op = if equal { BinOp::Eq } else { BinOp::Neq };
+        let mut expr = self.comparison()?;
+        while let Some(op) = self.match_operator(&[
+            (TokenType::OperatorEqual, BinOp::Eq),
+            (TokenType::OperatorNotEqual, BinOp::Neq),
+        ]) {
             let right = self.comparison()?;
-            return Ok(Expression::BinaryOp(Box::from(expr),op, Box::from(right)));
+            expr = self.binary_op(expr, op, right);
         }
         Ok(expr)
     }
 
     fn comparison(&mut self) -> Result<Expression,ParseError> {
-        let expr = self.addition()?;
-
-        let greater = self.match_next(TokenType::OperatorGreaterThen);
-        let greater_equal = self.match_next(TokenType::OperatorGreaterOrEqual);
-        let less = self.match_next(TokenType::OperatorLessThen);
-        let less_equal = self.match_next(TokenType::OperatorLessOrEqual);
-
-        if greater {
-            self.consume_next_token();
-            return Ok(Expression::BinaryOp(Box::from(expr), BinOp::Gt, Box::from(self.addition()?)))
-        } else if greater_equal {
-            self.consume_next_token();
-            return Ok(Expression::BinaryOp(Box::from(expr), BinOp::Ge, Box::from(self.addition()?)))
-        } else if less {
-            self.consume_next_token();
-            return Ok(Expression::BinaryOp(Box::from(expr), BinOp::Lt, Box::from(self.addition()?)))
-        } else if less_equal {
-            self.consume_next_token();
-            return Ok(Expression::BinaryOp(Box::from(expr), BinOp::Le, Box::from(self.addition()?)))
+        let mut expr = self.addition()?;
+        while let Some(op) = self.match_operator(&[
+            (TokenType::OperatorGreaterThen, BinOp::Gt),
+            (TokenType::OperatorGreaterOrEqual, BinOp::Ge),
+            (TokenType::OperatorLessThen, BinOp::Lt),
+            (TokenType::OperatorLessOrEqual, BinOp::Le),
+        ]) {
+            let right = self.addition()?;
+            expr = self.binary_op(expr, op, right);
         }
-
         Ok(expr)
     }
 
     fn addition(&mut self) -> Result<Expression,ParseError> {
-        let expr = self.multiplication()?;
-        let addition = self.match_next(TokenType::OperatorPlus);
-        let subtraction = self.match_next(TokenType::OperatorMinus);
-        if addition || subtraction {
-            self.consume_next_token();
-            let op = if addition { BinOp::Plus } else { BinOp::Minus };
+        let mut expr = self.multiplication()?;
+        while let Some(op) = self.match_operator(&[
+            (TokenType::OperatorPlus, BinOp::Plus),
+            (TokenType::OperatorMinus, BinOp::Minus),
+        ]) {
             let right = self.multiplication()?;
-            return Ok(Expression::BinaryOp(Box::from(expr),op, Box::from(right)));
+            expr = self.binary_op(expr, op, right);
         }
         Ok(expr)
     }
 
     fn multiplication(&mut self) -> Result<Expression,ParseError> {
-        let expr = self.unary()?;
-        let multiplication = self.match_next(TokenType::OperatorMultiplication);
-        let divide = self.match_next(TokenType::OperatorDivide);
-        if multiplication || divide {
-            self.consume_next_token();
-            let op = if multiplication {BinOp::Multi}else{BinOp::Divide};
+        let mut expr = self.unary()?;
+        while let Some(op) = self.match_operator(&[
+            (TokenType::OperatorMultiplication, BinOp::Multi),
+            (TokenType::OperatorDivide, BinOp::Divide),
+            (TokenType::OperatorModulo, BinOp::Modulo),
+        ]) {
             let right = self.unary()?;
-            return Ok(Expression::BinaryOp(Box::from(expr), op, Box::from(right)));
+            expr = self.binary_op(expr, op, right);
         }
         Ok(expr)
     }
@@ -251,87 +569,402 @@ impl ASTParser {
     fn unary(&mut self) -> Result<Expression,ParseError> {
         let is_negation = self.match_next(TokenType::OperatorNegation);
         let is_negative = self.match_next(TokenType::OperatorMinus);
+        // "-9223372036854775808" is only an int if the minus belongs to the literal,
+        // unless the literal is the receiver of a call, an index or a field which binds stronger
+        let postfix = [TokenType::SeparatorBracketOpen, TokenType::SeparatorSquareBracketOpen, TokenType::SeparatorDot];
+        if is_negative && self.lookup_kind(1) == TokenType::LiteralInteger && !postfix.contains(&self.lookup_kind(2)) {
+            let start = self.next().span();
+            let token = self.next();
+            let span = start.to(token.span());
+            let value = integer_literal(token, true)?;
+            return Ok(self.expression(ExpressionKind::Literal(DataValue::Integer(value)), span));
+        }
         if is_negation || is_negative {
-            self.consume_next_token();
+            let start = self.next().span();
             let op = if is_negation {UnOp::Negation } else { UnOp::Minus };
             let right = self.unary()?;
-            return Ok(Expression::UnaryOp(op, Box::from(right)));
+            let span = start.to(right.span);
+            return Ok(self.expression(ExpressionKind::UnaryOp(op, Box::from(right)), span));
         }
         self.call()
     }
 
+    /// parses calls, index expressions, fields and method calls after an atom like "lines[0].points.len()"
     fn call(&mut self) -> Result<Expression,ParseError> {
         //if '(' comes after this token this value string is needed as function name
-        let expr = self.atom()?;
+        let mut expr = self.atom()?;
 
-        if self.match_next(TokenType::SeparatorBracketOpen) {
-            self.consume_next_token(); //Consume the opening (
-            let name = match expr {
-              Expression::Symbol(name) => name,
-                _ => return Err(ParseError::GrammarMistake("invalid function Name"))
+        loop {
+            let start = expr.span;
+            let kind = match self.lookup_next().kind() {
+                TokenType::SeparatorBracketOpen => {
+                    self.consume_next_token(); //Consume the opening (
+                    let arguments = self.parse_call_arguments()?;
+                    // a name is called directly, every other expression has to return a function
+                    match expr.kind {
+                        ExpressionKind::Symbol(name) => ExpressionKind::FnCall(name, arguments),
+                        _ => ExpressionKind::Call(Box::from(expr), arguments),
+                    }
+                },
+                TokenType::SeparatorSquareBracketOpen => {
+                    self.consume_next_token(); //Consume the opening [
+                    let index = self.parse_expression()?;
+                    self.expect_nxt_and_consume(TokenType::SeparatorSquareBracketClosed)?;
+                    ExpressionKind::Index(Box::from(expr), Box::from(index))
+                },
+                TokenType::SeparatorDot => {
+                    self.consume_next_token(); //Consume the .
+                    self.expect_nxt(TokenType::Identifier)?;
+                    let name = self.next().move_value();
+                    if self.match_next(TokenType::SeparatorBracketOpen) {
+                        self.consume_next_token(); //Consume the opening (
+                        let arguments = self.parse_call_arguments()?;
+                        ExpressionKind::MethodCall(Box::from(expr), name, arguments)
+                    } else {
+                        ExpressionKind::Field(Box::from(expr), name)
+                    }
+                },
+                _ => return Ok(expr),
             };
-            let mut arguments = Vec::new();
-            while !self.match_next(TokenType::SeparatorBracketClose){
-                let expr = self.parse_expression()?;
-                arguments.push(expr);
-                if self.match_next(TokenType::SeparatorComma) {
-                    self.consume_next_token();
-                    continue;
-                }
-            }
-            //consume the closing of the function call
-            self.expect_nxt_and_consume(TokenType::SeparatorBracketClose)?;
-            return Ok(Expression::FnCall(name, arguments));
+            let span = self.span_from(start);
+            expr = self.expression(kind, span);
         }
+    }
 
-        Ok(expr)
+    /// parses the arguments of a call after the opening bracket up to and including the closing bracket
+    fn parse_call_arguments(&mut self) -> Result<Vec<Expression>,ParseError> {
+        let mut arguments = Vec::new();
+        while !self.match_next(TokenType::SeparatorBracketClose){
+            let expr = self.parse_expression()?;
+            arguments.push(expr);
+            if self.match_next(TokenType::SeparatorComma) {
+                self.consume_next_token();
+                continue;
+            }
+            if !self.match_next(TokenType::SeparatorBracketClose) {
+                return Err(self.unexpected(vec![TokenType::SeparatorComma, TokenType::SeparatorBracketClose]));
+            }
+        }
+        //consume the closing of the call
+        self.expect_nxt_and_consume(TokenType::SeparatorBracketClose)?;
+        Ok(arguments)
     }
 
     fn atom(&mut self) -> Result<Expression,ParseError> {
+        let span = self.lookup_next().span();
+        let kind = self.lookup_next().kind();
+        let is_atom = matches!(kind,
+            TokenType::BooleanTrue | TokenType::BooleanFalse | TokenType::Identifier | TokenType::SelfValue |
+            TokenType::LiteralInteger | TokenType::LiteralFloat | TokenType::LiteralString | TokenType::InterpolationStart |
+            TokenType::SeparatorBracketOpen | TokenType::SeparatorSquareBracketOpen | TokenType::Fn |
+            TokenType::Match | TokenType::If | TokenType::Loop | TokenType::SeparatorCurvedBracketOpen);
+        if !is_atom {
+            return Err(ParseError::GrammarMistake(span, "Expected literal or identifier"));
+        }
+        if ends_with_block(kind) {
+            return self.parse_block_expression();
+        }
+        if kind == TokenType::Fn {
+            return self.parse_lambda();
+        }
+        if kind == TokenType::InterpolationStart {
+            return self.parse_interpolation();
+        }
         let token = self.next();
-        match token.kind() {
-            TokenType::BooleanTrue => Ok(Expression::Literal(DataValue::Boolean(true))),
-            TokenType::BooleanFalse => Ok(Expression::Literal(DataValue::Boolean(false))),
-            TokenType::Identifier => Ok(Expression::Symbol(token.move_value())),
-            TokenType::LiteralInteger => Ok(Expression::Literal(DataValue::Integer(token.move_value()))),
-            TokenType::LiteralFloat => Ok(Expression::Literal(DataValue::Float(token.move_value()))),
-            TokenType::LiteralString => Ok(Expression::Literal(DataValue::String(token.move_value()))),
+        let kind = match kind {
+            TokenType::BooleanTrue => ExpressionKind::Literal(DataValue::Boolean(true)),
+            TokenType::BooleanFalse => ExpressionKind::Literal(DataValue::Boolean(false)),
+            // a name followed by "{ field:" is a struct literal and not a name in front of a block
+            TokenType::Identifier if self.match_next(TokenType::SeparatorCurvedBracketOpen) &&
+                self.lookup_kind(1) == TokenType::Identifier && self.lookup_kind(2) == TokenType::SeparatorColon => {
+                return self.parse_struct_literal(token.move_value(), span);
+            },
+            // a function of a type like "Point::new(1.0, 2.0)" or a variant of an enum like "Shape::Circle(1.0)"
+            // is called with its full name, a variant without fields like "Shape::Empty" is named without a call
+            TokenType::Identifier if self.match_next(TokenType::SeparatorPath) => {
+                self.consume_next_token(); //Consume the ::
+                self.expect_nxt(TokenType::Identifier)?;
+                let name = associated_name(token.value(), self.next().value());
+                if !self.match_next(TokenType::SeparatorBracketOpen) {
+                    return Ok(self.expression(ExpressionKind::Symbol(name), self.span_from(span)));
+                }
+                self.consume_next_token(); //Consume the opening (
+                let arguments = self.parse_call_arguments()?;
+                ExpressionKind::FnCall(name, arguments)
+            },
+            TokenType::Identifier => ExpressionKind::Symbol(token.move_value()),
+            TokenType::SelfValue => ExpressionKind::Symbol(SELF.into()),
+            TokenType::LiteralInteger => ExpressionKind::Literal(DataValue::Integer(integer_literal(token, false)?)),
+            TokenType::LiteralFloat => ExpressionKind::Literal(DataValue::Float(float_literal(token, false)?)),
+            TokenType::LiteralString => ExpressionKind::Literal(DataValue::String(token.move_value())),
             TokenType::SeparatorBracketOpen => {
-                let expr = self.parse_expression()?;
+                let mut expr = self.parse_expression()?;
                 self.expect_nxt_and_consume(TokenType::SeparatorBracketClose)?;
-                Ok(expr)
+                //the parenthesis belong to the span of the inner expression
+                expr.span = self.span_from(span);
+                return Ok(expr);
+            },
+            TokenType::SeparatorSquareBracketOpen => {
+                let mut elements = Vec::new();
+                while !self.match_next(TokenType::SeparatorSquareBracketClosed) {
+                    elements.push(self.parse_expression()?);
+                    if self.match_next(TokenType::SeparatorComma) {
+                        self.consume_next_token();
+                        continue;
+                    }
+                    if !self.match_next(TokenType::SeparatorSquareBracketClosed) {
+                        return Err(self.unexpected(vec![TokenType::SeparatorComma, TokenType::SeparatorSquareBracketClosed]));
+                    }
+                }
+                self.expect_nxt_and_consume(TokenType::SeparatorSquareBracketClosed)?;
+                let span = self.span_from(span);
+                return Ok(self.expression(ExpressionKind::Array(elements), span));
+            },
+            _ => unreachable!("token was checked before"),
+        };
+        Ok(self.expression(kind, span))
+    }
+
+    /// parses a lambda like "fn(x: int): int { return x * 2; }", it has no name and can not take "self"
+    fn parse_lambda(&mut self) -> Result<Expression,ParseError> {
+        let start = self.lookup_next().span();
+        self.expect_nxt_and_consume(TokenType::Fn)?;
+        self.expect_nxt(TokenType::SeparatorBracketOpen)?;
+        let args = self.parse_arg_list(None)?;
+        let return_type = self.parse_return_type()?;
+        let body = self.parse_block_stmt()?;
+        let span = self.span_from(start);
+        Ok(self.expression(ExpressionKind::Lambda(args, return_type, body), span))
+    }
+
+    /// parses a match expression like "match shape { Shape::Circle(r) => r * r, _ => { ... } }".
+    /// The comma behind an arm is optional if its value is a block
+    fn parse_match(&mut self) -> Result<Expression,ParseError> {
+        let start = self.lookup_next().span();
+        self.expect_nxt_and_consume(TokenType::Match)?;
+        let value = self.parse_expression()?;
+        self.expect_nxt_and_consume(TokenType::SeparatorCurvedBracketOpen)?;
+        let mut arms = Vec::new();
+        while !self.match_next(TokenType::SeparatorCurvedBracketClosed) {
+            let pattern = self.parse_pattern()?;
+            self.expect_nxt_and_consume(TokenType::SeparatorArrow)?;
+            let is_block = self.match_next(TokenType::SeparatorCurvedBracketOpen);
+            let arm_value = if is_block {
+                let block = self.parse_block_stmt()?;
+                let span = block.span;
+                self.expression(ExpressionKind::Block(block), span)
+            } else {
+                self.parse_expression()?
+            };
+            arms.push(MatchArm { pattern, value: arm_value });
+            if self.match_next(TokenType::SeparatorComma) {
+                self.consume_next_token();
+                continue;
+            }
+            if !is_block && !self.match_next(TokenType::SeparatorCurvedBracketClosed) {
+                return Err(self.unexpected(vec![TokenType::SeparatorComma, TokenType::SeparatorCurvedBracketClosed]));
+            }
+        }
+        self.expect_nxt_and_consume(TokenType::SeparatorCurvedBracketClosed)?;
+        let span = self.span_from(start);
+        Ok(self.expression(ExpressionKind::Match(Box::new(value), arms), span))
+    }
+
+    /// parses the pattern of a match arm like "_", "n", "-1" or "Shape::Rect(w, _)"
+    fn parse_pattern(&mut self) -> Result<Pattern,ParseError> {
+        let start = self.lookup_next().span();
+        let kind = match self.lookup_next().kind() {
+            TokenType::Underscore => {
+                self.consume_next_token();
+                PatternKind::Wildcard
+            },
+            TokenType::Identifier if self.lookup_kind(1) == TokenType::SeparatorPath => {
+                let enum_name = self.next().move_value();
+                self.consume_next_token(); //Consume the ::
+                self.expect_nxt(TokenType::Identifier)?;
+                let variant = self.next().move_value();
+                let mut fields = Vec::new();
+                if self.match_next(TokenType::SeparatorBracketOpen) {
+                    self.consume_next_token(); //Consume the opening (
+                    while !self.match_next(TokenType::SeparatorBracketClose) {
+                        fields.push(self.parse_pattern()?);
+                        if self.match_next(TokenType::SeparatorComma) {
+                            self.consume_next_token();
+                            continue;
+                        }
+                        if !self.match_next(TokenType::SeparatorBracketClose) {
+                            return Err(self.unexpected(vec![TokenType::SeparatorComma, TokenType::SeparatorBracketClose]));
+                        }
+                    }
+                    self.expect_nxt_and_consume(TokenType::SeparatorBracketClose)?;
+                }
+                PatternKind::Variant(enum_name, variant, fields)
+            },
+            TokenType::Identifier => PatternKind::Binding(self.next().move_value()),
+            TokenType::BooleanTrue | TokenType::BooleanFalse => {
+                PatternKind::Literal(DataValue::Boolean(self.next().kind() == TokenType::BooleanTrue))
+            },
+            TokenType::LiteralString => PatternKind::Literal(DataValue::String(self.next().move_value())),
+            TokenType::LiteralInteger | TokenType::LiteralFloat | TokenType::OperatorMinus => {
+                // the minus of a negative number belongs to the literal
+                let negative = self.match_next(TokenType::OperatorMinus);
+                if negative {
+                    self.consume_next_token();
+                }
+                let token = self.next();
+                match token.kind() {
+                    TokenType::LiteralInteger => PatternKind::Literal(DataValue::Integer(integer_literal(token, negative)?)),
+                    TokenType::LiteralFloat => PatternKind::Literal(DataValue::Float(float_literal(token, negative)?)),
+                    _ => return Err(ParseError::WrongToken(token, vec![TokenType::LiteralInteger, TokenType::LiteralFloat])),
+                }
             },
-            _ => Err(ParseError::GrammarMistake("Expected literal or identifier"))
+            _ => return Err(ParseError::GrammarMistake(start, "Expected a pattern like `_`, a name, a literal or a variant")),
+        };
+        let span = self.span_from(start);
+        Ok(Pattern::new(self.node_id(), kind, span))
+    }
+
+    /// parses the fields of a struct literal like "{ x: 1.0, y: 2.0 }" behind the name of the struct
+    fn parse_struct_literal(&mut self, name: String, start: Span) -> Result<Expression,ParseError> {
+        self.expect_nxt_and_consume(TokenType::SeparatorCurvedBracketOpen)?;
+        let mut fields: Vec<(String, Expression)> = Vec::new();
+        while !self.match_next(TokenType::SeparatorCurvedBracketClosed) {
+            self.expect_nxt(TokenType::Identifier)?;
+            let field = self.next();
+            self.expect_nxt_and_consume(TokenType::SeparatorColon)?;
+            let value = self.parse_expression()?;
+            if fields.iter().any(|(other, _)| other == field.value()) {
+                self.errors.push(ParseError::GrammarMistake(field.span(), "the field is assigned twice"));
+            } else {
+                fields.push((field.move_value(), value));
+            }
+            if self.match_next(TokenType::SeparatorComma) {
+                self.consume_next_token();
+                continue;
+            }
+            if !self.match_next(TokenType::SeparatorCurvedBracketClosed) {
+                return Err(self.unexpected(vec![TokenType::SeparatorComma, TokenType::SeparatorCurvedBracketClosed]));
+            }
         }
+        self.expect_nxt_and_consume(TokenType::SeparatorCurvedBracketClosed)?;
+        let span = self.span_from(start);
+        Ok(self.expression(ExpressionKind::StructLiteral(name, fields), span))
     }
 
     fn parse_argument(&mut self) -> Result<VariableBinding,ParseError> {
         //identifier : DataType
-        let symbol = match self.lookup_next().kind(){
-            TokenType::Identifier => self.next(),
-            _ => return Err(ParseError::WrongToken(self.next(),vec![TokenType::Identifier]))
-        };
-        let colon = self.next();
-        if colon.kind() != TokenType::SeparatorColon {
-            return Err(ParseError::WrongToken(colon,vec![TokenType::SeparatorColon]));
-        }
+        self.expect_nxt(TokenType::Identifier)?;
+        let symbol = self.next();
+        self.expect_nxt_and_consume(TokenType::SeparatorColon)?;
         let datatype = self.parse_datatype()?;
-        Ok(VariableBinding::new(datatype,symbol.move_value()))
+        let span = self.span_from(symbol.span());
+        Ok(VariableBinding::new(self.node_id(),datatype,symbol.move_value(),span))
     }
 
+    /// parses a block of statements. An expression without a semicolon in front of the
+    /// closing bracket is the value of the block
     fn parse_block_stmt(&mut self) -> Result<Block,ParseError>{
-        let token = self.next();
-        match token.kind() {
-            TokenType::SeparatorCurvedBracketOpen => {},
-            _ => {return Err(ParseError::WrongToken(token,vec![TokenType::SeparatorCurvedBracketOpen]))}
-        }
+        let start = self.lookup_next().span();
+        self.expect_nxt_and_consume(TokenType::SeparatorCurvedBracketOpen)?;
         let mut stmts = Vec::with_capacity(20);
-        while self.lookup_next().kind() != TokenType::SeparatorCurvedBracketClosed {
-            let stmt = self.parse_stmt()?;
-            stmts.push(stmt);
+        let mut value = None;
+        loop {
+            match self.lookup_next().kind() {
+                TokenType::SeparatorCurvedBracketClosed => break,
+                // the closing bracket is missing, the block ends here so the
+                // next function can be parsed. "fn(" starts a lambda, nothing follows EoF
+                TokenType::EoF | TokenType::Fn if self.lookup_kind(1) != TokenType::SeparatorBracketOpen => {
+                    let error = self.unexpected(vec![TokenType::SeparatorCurvedBracketClosed]);
+                    self.errors.push(error);
+                    return Ok(Block::new(stmts, self.span_from(start)));
+                },
+                TokenType::Let | TokenType::Const | TokenType::For | TokenType::While |
+                TokenType::Break | TokenType::Continue | TokenType::Return => match self.parse_stmt() {
+                    Ok(stmt) => stmts.push(stmt),
+                    Err(e) => {
+                        self.errors.push(e);
+                        self.synchronize_statement();
+                    },
+                },
+                _ => match self.parse_expression_item() {
+                    Ok((expr, true)) => {
+                        value = Some(expr);
+                        break;
+                    },
+                    Ok((expr, false)) => {
+                        let span = self.span_from(expr.span);
+                        stmts.push(Statement::new(StatementKind::Expression(expr), span));
+                    },
+                    Err(e) => {
+                        self.errors.push(e);
+                        self.synchronize_statement();
+                    },
+                },
+            }
         }
         self.expect_nxt_and_consume(TokenType::SeparatorCurvedBracketClosed)?;
-        Ok(Block::new(stmts))
+        let span = self.span_from(start);
+        Ok(match value {
+            Some(value) => Block::with_value(stmts, value, span),
+            None => Block::new(stmts, span),
+        })
+    }
+
+    /// parses an expression in a block and returns true with it if it is the value of the block.
+    /// The semicolon behind an expression which ends with a block like an if or a match is optional,
+    /// so it ends the statement on its own
+    fn parse_expression_item(&mut self) -> Result<(Expression, bool),ParseError> {
+        let block_like = ends_with_block(self.lookup_next().kind());
+        let expr = if block_like { self.parse_block_expression()? } else { self.parse_expression()? };
+        if self.match_next(TokenType::SeparatorSemiColon) {
+            self.consume_next_token();
+            return Ok((expr, false));
+        }
+        if self.match_next(TokenType::SeparatorCurvedBracketClosed) {
+            return Ok((expr, true));
+        }
+        if block_like {
+            return Ok((expr, false));
+        }
+        Err(self.unexpected(vec![TokenType::SeparatorSemiColon]))
+    }
+
+    /// parses an interpolated string like "value is {x + 1}". The lexer splits it into the text in front of
+    /// every embedded expression, the tokens of the expression and the text behind the last expression
+    fn parse_interpolation(&mut self) -> Result<Expression,ParseError> {
+        let start = self.lookup_next().span();
+        let mut parts = Vec::new();
+        let mut text = self.next();
+        loop {
+            // empty texts like the one in front of "{x}" are left out
+            if !text.value().is_empty() {
+                parts.push(InterpolationPart::Text(text.value().into()));
+            }
+            if text.kind() == TokenType::InterpolationEnd {
+                break;
+            }
+            parts.push(InterpolationPart::Value(self.parse_expression()?));
+            if !self.match_next(TokenType::InterpolationMiddle) && !self.match_next(TokenType::InterpolationEnd) {
+                return Err(self.unexpected(vec![TokenType::InterpolationEnd]));
+            }
+            text = self.next();
+        }
+        let span = self.span_from(start);
+        Ok(self.expression(ExpressionKind::Interpolation(parts), span))
+    }
+
+    /// parses an if, a match, a loop or a block used as expression
+    fn parse_block_expression(&mut self) -> Result<Expression,ParseError> {
+        match self.lookup_next().kind() {
+            TokenType::If => self.parse_if(),
+            TokenType::Match => self.parse_match(),
+            TokenType::Loop => self.parse_loop(),
+            _ => {
+                let block = self.parse_block_stmt()?;
+                let span = block.span;
+                Ok(self.expression(ExpressionKind::Block(block), span))
+            },
+        }
     }
 
     fn parse_return_type(&mut self) -> Result<Option<DataType>,ParseError>{
@@ -341,66 +974,93 @@ impl ASTParser {
             return Ok(Some(datatype));
             //if we dont find a return type it must follow the function body/ block main(){}
         } else if self.lookup_next().kind() != TokenType::SeparatorCurvedBracketOpen {
-            return Err(ParseError::WrongToken(self.next(),vec![TokenType::SeparatorColon,TokenType::SeparatorCurvedBracketOpen]));
+            return Err(self.unexpected(vec![TokenType::SeparatorColon,TokenType::SeparatorCurvedBracketOpen]));
         }
         Ok(Option::None)
     }
 
     fn parse_datatype(&mut self) -> Result<DataType,ParseError> {
-        let datatype_token = self.next();
-        let datatype = match datatype_token.kind() {
+        let datatype = match self.lookup_next().kind() {
             TokenType::Boolean => { DataType::Boolean },
             TokenType::Integer => { DataType::Integer },
             TokenType::Float => { DataType::Float },
             TokenType::String => { DataType::String },
-            _ => { return Err(ParseError::WrongToken(datatype_token, vec![TokenType::String, TokenType::Float,TokenType::Boolean, TokenType::Integer])) }
+            TokenType::SeparatorSquareBracketOpen => {
+                self.consume_next_token(); //Consume the opening [
+                let element = self.parse_datatype()?;
+                self.expect_nxt(TokenType::SeparatorSquareBracketClosed)?;
+                DataType::Array(Box::new(element))
+            },
+            TokenType::Identifier => { DataType::Named(self.lookup_next().value().into()) },
+            TokenType::Fn => return self.parse_function_type(),
+            _ => { return Err(self.unexpected(vec![TokenType::String, TokenType::Float,TokenType::Boolean, TokenType::Integer,
+                                                   TokenType::SeparatorSquareBracketOpen, TokenType::Identifier, TokenType::Fn])) }
         };
+        self.consume_next_token();
         Ok(datatype)
     }
 
+    /// parses the type of a function value like "fn(int, string): boolean", the return type is optional
+    fn parse_function_type(&mut self) -> Result<DataType,ParseError> {
+        self.expect_nxt_and_consume(TokenType::Fn)?;
+        self.expect_nxt_and_consume(TokenType::SeparatorBracketOpen)?;
+        let mut args = Vec::new();
+        while !self.match_next(TokenType::SeparatorBracketClose) {
+            args.push(self.parse_datatype()?);
+            if self.match_next(TokenType::SeparatorComma) {
+                self.consume_next_token();
+                continue;
+            }
+            if !self.match_next(TokenType::SeparatorBracketClose) {
+                return Err(self.unexpected(vec![TokenType::SeparatorComma, TokenType::SeparatorBracketClose]));
+            }
+        }
+        self.expect_nxt_and_consume(TokenType::SeparatorBracketClose)?;
+        let return_type = if self.match_next(TokenType::SeparatorColon) {
+            self.consume_next_token(); //Consume the :
+            Some(Box::new(self.parse_datatype()?))
+        } else {
+            None
+        };
+        Ok(DataType::Function(args, return_type))
+    }
+
     fn parse_stmt(&mut self) -> Result<Statement,ParseError> {
-        //possible statements
-        // fnCall identifer.identifier ( expression )
-        // if
+        //possible statements which start with a keyword,
+        //every other statement is an expression
         // let declaration
+        // const declaration
+        // for
         // break
         // continue
         // return
         // while
-        // loop
 
         let next_token = self.lookup_next().kind();
         let stmt = match next_token {
             TokenType::Let => {self.parse_let_stmt()? },
-            TokenType::For => {unimplemented!("For keyword is not supported yet")}, //TODO for loop does not exists at the moment
-            TokenType::Loop => {self.parse_loop()?},
+            TokenType::Const => {self.parse_const_stmt()? },
+            TokenType::For => {self.parse_for_stmt()?},
             TokenType::Break => {self.parse_break_stmt()?},
             TokenType::Continue => {self.parse_continue_stmt()?},
             TokenType::Return => {self.parse_return_stmt()?},
             TokenType::While => {self.parse_while_stmt()?},
-            TokenType::If => { self.parse_if()? },
-            TokenType::Identifier => {self.parse_expression_stmt()?},
-            _ => {return Err(ParseError::WrongToken(self.next(),vec![
-                TokenType::If,
+            _ => {return Err(self.unexpected(vec![
                 TokenType::For,
                 TokenType::Let,
-                TokenType::Loop,
+                TokenType::Const,
                 TokenType::Break,
+                TokenType::Continue,
                 TokenType::Return,
-                TokenType::While
+                TokenType::While,
             ]))}
         };
 
         Ok(stmt)
     }
 
-    fn parse_expression_stmt(&mut self) -> Result<Statement,ParseError> {
-        let expr = self.parse_expression()?;
-        self.expect_nxt_and_consume(TokenType::SeparatorSemiColon)?;
-        Ok(Statement::new(StatementKind::Expression(expr)))
-    }
-
-    fn parse_if(&mut self) -> Result<Statement,ParseError> {
+    fn parse_if(&mut self) -> Result<Expression,ParseError> {
+        let start = self.lookup_next().span();
         self.expect_nxt_and_consume(TokenType::If)?;
         let condition_expr = Box::new(self.parse_expression()?);
         self.expect_nxt(TokenType::SeparatorCurvedBracketOpen)?;
@@ -413,68 +1073,136 @@ impl ASTParser {
             }
             _ => {None}
         };
-        let if_expr = Expression::If(condition_expr, if_block, else_block);
-        let if_stmt = Statement::new(StatementKind::Expression(if_expr));
-        Ok(if_stmt)
+        let span = self.span_from(start);
+        Ok(self.expression(ExpressionKind::If(condition_expr, if_block, else_block), span))
     }
 
     fn parse_let_stmt(&mut self) -> Result<Statement,ParseError> {
-        self.expect_nxt_and_consume(TokenType::Let)?;
+        self.parse_declaration(TokenType::Let, StatementKind::Declaration)
+    }
+
+    fn parse_const_stmt(&mut self) -> Result<Statement,ParseError> {
+        self.parse_declaration(TokenType::Const, StatementKind::Constant)
+    }
+
+    /// parses "keyword identifier : DataType = expression ;" and creates the statement with kind
+    fn parse_declaration(&mut self, keyword: TokenType, kind: fn(VariableBinding, Expression) -> StatementKind) -> Result<Statement,ParseError> {
+        let start = self.lookup_next().span();
+        self.expect_nxt_and_consume(keyword)?;
         self.expect_nxt(TokenType::Identifier)?;
-        let variable_name = self.next().move_value();
+        let variable = self.next();
+        let variable_span = variable.span();
+        let variable_name = variable.move_value();
         self.expect_nxt_and_consume(TokenType::SeparatorColon)?;
         let variable_type = self.parse_datatype()?;
+        let binding = VariableBinding::new(self.node_id(),variable_type,variable_name,self.span_from(variable_span));
         self.expect_nxt_and_consume(TokenType::Assign)?;
         let expr = self.parse_expression()?;
-        let binding = VariableBinding::new(variable_type,variable_name);
         self.expect_nxt_and_consume(TokenType::SeparatorSemiColon)?;
-        Ok(Statement::new(StatementKind::Declaration(binding,expr)))
+        Ok(Statement::new(kind(binding,expr), self.span_from(start)))
     }
 
-    fn parse_loop(&mut self) -> Result<Statement,ParseError> {
+    fn parse_loop(&mut self) -> Result<Expression,ParseError> {
+        let start = self.lookup_next().span();
         self.expect_nxt(TokenType::Loop)?;
         self.consume_next_token(); //consume the loop token
         self.expect_nxt(TokenType::SeparatorCurvedBracketOpen)?;
         let loop_block = self.parse_block_stmt()?;
-        let loop_expr = Expression::Loop(loop_block);
-        Ok(Statement::new(StatementKind::Expression(loop_expr)))
+        let span = self.span_from(start);
+        Ok(self.expression(ExpressionKind::Loop(loop_block), span))
     }
 
+    /// parses "break;" or a break with the value of the loop like "break total;"
     fn parse_break_stmt(&mut self) -> Result<Statement,ParseError> {
+        let start = self.lookup_next().span();
         self.expect_nxt_and_consume(TokenType::Break)?;
-        let break_stmt = Statement::new(StatementKind::Expression(Expression::Break));
+        let value = match self.lookup_next().kind() {
+            TokenType::SeparatorSemiColon => None,
+            _ => Some(Box::new(self.parse_expression()?)),
+        };
+        let break_expr = self.expression(ExpressionKind::Break(value), self.span_from(start));
         self.expect_nxt_and_consume(TokenType::SeparatorSemiColon)?;
-        Ok(break_stmt)
+        Ok(Statement::new(StatementKind::Expression(break_expr), self.span_from(start)))
     }
 
     fn parse_continue_stmt(&mut self) -> Result<Statement,ParseError> {
+        let start = self.lookup_next().span();
         self.expect_nxt_and_consume(TokenType::Continue)?;
+        let continue_expr = self.expression(ExpressionKind::Continue, start);
         self.expect_nxt_and_consume(TokenType::SeparatorSemiColon)?;
-        Ok(Statement::new(StatementKind::Expression(Expression::Continue)))
+        Ok(Statement::new(StatementKind::Expression(continue_expr), self.span_from(start)))
     }
 
     fn parse_while_stmt(&mut self) -> Result<Statement,ParseError> {
+        let start = self.lookup_next().span();
         self.expect_nxt_and_consume(TokenType::While)?;
         let while_condition = self.parse_expression()?;
         let while_block = self.parse_block_stmt()?;
-        let while_expr = Expression::WhileLoop(Box::from(while_condition), while_block);
-        Ok(Statement::new(StatementKind::Expression(while_expr)))
+        let span = self.span_from(start);
+        let while_expr = self.expression(ExpressionKind::WhileLoop(Box::from(while_condition), while_block), span);
+        Ok(Statement::new(StatementKind::Expression(while_expr), span))
+    }
+
+    fn parse_for_stmt(&mut self) -> Result<Statement,ParseError> {
+        // for identifier : DataType in expression .. expression [step expression] {block}
+        let start = self.lookup_next().span();
+        self.expect_nxt_and_consume(TokenType::For)?;
+        let variable = self.parse_argument()?;
+        self.expect_nxt_and_consume(TokenType::In)?;
+        let range_start = self.parse_expression()?;
+        self.expect_nxt_and_consume(TokenType::SeparatorRange)?;
+        let range_end = self.parse_expression()?;
+        let step = if self.match_next(TokenType::Step) {
+            self.consume_next_token();
+            Some(Box::new(self.parse_expression()?))
+        } else {
+            None
+        };
+        if !self.match_next(TokenType::SeparatorCurvedBracketOpen) && step.is_none() {
+            return Err(self.unexpected(vec![TokenType::Step, TokenType::SeparatorCurvedBracketOpen]));
+        }
+        let body = self.parse_block_stmt()?;
+        let span = self.span_from(start);
+        let for_expr = self.expression(ExpressionKind::ForLoop(variable, Box::new(range_start), Box::new(range_end), step, body), span);
+        Ok(Statement::new(StatementKind::Expression(for_expr), span))
     }
 
     fn parse_return_stmt(&mut self) -> Result<Statement,ParseError> {
+        let start = self.lookup_next().span();
         self.expect_nxt(TokenType::Return)?;
         self.consume_next_token();
         let expr = match self.lookup_next().kind() {
-            TokenType::SeparatorSemiColon => {
-                self.consume_next_token();
-                None
-            }
+            TokenType::SeparatorSemiColon => None,
             _ => {Some(Box::new(self.parse_expression()?))}
         };
-        let return_expr = Expression::Return(expr);
-        let return_stmt = Statement::new(StatementKind::Expression(return_expr));
+        let return_expr = self.expression(ExpressionKind::Return(expr), self.span_from(start));
         self.expect_nxt_and_consume(TokenType::SeparatorSemiColon)?;
-        Ok(return_stmt)
+        Ok(Statement::new(StatementKind::Expression(return_expr), self.span_from(start)))
     }
 
 }
+
+/// converts an integer token like 0xFF or 1_000 into its value, negated or not, and fails if it does not fit into an int
+fn integer_literal(token: Token, negative: bool) -> Result<i64,ParseError> {
+    let (radix, digits) = split_radix(token.value());
+    let magnitude = u64::from_str_radix(&digits.replace('_', ""), radix).ok().map(i128::from);
+    let value = magnitude.and_then(|n| i64::try_from(if negative { -n } else { n }).ok());
+    value.ok_or(ParseError::IntegerOutOfRange(token))
+}
+
+/// converts a float token like 1.5 or 1e-9 into its value and fails if it is infinite or rounded to zero
+fn float_literal(token: Token, negative: bool) -> Result<f64,ParseError> {
+    let literal = token.value().replace('_', "");
+    let value: f64 = literal.parse().unwrap_or(f64::INFINITY);
+    let mantissa = literal.split(['e', 'E']).next().unwrap_or_default();
+    let underflow = value == 0.0 && mantissa.chars().any(|c| ('1'..='9').contains(&c));
+    if value.is_infinite() || underflow {
+        return Err(ParseError::FloatOutOfRange(token));
+    }
+    Ok(if negative { -value } else { value })
+}
+
+/// checks if an expression which starts with the token ends with a block, like an if, a match, a loop or a block itself
+fn ends_with_block(kind: TokenType) -> bool {
+    matches!(kind, TokenType::If | TokenType::Match | TokenType::Loop | TokenType::SeparatorCurvedBracketOpen)
+}
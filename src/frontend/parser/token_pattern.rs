@@ -1,8 +1,11 @@
 use crate::frontend::syntax::token::{TokenType, TokenStream, Token};
 use crate::frontend::syntax::ast::Expression;
+use crate::frontend::syntax::span::Span;
+use crate::frontend::lexer::LexerError;
 
 /// Small parser template which matches an pattern of Tokens
 /// and parse them to a expression
+#[allow(dead_code)]
 pub trait TokenPatternParser{
     /// returns the first
     fn first_token(&self) -> TokenType;
@@ -22,6 +25,29 @@ pub trait TokenPatternParser{
 pub enum ParseError{
     /// Found, Expected
     WrongToken(Token,Vec<TokenType>),
-    /// Language Mistake with description
-    GrammarMistake(&'static str),
+    /// Language Mistake at the given position with description
+    GrammarMistake(Span,&'static str),
+    /// The token source ended without an EoF token.
+    /// Contains the span of the last received token
+    UnexpectedEndOfStream(Span),
+    /// The lexer failed, the parser stops at the position of the error
+    Lexer(LexerError),
+    /// integer literal whose value does not fit into an int
+    IntegerOutOfRange(Token),
+    /// float literal which is too large for a float or so close to zero that it would be rounded to zero
+    FloatOutOfRange(Token),
+}
+
+impl ParseError {
+
+    /// returns the position of the error in the source code
+    pub fn span(&self) -> Span {
+        match self {
+            ParseError::WrongToken(token, _) => token.span(),
+            ParseError::GrammarMistake(span, _) => *span,
+            ParseError::UnexpectedEndOfStream(span) => *span,
+            ParseError::Lexer(e) => e.span(),
+            ParseError::IntegerOutOfRange(token) | ParseError::FloatOutOfRange(token) => token.span(),
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,6 @@
+pub mod type_checker;
+pub mod resolver;
+pub mod natives;
+
+/// Name of the function where every program starts
+pub const ENTRY_POINT: &str = "main";
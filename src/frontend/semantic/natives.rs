@@ -0,0 +1,135 @@
+use crate::frontend::syntax::DataType;
+use crate::frontend::semantic::type_checker::Type;
+
+/// Function of the standard library which is implemented natively.
+/// A native is called by its name like a declared function, but a declared
+/// function or a variable with the same name hides it.
+/// Unlike a declared function a native is no value, only a lambda which calls it can be stored in a variable
+#[derive(Eq, PartialEq, Copy, Clone, Hash, Debug)]
+pub enum Native {
+    Print,
+    Println,
+    ReadLine,
+    Length,
+    Substring,
+    Concat,
+    Split,
+    Trim,
+    Abs,
+    Min,
+    Max,
+    Pow,
+    Sqrt,
+    IntToFloat,
+    FloatToInt,
+    IntToString,
+    FloatToString,
+    BooleanToString,
+    ParseInt,
+    ParseFloat,
+    ParseBoolean,
+    Assert,
+    Exit,
+}
+
+/// every function of the standard library
+const NATIVES: [Native; 23] = [
+    Native::Print, Native::Println, Native::ReadLine,
+    Native::Length, Native::Substring, Native::Concat, Native::Split, Native::Trim,
+    Native::Abs, Native::Min, Native::Max, Native::Pow, Native::Sqrt,
+    Native::IntToFloat, Native::FloatToInt, Native::IntToString, Native::FloatToString, Native::BooleanToString,
+    Native::ParseInt, Native::ParseFloat, Native::ParseBoolean,
+    Native::Assert, Native::Exit,
+];
+
+impl Native {
+
+    /// returns the function of the standard library with this name
+    pub fn lookup(name: &str) -> Option<Native> {
+        NATIVES.iter().copied().find(|native| native.name() == name)
+    }
+
+    /// name of the function like it is called in the source code
+    pub fn name(self) -> &'static str {
+        match self {
+            Native::Print => "print",
+            Native::Println => "println",
+            Native::ReadLine => "read_line",
+            Native::Length => "length",
+            Native::Substring => "substring",
+            Native::Concat => "concat",
+            Native::Split => "split",
+            Native::Trim => "trim",
+            Native::Abs => "abs",
+            Native::Min => "min",
+            Native::Max => "max",
+            Native::Pow => "pow",
+            Native::Sqrt => "sqrt",
+            Native::IntToFloat => "int_to_float",
+            Native::FloatToInt => "float_to_int",
+            Native::IntToString => "int_to_string",
+            Native::FloatToString => "float_to_string",
+            Native::BooleanToString => "boolean_to_string",
+            Native::ParseInt => "parse_int",
+            Native::ParseFloat => "parse_float",
+            Native::ParseBoolean => "parse_boolean",
+            Native::Assert => "assert",
+            Native::Exit => "exit",
+        }
+    }
+
+    /// types of the arguments and the optional return type of every overload of the function.
+    /// abs, min, max and pow take either ints or floats, every other function has a single signature
+    pub fn signatures(self) -> Vec<(Vec<DataType>, Option<DataType>)> {
+        let (args, return_type) = self.signature();
+        let mut signatures = vec![(args.clone(), return_type.clone())];
+        if let Native::Abs | Native::Min | Native::Max | Native::Pow = self {
+            // the float overload takes and returns floats instead of ints
+            signatures.push((args.iter().map(|_| DataType::Float).collect(), return_type.map(|_| DataType::Float)));
+        }
+        signatures
+    }
+
+    /// returns the overload which accepts the types of the arguments,
+    /// or the first overload if none does, so the mismatches against it are reported
+    pub fn overload(self, found: &[Type]) -> (Vec<DataType>, Option<DataType>) {
+        let mut signatures = self.signatures();
+        let position = signatures.iter().position(|(expected, _)| {
+            expected.len() == found.len() &&
+                expected.iter().zip(found).all(|(expected, found)| Type::Data(expected.clone()).accepts(found))
+        });
+        signatures.swap_remove(position.unwrap_or(0))
+    }
+
+    /// types of the arguments and the optional return type of the first overload
+    fn signature(self) -> (Vec<DataType>, Option<DataType>) {
+        use DataType::{Boolean, Float, Integer, String};
+        match self {
+            Native::Print | Native::Println => (vec![String], None),
+            Native::ReadLine => (vec![], Some(String)),
+            Native::Length => (vec![String], Some(Integer)),
+            Native::Substring => (vec![String, Integer, Integer], Some(String)),
+            Native::Concat => (vec![String, String], Some(String)),
+            Native::Split => (vec![String, String], Some(DataType::Array(Box::new(String)))),
+            Native::Trim => (vec![String], Some(String)),
+            Native::Abs => (vec![Integer], Some(Integer)),
+            Native::Min | Native::Max | Native::Pow => (vec![Integer, Integer], Some(Integer)),
+            Native::Sqrt => (vec![Float], Some(Float)),
+            Native::IntToFloat => (vec![Integer], Some(Float)),
+            Native::FloatToInt => (vec![Float], Some(Integer)),
+            Native::IntToString => (vec![Integer], Some(String)),
+            Native::FloatToString => (vec![Float], Some(String)),
+            Native::BooleanToString => (vec![Boolean], Some(String)),
+            Native::ParseInt => (vec![String], Some(Integer)),
+            Native::ParseFloat => (vec![String], Some(Float)),
+            Native::ParseBoolean => (vec![String], Some(Boolean)),
+            Native::Assert => (vec![Boolean, String], None),
+            Native::Exit => (vec![Integer], None),
+        }
+    }
+
+    /// number of arguments of the function, it is the same for every overload
+    pub fn arity(self) -> usize {
+        self.signatures()[0].0.len()
+    }
+}
@@ -0,0 +1,744 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::frontend::syntax::ast::*;
+use crate::frontend::syntax::span::Span;
+use crate::frontend::syntax::DataType;
+use crate::frontend::semantic::natives::Native;
+
+/// Resolves every name in the AbstractSyntaxTree to its declaration.
+/// Returns the Resolution of every name which could be resolved and
+/// every error and warning found in the program.
+pub fn resolve(ast: &AbstractSyntaxTree) -> (Resolution, Vec<ResolveError>) {
+    let mut resolver = Resolver::new();
+    let errors = resolver.resolve_program(ast);
+    (resolver.into_resolution(), errors)
+}
+
+/// Side table which maps the nodes of an AbstractSyntaxTree to the symbols
+/// they declare or refer to
+#[derive(Clone, Debug, Default)]
+pub struct Resolution {
+    /// symbol of every VariableBinding, FnDecl, Lambda, Symbol, Assignment and FnCall
+    nodes: HashMap<NodeId, SymbolId>,
+    /// every declared symbol, the SymbolId is the index in this list
+    symbols: Vec<Symbol>,
+    /// every top level function by its name, the functions of impl blocks by their full name like "Point::new"
+    functions: HashMap<String, SymbolId>,
+    /// variables captured by every lambda by the symbol of the lambda
+    captures: HashMap<SymbolId, Vec<Capture>>,
+    /// number of global variables and constants
+    globals: usize,
+    /// number of slots of the call frame which initializes the global variables
+    initializer_slots: usize,
+}
+
+impl Resolution {
+
+    /// returns the symbol declared or referenced by the node
+    pub fn symbol(&self, node: NodeId) -> Option<SymbolId> {
+        self.nodes.get(&node).copied()
+    }
+
+    /// returns the declaration of the symbol
+    pub fn definition(&self, id: SymbolId) -> &Symbol {
+        &self.symbols[id.as_u32() as usize]
+    }
+
+    /// returns the slot in the call frame of the variable declared or referenced by the node
+    pub fn slot(&self, node: NodeId) -> Option<usize> {
+        self.variable_slot(self.symbol(node)?)
+    }
+
+    /// returns the slot in the call frame of the variable
+    pub fn variable_slot(&self, id: SymbolId) -> Option<usize> {
+        match self.definition(id).kind {
+            SymbolKind::Variable(slot) => Some(slot),
+            SymbolKind::Global(_) | SymbolKind::Function(_) => None,
+        }
+    }
+
+    /// returns the variables captured by the lambda in the order their values are stored in the lambda
+    pub fn captures(&self, lambda: SymbolId) -> &[Capture] {
+        self.captures.get(&lambda).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// returns the index of the global variable declared or referenced by the node
+    pub fn global(&self, node: NodeId) -> Option<usize> {
+        match self.definition(self.symbol(node)?).kind {
+            SymbolKind::Global(index) => Some(index),
+            SymbolKind::Variable(_) | SymbolKind::Function(_) => None,
+        }
+    }
+
+    /// returns the number of global variables and constants,
+    /// their indices are the numbers below it in the order of their declarations
+    pub fn global_count(&self) -> usize {
+        self.globals
+    }
+
+    /// returns the number of slots needed by the variables declared in the values of the global variables,
+    /// every global is initialized in the same call frame
+    pub fn initializer_slots(&self) -> usize {
+        self.initializer_slots
+    }
+
+    /// returns the top level function with this name
+    pub fn function(&self, name: &str) -> Option<SymbolId> {
+        self.functions.get(name).copied()
+    }
+
+    /// returns the function with this name in the impl block of the type.
+    /// Methods are resolved by the type of their receiver, which is only known to
+    /// the TypeChecker or at runtime
+    pub fn method(&self, type_name: &str, name: &str) -> Option<SymbolId> {
+        self.function(&associated_name(type_name, name))
+    }
+
+    fn declare(&mut self, node: NodeId, name: &str, kind: SymbolKind, constant: bool,
+               data_type: Option<DataType>, span: Span) -> SymbolId {
+        let id = self.add(Symbol { name: name.into(), kind, constant, data_type, span });
+        self.nodes.insert(node, id);
+        id
+    }
+
+    /// adds a symbol which is not declared by a node of the AST
+    fn add(&mut self, symbol: Symbol) -> SymbolId {
+        let id = SymbolId::from(self.symbols.len() as u32);
+        self.symbols.push(symbol);
+        id
+    }
+
+    fn set_kind(&mut self, id: SymbolId, kind: SymbolKind) {
+        self.symbols[id.as_u32() as usize].kind = kind;
+    }
+}
+
+/// Declaration of a variable, a constant, a function argument or a function
+#[derive(Eq, PartialEq, Clone, Hash, Debug)]
+pub struct Symbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    /// true if the symbol was declared with const and can not be assigned
+    pub constant: bool,
+    /// declared type of a variable, None for functions and the bindings of match patterns
+    pub data_type: Option<DataType>,
+    /// span of the declaration
+    pub span: Span,
+}
+
+#[derive(Eq, PartialEq, Copy, Clone, Hash, Debug)]
+pub enum SymbolKind {
+    /// local variable or function argument, stored in this slot of the call frame
+    Variable(usize),
+    /// variable declared outside of every function, stored at this index of the global variables
+    Global(usize),
+    /// function or lambda and the number of slots its call frame needs
+    Function(usize),
+}
+
+/// Variable used by a lambda which is declared around the lambda.
+/// Its value is copied into the lambda when the lambda is created
+#[derive(Eq, PartialEq, Copy, Clone, Hash, Debug)]
+pub struct Capture {
+    /// variable in the call frame which creates the lambda
+    pub outer: SymbolId,
+    /// copy of the variable in the call frame of the lambda
+    pub inner: SymbolId,
+}
+
+/// Error or warning found by the Resolver and the range of the source code which caused it
+#[derive(Eq, PartialEq, Clone, Hash, Debug)]
+pub struct ResolveError {
+    pub kind: ResolveErrorKind,
+    pub span: Span,
+}
+
+#[derive(Eq, PartialEq, Clone, Hash, Debug)]
+pub enum ResolveErrorKind {
+    UndefinedVariable(String),
+    UndefinedFunction(String),
+    /// function name, span of the first declaration
+    DuplicateFunction(String, Span),
+    /// variable name, span of the shadowed declaration
+    ShadowedVariable(String, Span),
+    /// argument name, span of the first argument with this name in the same function
+    DuplicateParameter(String, Span),
+    /// global variable name, span of the first declaration
+    DuplicateGlobal(String, Span),
+    /// constant name, span of the declaration of the constant
+    AssignmentToConstant(String, Span),
+    /// a lambda assigns a variable it captured, variable name, span of the declaration of the variable
+    AssignmentToCapture(String, Span),
+    /// the value of a constant contains a variable, a call or another expression
+    /// which can not be evaluated before the program runs
+    NonConstantValue,
+}
+
+/// Variables declared in a single block
+#[derive(Clone)]
+struct Scope {
+    names: HashMap<String, SymbolId>,
+    /// first slot used by this scope, the slots are free again after the block ends
+    first_slot: usize,
+}
+
+/// Variables of a function or lambda while a lambda inside of it is resolved
+#[derive(Clone)]
+struct Enclosing {
+    scopes: Vec<Scope>,
+    used_slots: usize,
+    max_slots: usize,
+    captures: Vec<Capture>,
+}
+
+/// Assigns an unique SymbolId to every declaration and resolves every
+/// name to the declaration it refers to.
+/// Every variable gets a slot in the call frame of its function, slots of
+/// variables in finished blocks are reused by the following blocks.
+/// Variables declared outside of functions are global and visible in every function.
+/// A lambda has its own call frame, the variables around it which it uses get a slot in it
+/// and are captured when the lambda is created.
+#[derive(Clone)]
+pub struct Resolver {
+    resolution: Resolution,
+    /// every global variable and constant by its name
+    globals: HashMap<String, SymbolId>,
+    /// full name of every variant of the declared enums like "Shape::Circle"
+    variants: HashSet<String>,
+    /// scopes of the function which is resolved at the moment, the innermost scope is the last one
+    scopes: Vec<Scope>,
+    /// number of slots used at the moment
+    used_slots: usize,
+    /// maximum number of slots used by the current function
+    max_slots: usize,
+    /// variables captured by the lambda which is resolved at the moment
+    captures: Vec<Capture>,
+    /// functions and lambdas around the lambda which is resolved at the moment, the innermost one is the last one
+    enclosing: Vec<Enclosing>,
+    errors: Vec<ResolveError>,
+}
+
+impl Resolver {
+
+    pub fn new() -> Self {
+        Resolver {
+            resolution: Resolution::default(),
+            globals: HashMap::new(),
+            variants: HashSet::new(),
+            scopes: Vec::new(),
+            used_slots: 0,
+            max_slots: 0,
+            captures: Vec::new(),
+            enclosing: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// resolves the whole program. Functions are declared before any body is resolved,
+    /// so a function can be called before its declaration. The functions of an impl block
+    /// are declared with the name of their type in front, like "Point::new", the variants of the
+    /// enums are declared the same way and can be used everywhere.
+    /// Global variables are initialized in the order of their declarations, so the value of a
+    /// global can only refer to the globals above it. Every function can use every global.
+    pub fn resolve_program(&mut self, ast: &AbstractSyntaxTree) -> Vec<ResolveError> {
+        let functions: Vec<(String, &Expression)> = ast.nodes.iter()
+            .flat_map(|stmt| match &stmt.kind {
+                StatementKind::Expression(expr @ Expression { kind: ExpressionKind::FnDecl(name, ..), .. }) => {
+                    vec![(name.clone(), expr)]
+                },
+                StatementKind::Impl(type_name, functions) => functions.iter()
+                    .filter_map(|decl| match &decl.kind {
+                        ExpressionKind::FnDecl(name, ..) => Some((associated_name(type_name, name), decl)),
+                        _ => None,
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            })
+            .collect();
+
+        for (name, decl) in &functions {
+            self.declare_function(name, decl);
+        }
+        for stmt in &ast.nodes {
+            if let StatementKind::Enum(name, variants) = &stmt.kind {
+                self.declare_variants(name, variants);
+            }
+        }
+        for stmt in &ast.nodes {
+            match &stmt.kind {
+                StatementKind::Declaration(binding, expr) => {
+                    self.resolve_expression(expr);
+                    self.define_global(binding, false);
+                },
+                StatementKind::Constant(binding, expr) => {
+                    self.resolve_constant_value(expr);
+                    self.define_global(binding, true);
+                },
+                // the names of types are checked by the TypeChecker
+                StatementKind::Struct(..) | StatementKind::Enum(..) | StatementKind::Impl(..) |
+                StatementKind::Expression(_) => {},
+            }
+        }
+        self.resolution.initializer_slots = self.max_slots;
+        for (_, decl) in &functions {
+            self.resolve_function(decl);
+        }
+        std::mem::take(&mut self.errors)
+    }
+
+    /// resolves a single statement of an interactive session.
+    /// Variables and constants are declared as globals like in a program, so they are visible for the
+    /// following statements and functions. Other statements are resolved like the body of a function
+    /// which never ends. A declaration replaces an earlier function or global with the same name,
+    /// names which were resolved before still refer to the earlier declaration.
+    pub fn resolve_input(&mut self, stmt: &Statement) -> Vec<ResolveError> {
+        match &stmt.kind {
+            StatementKind::Expression(decl @ Expression { kind: ExpressionKind::FnDecl(name, ..), .. }) => {
+                self.redeclare_function(name.clone(), decl);
+                self.resolve_function(decl);
+            },
+            StatementKind::Impl(type_name, functions) => {
+                for decl in functions {
+                    if let ExpressionKind::FnDecl(name, ..) = &decl.kind {
+                        self.redeclare_function(associated_name(type_name, name), decl);
+                    }
+                }
+                for decl in functions {
+                    self.resolve_function(decl);
+                }
+            },
+            _ => {
+                if self.scopes.is_empty() {
+                    self.begin_scope();
+                }
+                match &stmt.kind {
+                    StatementKind::Declaration(binding, expr) => {
+                        self.resolve_expression(expr);
+                        self.redefine_global(binding, false);
+                    },
+                    StatementKind::Constant(binding, expr) => {
+                        self.resolve_constant_value(expr);
+                        self.redefine_global(binding, true);
+                    },
+                    _ => self.resolve_statement(stmt),
+                }
+            },
+        }
+        std::mem::take(&mut self.errors)
+    }
+
+    /// returns the side table of every resolved program or input
+    pub fn resolution(&self) -> &Resolution {
+        &self.resolution
+    }
+
+    /// returns the number of slots needed by the call frame of an interactive session
+    pub fn session_slots(&self) -> usize {
+        self.max_slots
+    }
+
+    /// returns the side table of every resolved program
+    pub fn into_resolution(self) -> Resolution {
+        self.resolution
+    }
+
+    fn declare_function(&mut self, name: &str, decl: &Expression) {
+        let id = self.resolution.declare(decl.id, name, SymbolKind::Function(0), false, None, decl.span);
+        match self.resolution.function(name) {
+            Some(first) => {
+                let first = self.resolution.definition(first).span;
+                self.error(ResolveErrorKind::DuplicateFunction(name.into(), first), decl.span);
+            },
+            None => {
+                self.resolution.functions.insert(name.into(), id);
+            },
+        }
+    }
+
+    /// declares a function of an interactive session, it replaces an earlier function with the same name
+    fn redeclare_function(&mut self, name: String, decl: &Expression) {
+        let id = self.resolution.declare(decl.id, &name, SymbolKind::Function(0), false, None, decl.span);
+        self.resolution.functions.insert(name, id);
+    }
+
+    fn declare_variants(&mut self, enum_name: &str, variants: &[Variant]) {
+        for variant in variants {
+            self.variants.insert(associated_name(enum_name, &variant.name));
+        }
+    }
+
+    fn resolve_function(&mut self, decl: &Expression) {
+        if let ExpressionKind::FnDecl(_, body, args, _) = &decl.kind {
+            // the function has its own call frame, so it can not see
+            // the variables of an interactive session
+            let outer_scopes = std::mem::take(&mut self.scopes);
+            let outer_slots = (self.used_slots, self.max_slots);
+            self.used_slots = 0;
+            self.max_slots = 0;
+            self.begin_scope();
+            self.define_arguments(args.iter().flatten());
+            self.resolve_block(body);
+            self.end_scope();
+
+            if let Some(id) = self.resolution.symbol(decl.id) {
+                self.resolution.set_kind(id, SymbolKind::Function(self.max_slots));
+            }
+            self.scopes = outer_scopes;
+            self.used_slots = outer_slots.0;
+            self.max_slots = outer_slots.1;
+        }
+    }
+
+    /// resolves a lambda in its own call frame. The variables around the lambda which it uses
+    /// are captured, they get the slots behind the variables of the lambda
+    fn resolve_lambda(&mut self, expr: &Expression, args: &[VariableBinding], body: &Block) {
+        let id = self.resolution.declare(expr.id, LAMBDA, SymbolKind::Function(0), false, None, expr.span);
+        self.enclosing.push(Enclosing {
+            scopes: std::mem::take(&mut self.scopes),
+            used_slots: std::mem::take(&mut self.used_slots),
+            max_slots: std::mem::take(&mut self.max_slots),
+            captures: std::mem::take(&mut self.captures),
+        });
+        self.begin_scope();
+        self.define_arguments(args);
+        self.resolve_block(body);
+        self.end_scope();
+
+        let captures = std::mem::take(&mut self.captures);
+        for (i, capture) in captures.iter().enumerate() {
+            self.resolution.set_kind(capture.inner, SymbolKind::Variable(self.max_slots + i));
+        }
+        self.resolution.set_kind(id, SymbolKind::Function(self.max_slots + captures.len()));
+        self.resolution.captures.insert(id, captures);
+
+        let outer = self.enclosing.pop().expect("the surroundings of the lambda were pushed before");
+        self.scopes = outer.scopes;
+        self.used_slots = outer.used_slots;
+        self.max_slots = outer.max_slots;
+        self.captures = outer.captures;
+    }
+
+    fn resolve_block(&mut self, block: &Block) {
+        self.begin_scope();
+        for stmt in &block.statements {
+            self.resolve_statement(stmt);
+        }
+        if let Some(value) = &block.value {
+            self.resolve_expression(value);
+        }
+        self.end_scope();
+    }
+
+    fn resolve_statement(&mut self, stmt: &Statement) {
+        match &stmt.kind {
+            StatementKind::Declaration(binding, expr) => {
+                // the initializer can not refer to the declared variable
+                self.resolve_expression(expr);
+                self.define(binding, false);
+            },
+            StatementKind::Constant(binding, expr) => {
+                self.resolve_constant_value(expr);
+                self.define(binding, true);
+            },
+            StatementKind::Enum(name, variants) => self.declare_variants(name, variants),
+            StatementKind::Struct(..) | StatementKind::Impl(..) => {},
+            StatementKind::Expression(expr) => self.resolve_expression(expr),
+        }
+    }
+
+    fn resolve_expression(&mut self, expr: &Expression) {
+        match &expr.kind {
+            ExpressionKind::FnCall(name, args) => {
+                for arg in args {
+                    self.resolve_expression(arg);
+                }
+                // a variable which holds a function hides a function with the same name,
+                // the variants of enums are created like a function call and
+                // the functions of the standard library have no symbol
+                match self.lookup(name).or_else(|| self.resolution.function(name)) {
+                    Some(id) => { self.resolution.nodes.insert(expr.id, id); },
+                    None if self.variants.contains(name) || Native::lookup(name).is_some() => {},
+                    None => self.error(ResolveErrorKind::UndefinedFunction(name.clone()), expr.span),
+                }
+            },
+            // functions can only be declared at the top level
+            ExpressionKind::FnDecl(..) => {},
+            ExpressionKind::Lambda(args, _, body) => self.resolve_lambda(expr, args, body),
+            ExpressionKind::Call(callee, args) => {
+                self.resolve_expression(callee);
+                for arg in args {
+                    self.resolve_expression(arg);
+                }
+            },
+            ExpressionKind::UnaryOp(_, operand) => self.resolve_expression(operand),
+            ExpressionKind::BinaryOp(left, _, right) => {
+                self.resolve_expression(left);
+                self.resolve_expression(right);
+            },
+            ExpressionKind::If(condition, if_block, else_block) => {
+                self.resolve_expression(condition);
+                self.resolve_block(if_block);
+                if let Some(else_block) = else_block {
+                    self.resolve_block(else_block);
+                }
+            },
+            // a variant without fields is used by its name
+            ExpressionKind::Symbol(name) if self.variants.contains(name) => {},
+            // a function is used as a value by its name
+            ExpressionKind::Symbol(name) => match self.lookup(name).or_else(|| self.resolution.function(name)) {
+                Some(id) => { self.resolution.nodes.insert(expr.id, id); },
+                None => self.error(ResolveErrorKind::UndefinedVariable(name.clone()), expr.span),
+            },
+            ExpressionKind::Assignment(name, value) => {
+                self.resolve_expression(value);
+                self.resolve_variable(expr, name);
+                if let Some(id) = self.resolution.symbol(expr.id) {
+                    let symbol = self.resolution.definition(id);
+                    let declaration = symbol.span;
+                    if symbol.constant {
+                        self.error(ResolveErrorKind::AssignmentToConstant(name.clone(), declaration), expr.span);
+                    } else if self.captures.iter().any(|capture| capture.inner == id) {
+                        self.error(ResolveErrorKind::AssignmentToCapture(name.clone(), declaration), expr.span);
+                    }
+                }
+            },
+            ExpressionKind::Return(Some(value)) | ExpressionKind::Break(Some(value)) => self.resolve_expression(value),
+            ExpressionKind::WhileLoop(condition, block) => {
+                self.resolve_expression(condition);
+                self.resolve_block(block);
+            },
+            ExpressionKind::Loop(block) => self.resolve_block(block),
+            ExpressionKind::ForLoop(variable, start, end, step, block) => {
+                self.resolve_expression(start);
+                self.resolve_expression(end);
+                if let Some(step) = step {
+                    self.resolve_expression(step);
+                }
+                // the loop variable is only visible in the body
+                self.begin_scope();
+                self.define(variable, false);
+                self.resolve_block(block);
+                self.end_scope();
+            },
+            ExpressionKind::Array(elements) => {
+                for element in elements {
+                    self.resolve_expression(element);
+                }
+            },
+            ExpressionKind::Index(array, index) => {
+                self.resolve_expression(array);
+                self.resolve_expression(index);
+            },
+            ExpressionKind::IndexAssignment(array, index, value) => {
+                self.resolve_expression(array);
+                self.resolve_expression(index);
+                self.resolve_expression(value);
+            },
+            ExpressionKind::MethodCall(receiver, _, args) => {
+                self.resolve_expression(receiver);
+                for arg in args {
+                    self.resolve_expression(arg);
+                }
+            },
+            ExpressionKind::StructLiteral(_, fields) => {
+                for (_, value) in fields {
+                    self.resolve_expression(value);
+                }
+            },
+            ExpressionKind::Field(object, _) => self.resolve_expression(object),
+            ExpressionKind::FieldAssignment(object, _, value) => {
+                self.resolve_expression(object);
+                self.resolve_expression(value);
+            },
+            ExpressionKind::Match(value, arms) => {
+                self.resolve_expression(value);
+                // the bindings of a pattern are only visible in the value of its arm
+                for arm in arms {
+                    self.begin_scope();
+                    self.define_pattern(&arm.pattern);
+                    self.resolve_expression(&arm.value);
+                    self.end_scope();
+                }
+            },
+            ExpressionKind::Interpolation(parts) => {
+                for part in parts {
+                    if let InterpolationPart::Value(value) = part {
+                        self.resolve_expression(value);
+                    }
+                }
+            },
+            ExpressionKind::Block(block) => self.resolve_block(block),
+            ExpressionKind::Literal(_) | ExpressionKind::Break(None) |
+            ExpressionKind::Continue | ExpressionKind::Return(None) => {},
+        }
+    }
+
+    /// resolves the value of a constant and reports every part of it
+    /// which is not a literal, an operator or another constant
+    fn resolve_constant_value(&mut self, expr: &Expression) {
+        self.resolve_expression(expr);
+        self.check_constant(expr);
+    }
+
+    fn check_constant(&mut self, expr: &Expression) {
+        match &expr.kind {
+            ExpressionKind::Literal(_) => {},
+            ExpressionKind::UnaryOp(_, operand) => self.check_constant(operand),
+            ExpressionKind::BinaryOp(left, _, right) => {
+                self.check_constant(left);
+                self.check_constant(right);
+            },
+            // an undefined name was already reported
+            ExpressionKind::Symbol(_) => match self.resolution.symbol(expr.id) {
+                Some(id) if !self.resolution.definition(id).constant => {
+                    self.error(ResolveErrorKind::NonConstantValue, expr.span);
+                },
+                _ => {},
+            },
+            _ => self.error(ResolveErrorKind::NonConstantValue, expr.span),
+        }
+    }
+
+    fn resolve_variable(&mut self, expr: &Expression, name: &str) {
+        match self.lookup(name) {
+            Some(id) => { self.resolution.nodes.insert(expr.id, id); },
+            None => self.error(ResolveErrorKind::UndefinedVariable(name.into()), expr.span),
+        }
+    }
+
+    fn define(&mut self, binding: &VariableBinding, constant: bool) {
+        self.define_variable(binding.id, &binding.symbol, constant, Some(binding.data_type.clone()), binding.span);
+    }
+
+    /// declares every name bound by the pattern, their types are given by the matched value
+    fn define_pattern(&mut self, pattern: &Pattern) {
+        match &pattern.kind {
+            PatternKind::Binding(name) => self.define_variable(pattern.id, name, false, None, pattern.span),
+            PatternKind::Variant(_, _, fields) => {
+                for field in fields {
+                    self.define_pattern(field);
+                }
+            },
+            PatternKind::Wildcard | PatternKind::Literal(_) => {},
+        }
+    }
+
+    /// declares the arguments of a function in its first scope, every argument needs its own name.
+    /// A duplicate argument still gets a slot, so the arguments keep their positions
+    fn define_arguments<'b>(&mut self, args: impl IntoIterator<Item = &'b VariableBinding>) {
+        for binding in args {
+            let first = self.scopes.last().and_then(|scope| scope.names.get(&binding.symbol)).copied();
+            match first {
+                Some(first) => {
+                    let first = self.resolution.definition(first).span;
+                    self.error(ResolveErrorKind::DuplicateParameter(binding.symbol.clone(), first), binding.span);
+                    self.declare_variable(binding.id, &binding.symbol, false, Some(binding.data_type.clone()), binding.span);
+                },
+                None => self.define(binding, false),
+            }
+        }
+    }
+
+    /// declares the variable in the innermost scope and warns if it shadows another variable
+    fn define_variable(&mut self, node: NodeId, name: &str, constant: bool, data_type: Option<DataType>, span: Span) {
+        if let Some(shadowed) = self.visible(name) {
+            let shadowed = self.resolution.definition(shadowed).span;
+            self.error(ResolveErrorKind::ShadowedVariable(name.into(), shadowed), span);
+        }
+        self.declare_variable(node, name, constant, data_type, span);
+    }
+
+    /// declares the variable in the innermost scope and gives it the next free slot
+    fn declare_variable(&mut self, node: NodeId, name: &str, constant: bool, data_type: Option<DataType>, span: Span) {
+        let slot = self.used_slots;
+        self.used_slots += 1;
+        self.max_slots = self.max_slots.max(self.used_slots);
+        let id = self.resolution.declare(node, name, SymbolKind::Variable(slot), constant, data_type, span);
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.names.insert(name.into(), id);
+        }
+    }
+
+    /// declares a variable outside of every function and gives it the next global index
+    fn define_global(&mut self, binding: &VariableBinding, constant: bool) {
+        let index = self.resolution.globals;
+        self.resolution.globals += 1;
+        let id = self.resolution.declare(binding.id, &binding.symbol, SymbolKind::Global(index), constant,
+                                         Some(binding.data_type.clone()), binding.span);
+        match self.globals.get(&binding.symbol) {
+            Some(first) => {
+                let first = self.resolution.definition(*first).span;
+                self.error(ResolveErrorKind::DuplicateGlobal(binding.symbol.clone(), first), binding.span);
+            },
+            None => {
+                self.globals.insert(binding.symbol.clone(), id);
+            },
+        }
+    }
+
+    /// declares a global variable of an interactive session, it replaces an earlier global with the same name
+    fn redefine_global(&mut self, binding: &VariableBinding, constant: bool) {
+        let index = self.resolution.globals;
+        self.resolution.globals += 1;
+        let id = self.resolution.declare(binding.id, &binding.symbol, SymbolKind::Global(index), constant,
+                                         Some(binding.data_type.clone()), binding.span);
+        self.globals.insert(binding.symbol.clone(), id);
+    }
+
+    /// searches the name in the open scopes from the innermost to the outermost, then in the
+    /// functions around the current lambda and at last in the global variables.
+    /// A variable of a function around the lambda is captured by the lambda
+    fn lookup(&mut self, name: &str) -> Option<SymbolId> {
+        find_name(&self.scopes, name)
+            .or_else(|| self.capture(name))
+            .or_else(|| self.globals.get(name).copied())
+    }
+
+    /// searches the name like lookup without capturing it
+    fn visible(&self, name: &str) -> Option<SymbolId> {
+        find_name(&self.scopes, name)
+            .or_else(|| self.enclosing.iter().rev().find_map(|function| find_name(&function.scopes, name)))
+            .or_else(|| self.globals.get(name).copied())
+    }
+
+    /// captures the variable of a function around the current lambda. Every lambda between the
+    /// function and the current lambda captures it too, so it is copied from lambda to lambda
+    fn capture(&mut self, name: &str) -> Option<SymbolId> {
+        let depth = self.enclosing.iter().rposition(|function| find_name(&function.scopes, name).is_some())?;
+        let mut outer = find_name(&self.enclosing[depth].scopes, name)?;
+        for level in depth + 1..=self.enclosing.len() {
+            // the slot of the copy is known after the whole lambda was resolved
+            let symbol = self.resolution.definition(outer).clone();
+            let inner = self.resolution.add(Symbol { kind: SymbolKind::Variable(0), ..symbol });
+            let (scopes, captures) = match self.enclosing.get_mut(level) {
+                Some(function) => (&mut function.scopes, &mut function.captures),
+                None => (&mut self.scopes, &mut self.captures),
+            };
+            if let Some(scope) = scopes.first_mut() {
+                scope.names.insert(name.into(), inner);
+            }
+            captures.push(Capture { outer, inner });
+            outer = inner;
+        }
+        Some(outer)
+    }
+
+    fn begin_scope(&mut self) {
+        self.scopes.push(Scope { names: HashMap::new(), first_slot: self.used_slots });
+    }
+
+    fn end_scope(&mut self) {
+        if let Some(scope) = self.scopes.pop() {
+            self.used_slots = scope.first_slot;
+        }
+    }
+
+    fn error(&mut self, kind: ResolveErrorKind, span: Span) {
+        self.errors.push(ResolveError { kind, span });
+    }
+}
+
+/// searches the name in the scopes from the innermost to the outermost
+fn find_name(scopes: &[Scope], name: &str) -> Option<SymbolId> {
+    scopes.iter().rev().find_map(|scope| scope.names.get(name)).copied()
+}
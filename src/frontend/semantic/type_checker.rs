@@ -0,0 +1,1108 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::frontend::syntax::ast::*;
+use crate::frontend::syntax::span::Span;
+use crate::frontend::syntax::{DataType, DataValue};
+use crate::frontend::semantic::natives::Native;
+use crate::frontend::semantic::ENTRY_POINT;
+
+/// Checks the types of every expression in the AbstractSyntaxTree.
+/// Returns every TypeError found in the program.
+pub fn check(ast: &AbstractSyntaxTree) -> Result<(), Vec<TypeError>> {
+    TypeChecker::new().check_program(ast)
+}
+
+/// Type of an expression
+#[derive(Eq, PartialEq, Clone, Hash, Debug)]
+pub enum Type {
+    Data(DataType),
+    /// type of the empty array literal "[]", it matches every array type
+    EmptyArray,
+    /// expression produces no value, like the call of a function without return type
+    Void,
+    /// type of an expression which contains an error.
+    /// It matches every other type, so an error is only reported once
+    Unknown,
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Type::Data(data_type) => data_type.fmt(f),
+            Type::EmptyArray => f.write_str("[]"),
+            Type::Void => f.write_str("void"),
+            Type::Unknown => f.write_str("{unknown}"),
+        }
+    }
+}
+
+impl Type {
+
+    /// checks if a value of the found type can be used where this type is required.
+    /// Unknown matches every type and the empty array matches every array type
+    pub fn accepts(&self, found: &Type) -> bool {
+        match (self, found) {
+            (Type::Unknown, _) | (_, Type::Unknown) => true,
+            (Type::Data(DataType::Array(_)), Type::EmptyArray) |
+            (Type::EmptyArray, Type::Data(DataType::Array(_))) => true,
+            _ => self == found,
+        }
+    }
+}
+
+impl From<Option<DataType>> for Type {
+    fn from(data_type: Option<DataType>) -> Self {
+        data_type.map(Type::Data).unwrap_or(Type::Void)
+    }
+}
+
+/// Error found by the TypeChecker and the range of the source code which caused it
+#[derive(Eq, PartialEq, Clone, Hash, Debug)]
+pub struct TypeError {
+    pub kind: TypeErrorKind,
+    pub span: Span,
+}
+
+#[derive(Eq, PartialEq, Clone, Hash, Debug)]
+pub enum TypeErrorKind {
+    /// Expected, Found
+    Mismatch(DataType, Type),
+    UndefinedVariable(String),
+    UndefinedFunction(String),
+    /// function name, expected number of arguments, found number of arguments
+    WrongArgumentCount(String, usize, usize),
+    InvalidOperand(UnOp, Type),
+    InvalidOperands(BinOp, Type, Type),
+    /// function with a return type which does not return a value on every path
+    MissingReturn(String, DataType),
+    /// "return;" in a function which has to return a value of this type
+    MissingReturnValue(DataType),
+    /// function without return type returns a value
+    UnexpectedReturnValue(String),
+    BreakOutsideLoop,
+    /// "break" with a value in a while or for loop, only "loop" produces a value
+    BreakWithValue,
+    ContinueOutsideLoop,
+    /// type of the first element, type of the other element
+    ArrayElementMismatch(Type, Type),
+    /// element of an array literal produces no value
+    VoidArrayElement,
+    /// index expression on a value which is not an array
+    NotIndexable(Type),
+    /// type of the value, name of the method
+    UndefinedMethod(Type, String),
+    /// name of a type which was never declared
+    UndefinedType(String),
+    /// name of a struct or enum, span of the first declaration of a type with this name
+    DuplicateType(String, Span),
+    /// the program declares no main function, so it can not be executed
+    MissingMainFunction,
+    /// type of the value, name of the field
+    UndefinedField(Type, String),
+    /// name of the struct, name of the field which is not given in the literal
+    MissingField(String, String),
+    /// name of the enum, name of the variant which is not declared
+    UndefinedVariant(String, String),
+    /// the arms of a match do not cover every value, contains a pattern of a value which is not covered
+    NonExhaustiveMatch(String),
+    /// type of the first branch, type of the other branch which produces a different value
+    BranchMismatch(Type, Type),
+    /// call of a value which is not a function
+    NotCallable(Type),
+    /// value embedded in an interpolated string which can not be formatted
+    NotInterpolatable(Type),
+}
+
+/// Signature of a declared function
+#[derive(Eq, PartialEq, Clone, Hash, Debug)]
+struct FnSignature {
+    args: Vec<DataType>,
+    return_type: Option<DataType>,
+    /// true if the first argument is the receiver "self" of a method
+    method: bool,
+}
+
+impl FnSignature {
+
+    /// returns the type of the function used as a value
+    fn data_type(&self) -> DataType {
+        DataType::Function(self.args.clone(), self.return_type.clone().map(Box::new))
+    }
+}
+
+/// The function whose body is checked at the moment
+#[derive(Clone)]
+struct FnContext {
+    name: String,
+    return_type: Option<DataType>,
+}
+
+/// A loop whose body is checked at the moment
+#[derive(Clone)]
+struct LoopContext {
+    /// true for "loop", while and for loops can not break with a value
+    has_value: bool,
+    /// type of the value of the first break, every other break needs the same type
+    value: Option<Type>,
+}
+
+/// Infers the type of every expression and checks it against the declared types
+/// of variables, function arguments and return values.
+#[derive(Clone)]
+pub struct TypeChecker {
+    functions: HashMap<String, FnSignature>,
+    /// fields of every declared struct in the order of their declaration
+    structs: HashMap<String, Vec<(String, DataType)>>,
+    /// variants of every declared enum with the types of their fields in the order of their declaration
+    enums: HashMap<String, Vec<(String, Vec<DataType>)>>,
+    /// types of the variables and constants declared outside of functions
+    globals: HashMap<String, DataType>,
+    /// types of the variables in every open block, the innermost block is the last one
+    scopes: Vec<HashMap<String, DataType>>,
+    function: Option<FnContext>,
+    /// loops around the current expression inside of the current function, the innermost loop is the last one
+    loops: Vec<LoopContext>,
+    errors: Vec<TypeError>,
+}
+
+impl TypeChecker {
+
+    pub fn new() -> Self {
+        TypeChecker {
+            functions: HashMap::new(),
+            structs: HashMap::new(),
+            enums: HashMap::new(),
+            globals: HashMap::new(),
+            scopes: Vec::new(),
+            function: None,
+            loops: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// checks the whole program. Structs, enums and functions are declared before any body is checked,
+    /// so they can be used before their declaration. The functions of impl blocks
+    /// are declared with their full name like "Point::new".
+    /// Global variables are checked in the order of their declarations before the functions,
+    /// so every function knows the type of every global.
+    /// A program without a main function is an error, because it can not be executed
+    pub fn check_program(&mut self, ast: &AbstractSyntaxTree) -> Result<(), Vec<TypeError>> {
+        // structs and enums share their names, so a name can only be declared once by either of them
+        let mut types: HashMap<&str, Span> = HashMap::new();
+        for stmt in &ast.nodes {
+            if let StatementKind::Struct(name, _) | StatementKind::Enum(name, _) = &stmt.kind {
+                match types.get(name.as_str()) {
+                    Some(&first) => self.error(TypeErrorKind::DuplicateType(name.clone(), first), stmt.span),
+                    None => {
+                        types.insert(name, stmt.span);
+                    },
+                }
+            }
+            match &stmt.kind {
+                StatementKind::Expression(expr) => self.declare_function(expr),
+                StatementKind::Struct(name, fields) => self.declare_struct(name, fields),
+                StatementKind::Enum(name, variants) => self.declare_enum(name, variants),
+                StatementKind::Impl(type_name, functions) => self.declare_impl(type_name, functions),
+                _ => {},
+            }
+        }
+        let (functions, globals): (Vec<&Statement>, Vec<&Statement>) = ast.nodes.iter()
+            .partition(|stmt| matches!(stmt.kind, StatementKind::Expression(_) | StatementKind::Impl(..)));
+        for stmt in globals.into_iter().chain(functions) {
+            self.check_statement(stmt);
+        }
+        if !self.functions.contains_key(ENTRY_POINT) {
+            self.error(TypeErrorKind::MissingMainFunction, Span::new(0, 0, 1, 1));
+        }
+        self.finish()
+    }
+
+    /// checks a single statement of an interactive session.
+    /// The variables declared by the statement are visible for the following statements
+    /// and a function declaration replaces an earlier function with the same name.
+    pub fn check_input(&mut self, stmt: &Statement) -> Result<(), Vec<TypeError>> {
+        match &stmt.kind {
+            StatementKind::Expression(expr) => self.declare_function(expr),
+            StatementKind::Impl(type_name, functions) => self.declare_impl(type_name, functions),
+            _ => {},
+        }
+        // no scope is open between the inputs, so the variables are declared as globals
+        self.check_statement(stmt);
+        self.finish()
+    }
+
+    /// returns the type of an expression of an interactive session
+    pub fn infer_input(&mut self, expr: &Expression) -> Result<Type, Vec<TypeError>> {
+        let found = self.infer(expr);
+        self.finish().map(|_| found)
+    }
+
+    fn finish(&mut self) -> Result<(), Vec<TypeError>> {
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(std::mem::take(&mut self.errors))
+        }
+    }
+
+    /// registers the signature of a function declaration
+    fn declare_function(&mut self, expr: &Expression) {
+        if let ExpressionKind::FnDecl(name, ..) = &expr.kind {
+            self.declare_signature(name.clone(), expr);
+        }
+    }
+
+    /// registers the signatures of the functions of an impl block by their full name
+    fn declare_impl(&mut self, type_name: &str, functions: &[Expression]) {
+        for expr in functions {
+            if let ExpressionKind::FnDecl(name, ..) = &expr.kind {
+                self.declare_signature(associated_name(type_name, name), expr);
+            }
+        }
+    }
+
+    fn declare_signature(&mut self, name: String, expr: &Expression) {
+        if let ExpressionKind::FnDecl(_, _, args, return_type) = &expr.kind {
+            let method = args.iter().flatten().next().is_some_and(|binding| binding.symbol == SELF);
+            let args = args.iter().flatten().map(|binding| binding.data_type.clone()).collect();
+            let signature = FnSignature { args, return_type: return_type.clone(), method };
+            self.functions.insert(name, signature);
+        }
+    }
+
+    /// registers the fields of a struct declaration
+    fn declare_struct(&mut self, name: &str, fields: &[VariableBinding]) {
+        let fields = fields.iter().map(|field| (field.symbol.clone(), field.data_type.clone())).collect();
+        self.structs.insert(name.into(), fields);
+    }
+
+    /// registers the variants of an enum declaration
+    fn declare_enum(&mut self, name: &str, variants: &[Variant]) {
+        let variants = variants.iter().map(|variant| (variant.name.clone(), variant.fields.clone())).collect();
+        self.enums.insert(name.into(), variants);
+    }
+
+    fn check_statement(&mut self, stmt: &Statement) {
+        match &stmt.kind {
+            StatementKind::Struct(name, fields) => {
+                self.declare_struct(name, fields);
+                for field in fields {
+                    self.check_data_type(&field.data_type, field.span);
+                }
+            },
+            StatementKind::Enum(name, variants) => {
+                self.declare_enum(name, variants);
+                for variant in variants {
+                    for field in &variant.fields {
+                        self.check_data_type(field, variant.span);
+                    }
+                }
+            },
+            StatementKind::Impl(type_name, functions) => {
+                self.check_data_type(&DataType::Named(type_name.clone()), stmt.span);
+                for function in functions {
+                    self.check_function(function);
+                }
+            },
+            StatementKind::Declaration(binding, expr) | StatementKind::Constant(binding, expr) => {
+                self.check_data_type(&binding.data_type, binding.span);
+                let found = self.infer(expr);
+                self.expect(&binding.data_type, found, expr.span);
+                self.define(binding);
+            },
+            StatementKind::Expression(expr) => {
+                self.infer(expr);
+            },
+        }
+    }
+
+    /// checks every statement of the block and returns the type of its value.
+    /// A block which never completes matches every type like an Unknown
+    fn check_block(&mut self, block: &Block) -> Type {
+        self.scopes.push(HashMap::new());
+        for stmt in &block.statements {
+            self.check_statement(stmt);
+        }
+        let value = match &block.value {
+            Some(value) => self.infer(value),
+            None if diverges(block) => Type::Unknown,
+            None => Type::Void,
+        };
+        self.scopes.pop();
+        value
+    }
+
+    /// checks the body of a function or lambda. The value of the body is returned
+    /// if it does not end with a return on every path
+    fn check_body(&mut self, name: &str, body: &Block, return_type: Option<&DataType>, span: Span) {
+        let value = self.check_block(body);
+        if let Some(return_type) = return_type {
+            match &body.value {
+                _ if block_returns(body) => {},
+                Some(expr) if value != Type::Void => self.expect(return_type, value, expr.span),
+                _ => self.error(TypeErrorKind::MissingReturn(name.into(), return_type.clone()), span),
+            }
+        }
+    }
+
+    fn check_function(&mut self, decl: &Expression) {
+        if let ExpressionKind::FnDecl(name, body, args, return_type) = &decl.kind {
+            let outer_function = self.function.replace(FnContext {
+                name: name.clone(),
+                return_type: return_type.clone(),
+            });
+            let outer_loops = std::mem::take(&mut self.loops);
+            let outer_scopes = std::mem::replace(&mut self.scopes, vec![HashMap::new()]);
+            for binding in args.iter().flatten() {
+                // the type of self is checked with its impl block
+                if binding.symbol != SELF {
+                    self.check_data_type(&binding.data_type, binding.span);
+                }
+                self.define(binding);
+            }
+            if let Some(return_type) = return_type {
+                self.check_data_type(return_type, decl.span);
+            }
+
+            self.check_body(name, body, return_type.as_ref(), decl.span);
+
+            self.scopes = outer_scopes;
+            self.loops = outer_loops;
+            self.function = outer_function;
+        }
+    }
+
+    /// returns the type of the expression and checks every inner expression
+    fn infer(&mut self, expr: &Expression) -> Type {
+        match &expr.kind {
+            ExpressionKind::FnCall(name, args) => self.infer_call(name, args, expr.span),
+            ExpressionKind::FnDecl(..) => {
+                // nested functions are declared when they are reached
+                if self.function.is_some() {
+                    self.declare_function(expr);
+                }
+                self.check_function(expr);
+                Type::Void
+            },
+            ExpressionKind::Lambda(args, return_type, body) => self.infer_lambda(args, return_type.as_ref(), body, expr.span),
+            ExpressionKind::Call(callee, args) => {
+                let callee = self.infer(callee);
+                let found: Vec<Type> = args.iter().map(|arg| self.infer(arg)).collect();
+                self.infer_value_call(LAMBDA, callee, found, args, expr.span)
+            },
+            ExpressionKind::UnaryOp(op, operand) => {
+                let operand = self.infer(operand);
+                match (op, &operand) {
+                    (_, Type::Unknown) => Type::Unknown,
+                    (UnOp::Negation, Type::Data(DataType::Boolean)) |
+                    (UnOp::Minus, Type::Data(DataType::Integer)) |
+                    (UnOp::Minus, Type::Data(DataType::Float)) => operand,
+                    _ => {
+                        self.error(TypeErrorKind::InvalidOperand(*op, operand), expr.span);
+                        Type::Unknown
+                    },
+                }
+            },
+            ExpressionKind::BinaryOp(left, op, right) => {
+                let left = self.infer(left);
+                let right = self.infer(right);
+                match binary_operation_type(*op, &left, &right) {
+                    Some(result) => result,
+                    None => {
+                        self.error(TypeErrorKind::InvalidOperands(*op, left, right), expr.span);
+                        Type::Unknown
+                    },
+                }
+            },
+            ExpressionKind::If(condition, if_block, else_block) => {
+                self.check_condition(condition);
+                let found = self.check_block(if_block);
+                match else_block {
+                    Some(else_block) => {
+                        let other = self.check_block(else_block);
+                        self.join_branches(found, other, else_block.span)
+                    },
+                    None => Type::Void,
+                }
+            },
+            ExpressionKind::Symbol(name) => {
+                match self.lookup(name) {
+                    Some(data_type) => Type::Data(data_type),
+                    // a function used as a value
+                    None if self.functions.contains_key(name) => Type::Data(self.functions[name].data_type()),
+                    // a variant without fields like "Shape::Empty"
+                    None if self.is_enum_path(name) => self.infer_variant(name, Vec::new(), &[], expr.span),
+                    None => {
+                        self.error(TypeErrorKind::UndefinedVariable(name.clone()), expr.span);
+                        Type::Unknown
+                    },
+                }
+            },
+            ExpressionKind::Assignment(name, value) => {
+                let found = self.infer(value);
+                match self.lookup(name) {
+                    Some(data_type) => self.expect(&data_type, found, value.span),
+                    None => self.error(TypeErrorKind::UndefinedVariable(name.clone()), expr.span),
+                }
+                Type::Void
+            },
+            ExpressionKind::Literal(literal) => Type::Data(literal_type(literal)),
+            ExpressionKind::Break(value) => {
+                self.check_break(value.as_deref(), expr.span);
+                Type::Void
+            },
+            ExpressionKind::Continue => {
+                if self.loop_depth() == 0 {
+                    self.error(TypeErrorKind::ContinueOutsideLoop, expr.span);
+                }
+                Type::Void
+            },
+            ExpressionKind::Return(value) => {
+                self.check_return(value.as_deref(), expr.span);
+                Type::Void
+            },
+            ExpressionKind::WhileLoop(condition, block) => {
+                self.check_condition(condition);
+                self.check_loop_body(block, false);
+                Type::Void
+            },
+            ExpressionKind::Loop(block) => self.check_loop_body(block, true).unwrap_or(Type::Void),
+            ExpressionKind::ForLoop(variable, start, end, step, block) => {
+                for bound in [Some(start), Some(end), step.as_ref()].iter().flatten() {
+                    let found = self.infer(bound);
+                    self.expect(&DataType::Integer, found, bound.span);
+                }
+                self.expect(&DataType::Integer, Type::Data(variable.data_type.clone()), variable.span);
+                self.scopes.push(HashMap::new());
+                self.define(variable);
+                self.check_loop_body(block, false);
+                self.scopes.pop();
+                Type::Void
+            },
+            ExpressionKind::Array(elements) => self.infer_array(elements),
+            ExpressionKind::Index(array, index) => {
+                let array = self.infer(array);
+                self.check_index(index);
+                match array {
+                    Type::Data(DataType::Array(element)) => Type::Data(*element),
+                    Type::EmptyArray | Type::Unknown => Type::Unknown,
+                    other => {
+                        self.error(TypeErrorKind::NotIndexable(other), expr.span);
+                        Type::Unknown
+                    },
+                }
+            },
+            ExpressionKind::IndexAssignment(array, index, value) => {
+                let array = self.infer(array);
+                self.check_index(index);
+                let found = self.infer(value);
+                match array {
+                    Type::Data(DataType::Array(element)) => self.expect(&element, found, value.span),
+                    Type::EmptyArray | Type::Unknown => {},
+                    other => self.error(TypeErrorKind::NotIndexable(other), expr.span),
+                }
+                Type::Void
+            },
+            ExpressionKind::MethodCall(receiver, name, args) => {
+                let receiver = self.infer(receiver);
+                self.infer_method_call(receiver, name, args, expr.span)
+            },
+            ExpressionKind::StructLiteral(name, fields) => self.infer_struct_literal(name, fields, expr.span),
+            ExpressionKind::Field(object, field) => {
+                let object = self.infer(object);
+                self.field_type(object, field, expr.span)
+            },
+            ExpressionKind::FieldAssignment(object, field, value) => {
+                let object = self.infer(object);
+                let found = self.infer(value);
+                if let Type::Data(expected) = self.field_type(object, field, expr.span) {
+                    self.expect(&expected, found, value.span);
+                }
+                Type::Void
+            },
+            ExpressionKind::Match(value, arms) => self.infer_match(value, arms, expr.span),
+            ExpressionKind::Interpolation(parts) => {
+                for part in parts {
+                    if let InterpolationPart::Value(value) = part {
+                        self.check_interpolated(value);
+                    }
+                }
+                Type::Data(DataType::String)
+            },
+            ExpressionKind::Block(block) => self.check_block(block),
+        }
+    }
+
+    /// checks that the value embedded in an interpolated string is a string, a number or a boolean
+    fn check_interpolated(&mut self, value: &Expression) {
+        match self.infer(value) {
+            Type::Data(DataType::String) | Type::Data(DataType::Integer) |
+            Type::Data(DataType::Float) | Type::Data(DataType::Boolean) | Type::Unknown => {},
+            other => self.error(TypeErrorKind::NotInterpolatable(other), value.span),
+        }
+    }
+
+    /// checks the body of a lambda like the body of a function. The lambda sees the variables
+    /// around it, so the scopes around it stay open
+    fn infer_lambda(&mut self, args: &[VariableBinding], return_type: Option<&DataType>, body: &Block, span: Span) -> Type {
+        let outer_function = self.function.replace(FnContext {
+            name: LAMBDA.into(),
+            return_type: return_type.cloned(),
+        });
+        let outer_loops = std::mem::take(&mut self.loops);
+        self.scopes.push(HashMap::new());
+        for binding in args {
+            self.check_data_type(&binding.data_type, binding.span);
+            self.define(binding);
+        }
+        if let Some(return_type) = return_type {
+            self.check_data_type(return_type, span);
+        }
+
+        self.check_body(LAMBDA, body, return_type, span);
+
+        self.scopes.pop();
+        self.loops = outer_loops;
+        self.function = outer_function;
+        let args = args.iter().map(|binding| binding.data_type.clone()).collect();
+        Type::Data(DataType::Function(args, return_type.cloned().map(Box::new)))
+    }
+
+    /// every arm has to produce a value of the same type, which is the type of the match.
+    /// The patterns have to cover every value of the matched type
+    fn infer_match(&mut self, value: &Expression, arms: &[MatchArm], span: Span) -> Type {
+        let matched = self.infer(value);
+        let mut result: Option<Type> = None;
+        let mut valid_patterns = true;
+        for arm in arms {
+            self.scopes.push(HashMap::new());
+            let errors = self.errors.len();
+            self.check_pattern(&arm.pattern, &matched);
+            valid_patterns &= self.errors.len() == errors;
+            let found = self.infer(&arm.value);
+            self.scopes.pop();
+            result = Some(match result {
+                None => found,
+                Some(first) => self.join_branches(first, found, arm.value.span),
+            });
+        }
+
+        // the coverage is only checked for valid patterns, so an error is only reported once
+        if let (Type::Data(data_type), true) = (&matched, valid_patterns) {
+            let rows = arms.iter().map(|arm| vec![Some(&arm.pattern)]).collect();
+            if let Some(witness) = self.uncovered(rows, std::slice::from_ref(data_type)) {
+                self.error(TypeErrorKind::NonExhaustiveMatch(witness.join(", ")), span);
+            }
+        }
+        result.unwrap_or(Type::Void)
+    }
+
+    /// checks the pattern against the type of the matched value and declares its bindings in the innermost scope
+    fn check_pattern(&mut self, pattern: &Pattern, expected: &Type) {
+        match &pattern.kind {
+            PatternKind::Wildcard => {},
+            PatternKind::Binding(name) => if let (Type::Data(data_type), Some(scope)) = (expected, self.scopes.last_mut()) {
+                scope.insert(name.clone(), data_type.clone());
+            },
+            PatternKind::Literal(literal) => self.expect_pattern(expected, literal_type(literal), pattern.span),
+            PatternKind::Variant(enum_name, variant, fields) => {
+                let declared = match self.enums.get(enum_name) {
+                    Some(declared) => declared,
+                    None => return self.error(TypeErrorKind::UndefinedType(enum_name.clone()), pattern.span),
+                };
+                let field_types = match declared.iter().find(|(name, _)| name == variant) {
+                    Some((_, field_types)) => field_types.clone(),
+                    None => {
+                        let error = TypeErrorKind::UndefinedVariant(enum_name.clone(), variant.clone());
+                        return self.error(error, pattern.span);
+                    },
+                };
+                self.expect_pattern(expected, DataType::Named(enum_name.clone()), pattern.span);
+                if field_types.len() != fields.len() {
+                    let name = associated_name(enum_name, variant);
+                    self.error(TypeErrorKind::WrongArgumentCount(name, field_types.len(), fields.len()), pattern.span);
+                    return;
+                }
+                for (field, data_type) in fields.iter().zip(field_types) {
+                    self.check_pattern(field, &Type::Data(data_type));
+                }
+            },
+        }
+    }
+
+    /// reports a Mismatch if a pattern of the found type can not match a value of the expected type
+    fn expect_pattern(&mut self, expected: &Type, found: DataType, span: Span) {
+        match expected {
+            Type::Data(data_type) => self.expect(data_type, Type::Data(found), span),
+            Type::Unknown => {},
+            other => self.error(TypeErrorKind::Mismatch(found, other.clone()), span),
+        }
+    }
+
+    /// returns the patterns of values of the given types which are matched by no row of patterns
+    /// or None if every value is matched. A row contains a pattern for every type, None is a wildcard.
+    /// Enums and booleans are checked variant by variant, values of the other types can only
+    /// be covered by a wildcard or a binding
+    fn uncovered(&self, rows: Vec<Vec<Option<&Pattern>>>, types: &[DataType]) -> Option<Vec<String>> {
+        let (first, rest) = match types.split_first() {
+            Some(split) => split,
+            None => return if rows.is_empty() { Some(Vec::new()) } else { None },
+        };
+        let constructors: Vec<(String, Vec<DataType>)> = match first {
+            DataType::Boolean => vec![("true".into(), Vec::new()), ("false".into(), Vec::new())],
+            DataType::Named(name) => match self.enums.get(name) {
+                Some(variants) => variants.iter()
+                    .map(|(variant, fields)| (associated_name(name, variant), fields.clone()))
+                    .collect(),
+                None => Vec::new(),
+            },
+            _ => Vec::new(),
+        };
+
+        if constructors.is_empty() {
+            let rows = rows.into_iter()
+                .filter(|row| is_wildcard(row[0]))
+                .map(|row| row[1..].to_vec())
+                .collect();
+            let mut witness = self.uncovered(rows, rest)?;
+            witness.insert(0, "_".into());
+            return Some(witness);
+        }
+        constructors.into_iter().find_map(|(constructor, fields)| {
+            let rows = rows.iter()
+                .filter_map(|row| specialize(row, &constructor, fields.len()))
+                .collect();
+            let types: Vec<DataType> = fields.iter().chain(rest).cloned().collect();
+            let mut witness = self.uncovered(rows, &types)?;
+            let rest = witness.split_off(fields.len());
+            let value = if fields.is_empty() { constructor } else { format!("{}({})", constructor, witness.join(", ")) };
+            Some(std::iter::once(value).chain(rest).collect())
+        })
+    }
+
+    /// checks if the name is a path like "Shape::Circle" which starts with the name of a declared enum
+    fn is_enum_path(&self, name: &str) -> bool {
+        split_associated_name(name).is_some_and(|(enum_name, _)| self.enums.contains_key(enum_name))
+    }
+
+    /// checks the creation of a variant like "Shape::Circle(1.0)", which is called like a function
+    fn infer_variant(&mut self, name: &str, found: Vec<Type>, args: &[Expression], span: Span) -> Type {
+        let (enum_name, variant) = match split_associated_name(name) {
+            Some(split) => split,
+            None => return Type::Unknown,
+        };
+        let fields = match self.enums.get(enum_name).and_then(|variants| variants.iter().find(|(v, _)| v == variant)) {
+            Some((_, fields)) => fields.clone(),
+            None => {
+                self.error(TypeErrorKind::UndefinedVariant(enum_name.into(), variant.into()), span);
+                return Type::Unknown;
+            },
+        };
+        if fields.len() != args.len() {
+            self.error(TypeErrorKind::WrongArgumentCount(name.into(), fields.len(), args.len()), span);
+        } else {
+            for ((expected, found), arg) in fields.iter().zip(found).zip(args) {
+                self.expect(expected, found, arg.span);
+            }
+        }
+        Type::Data(DataType::Named(enum_name.into()))
+    }
+
+    /// every field of the struct has to be given exactly once with a value of the field's type
+    fn infer_struct_literal(&mut self, name: &str, fields: &[(String, Expression)], span: Span) -> Type {
+        let found: Vec<Type> = fields.iter().map(|(_, value)| self.infer(value)).collect();
+        let declared = match self.structs.get(name) {
+            Some(declared) => declared.clone(),
+            None => {
+                self.error(TypeErrorKind::UndefinedType(name.into()), span);
+                return Type::Unknown;
+            },
+        };
+        let struct_type = Type::Data(DataType::Named(name.into()));
+
+        for ((field, value), found) in fields.iter().zip(found) {
+            match declared.iter().find(|(declared, _)| declared == field) {
+                Some((_, expected)) => self.expect(expected, found, value.span),
+                None => self.error(TypeErrorKind::UndefinedField(struct_type.clone(), field.clone()), value.span),
+            }
+        }
+        for (field, _) in &declared {
+            if !fields.iter().any(|(given, _)| given == field) {
+                self.error(TypeErrorKind::MissingField(name.into(), field.clone()), span);
+            }
+        }
+        struct_type
+    }
+
+    /// returns the type of the field of a value of the object type
+    fn field_type(&mut self, object: Type, field: &str, span: Span) -> Type {
+        let declared = match &object {
+            Type::Data(DataType::Named(name)) => self.structs.get(name)
+                .and_then(|fields| fields.iter().find(|(name, _)| name == field))
+                .map(|(_, data_type)| data_type.clone()),
+            Type::Unknown => return Type::Unknown,
+            _ => None,
+        };
+        match declared {
+            Some(data_type) => Type::Data(data_type),
+            None => {
+                self.error(TypeErrorKind::UndefinedField(object, field.into()), span);
+                Type::Unknown
+            },
+        }
+    }
+
+    /// reports every struct or enum named in the data type which was never declared
+    fn check_data_type(&mut self, data_type: &DataType, span: Span) {
+        match data_type {
+            DataType::Array(element) => self.check_data_type(element, span),
+            DataType::Function(args, return_type) => {
+                for data_type in args.iter().chain(return_type.as_deref()) {
+                    self.check_data_type(data_type, span);
+                }
+            },
+            DataType::Named(name) if !self.structs.contains_key(name) && !self.enums.contains_key(name) => {
+                self.error(TypeErrorKind::UndefinedType(name.clone()), span);
+            },
+            _ => {},
+        }
+    }
+
+    /// every element of an array literal has to have the type of the first element
+    fn infer_array(&mut self, elements: &[Expression]) -> Type {
+        let mut element_type: Option<Type> = None;
+        for element in elements {
+            let found = self.infer(element);
+            match (&element_type, found) {
+                (_, Type::Void) => self.error(TypeErrorKind::VoidArrayElement, element.span),
+                (_, Type::Unknown) => {},
+                (None, found) | (Some(Type::Unknown), found) => element_type = Some(found),
+                (Some(first), found) => if !first.accepts(&found) {
+                    self.error(TypeErrorKind::ArrayElementMismatch(first.clone(), found), element.span);
+                },
+            }
+        }
+        match element_type {
+            Some(Type::Data(data_type)) => Type::Data(DataType::Array(Box::new(data_type))),
+            Some(Type::EmptyArray) | Some(Type::Unknown) => Type::Unknown,
+            _ if elements.is_empty() => Type::EmptyArray,
+            _ => Type::Unknown,
+        }
+    }
+
+    fn check_index(&mut self, index: &Expression) {
+        let found = self.infer(index);
+        self.expect(&DataType::Integer, found, index.span);
+    }
+
+    fn infer_method_call(&mut self, receiver: Type, name: &str, args: &[Expression], span: Span) -> Type {
+        let found: Vec<Type> = args.iter().map(|arg| self.infer(arg)).collect();
+        if let Type::Data(DataType::Named(type_name)) = &receiver {
+            return self.infer_user_method(&receiver, type_name, name, found, args, span);
+        }
+        let element = match &receiver {
+            Type::Data(DataType::Array(element)) => Type::Data((**element).clone()),
+            Type::EmptyArray | Type::Unknown => Type::Unknown,
+            _ => {
+                self.error(TypeErrorKind::UndefinedMethod(receiver, name.into()), span);
+                return Type::Unknown;
+            },
+        };
+        let (expected, result) = match array_method(name, element) {
+            Some(method) => method,
+            None => {
+                self.error(TypeErrorKind::UndefinedMethod(receiver, name.into()), span);
+                return Type::Unknown;
+            },
+        };
+
+        if expected.len() != args.len() {
+            self.error(TypeErrorKind::WrongArgumentCount(name.into(), expected.len(), args.len()), span);
+        } else {
+            for ((expected, found), arg) in expected.iter().zip(found).zip(args) {
+                if let Type::Data(expected) = expected {
+                    self.expect(expected, found, arg.span);
+                }
+            }
+        }
+        result
+    }
+
+    /// checks the call of a method declared in the impl block of the receiver type,
+    /// the receiver is passed as the first argument
+    fn infer_user_method(&mut self, receiver: &Type, type_name: &str, name: &str, found: Vec<Type>,
+                         args: &[Expression], span: Span) -> Type {
+        let signature = match self.functions.get(&associated_name(type_name, name)) {
+            Some(signature) if signature.method => signature.clone(),
+            _ => {
+                self.error(TypeErrorKind::UndefinedMethod(receiver.clone(), name.into()), span);
+                return Type::Unknown;
+            },
+        };
+        let expected = &signature.args[1..];
+        if expected.len() != args.len() {
+            self.error(TypeErrorKind::WrongArgumentCount(name.into(), expected.len(), args.len()), span);
+        } else {
+            for ((expected, found), arg) in expected.iter().zip(found).zip(args) {
+                self.expect(expected, found, arg.span);
+            }
+        }
+        Type::from(signature.return_type)
+    }
+
+    /// checks the call of a function by its name, a variable which holds a function hides a function with the same name
+    fn infer_call(&mut self, name: &str, args: &[Expression], span: Span) -> Type {
+        let found: Vec<Type> = args.iter().map(|arg| self.infer(arg)).collect();
+        if let Some(data_type) = self.lookup(name) {
+            return self.infer_value_call(name, Type::Data(data_type), found, args, span);
+        }
+        let signature = match self.functions.get(name) {
+            Some(signature) => signature.clone(),
+            None if self.is_enum_path(name) => return self.infer_variant(name, found, args, span),
+            None if Native::lookup(name).is_some() => {
+                let (expected, return_type) = Native::lookup(name).expect("the native was found before").overload(&found);
+                self.check_arguments(name, &expected, found, args, span);
+                return Type::from(return_type);
+            },
+            None => {
+                self.error(TypeErrorKind::UndefinedFunction(name.into()), span);
+                return Type::Unknown;
+            },
+        };
+        self.check_arguments(name, &signature.args, found, args, span);
+        Type::from(signature.return_type)
+    }
+
+    /// checks the call of a value, which has to be a function
+    fn infer_value_call(&mut self, name: &str, callee: Type, found: Vec<Type>, args: &[Expression], span: Span) -> Type {
+        match callee {
+            Type::Data(DataType::Function(expected, return_type)) => {
+                self.check_arguments(name, &expected, found, args, span);
+                Type::from(return_type.map(|return_type| *return_type))
+            },
+            Type::Unknown => Type::Unknown,
+            other => {
+                self.error(TypeErrorKind::NotCallable(other), span);
+                Type::Unknown
+            },
+        }
+    }
+
+    /// checks the number and the types of the arguments of a call
+    fn check_arguments(&mut self, name: &str, expected: &[DataType], found: Vec<Type>, args: &[Expression], span: Span) {
+        if expected.len() != args.len() {
+            self.error(TypeErrorKind::WrongArgumentCount(name.into(), expected.len(), args.len()), span);
+        } else {
+            for ((expected, found), arg) in expected.iter().zip(found).zip(args) {
+                self.expect(expected, found, arg.span);
+            }
+        }
+    }
+
+    fn check_return(&mut self, value: Option<&Expression>, span: Span) {
+        let found = value.map(|value| (self.infer(value), value.span));
+        let function = match &self.function {
+            Some(function) => function,
+            None => return,
+        };
+        match (function.return_type.clone(), found) {
+            (Some(expected), Some((found, span))) => self.expect(&expected, found, span),
+            (Some(expected), None) => self.error(TypeErrorKind::MissingReturnValue(expected), span),
+            (None, Some(_)) => {
+                let name = function.name.clone();
+                self.error(TypeErrorKind::UnexpectedReturnValue(name), span);
+            },
+            (None, None) => {},
+        }
+    }
+
+    fn check_condition(&mut self, condition: &Expression) {
+        let found = self.infer(condition);
+        self.expect(&DataType::Boolean, found, condition.span);
+    }
+
+    /// checks the body of a loop and returns the type of the values of its breaks
+    fn check_loop_body(&mut self, block: &Block, has_value: bool) -> Option<Type> {
+        self.loops.push(LoopContext { has_value, value: None });
+        self.check_block(block);
+        self.loops.pop().and_then(|context| context.value)
+    }
+
+    fn loop_depth(&self) -> usize {
+        self.loops.len()
+    }
+
+    /// checks that a break is inside of a loop which can take its value.
+    /// The value of the first break decides the type of the loop
+    fn check_break(&mut self, value: Option<&Expression>, span: Span) {
+        let found = value.map(|value| self.infer(value)).unwrap_or(Type::Void);
+        let mut context = match self.loops.pop() {
+            Some(context) => context,
+            None => return self.error(TypeErrorKind::BreakOutsideLoop, span),
+        };
+        if value.is_some() && !context.has_value {
+            self.error(TypeErrorKind::BreakWithValue, span);
+        } else if context.has_value {
+            context.value = Some(match context.value.take() {
+                None => found,
+                Some(first) => self.join_branches(first, found, value.map_or(span, |value| value.span)),
+            });
+        }
+        self.loops.push(context);
+    }
+
+    /// returns the type of two branches which have to produce values of the same type
+    /// and reports a BranchMismatch at the span of the other branch if they differ
+    fn join_branches(&mut self, first: Type, other: Type, span: Span) -> Type {
+        match first {
+            Type::Unknown => other,
+            _ if first.accepts(&other) => first,
+            _ => {
+                self.error(TypeErrorKind::BranchMismatch(first.clone(), other), span);
+                first
+            },
+        }
+    }
+
+    /// reports a Mismatch if the found type is not the expected type
+    fn expect(&mut self, expected: &DataType, found: Type, span: Span) {
+        if !Type::Data(expected.clone()).accepts(&found) {
+            self.error(TypeErrorKind::Mismatch(expected.clone(), found), span);
+        }
+    }
+
+    /// declares the variable in the innermost block or as global variable outside of every block
+    fn define(&mut self, binding: &VariableBinding) {
+        let scope = match self.scopes.last_mut() {
+            Some(scope) => scope,
+            None => &mut self.globals,
+        };
+        scope.insert(binding.symbol.clone(), binding.data_type.clone());
+    }
+
+    fn lookup(&self, name: &str) -> Option<DataType> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name))
+            .or_else(|| self.globals.get(name))
+            .cloned()
+    }
+
+    fn error(&mut self, kind: TypeErrorKind, span: Span) {
+        self.errors.push(TypeError { kind, span });
+    }
+}
+
+/// returns the argument types and the result type of a built-in method of an array
+/// with elements of the given type or None if arrays have no method with this name
+pub fn array_method(name: &str, element: Type) -> Option<(Vec<Type>, Type)> {
+    match name {
+        "len" => Some((vec![], Type::Data(DataType::Integer))),
+        "push" => Some((vec![element], Type::Void)),
+        "pop" => Some((vec![], element)),
+        _ => None,
+    }
+}
+
+fn literal_type(literal: &DataValue) -> DataType {
+    match literal {
+        DataValue::Integer(_) => DataType::Integer,
+        DataValue::Float(_) => DataType::Float,
+        DataValue::Boolean(_) => DataType::Boolean,
+        DataValue::String(_) => DataType::String,
+    }
+}
+
+/// returns the type of the result of the binary operation or None
+/// if the operator can not be applied on these types
+fn binary_operation_type(op: BinOp, left: &Type, right: &Type) -> Option<Type> {
+    let (left, right) = match (left, right) {
+        (Type::Unknown, _) | (_, Type::Unknown) => return Some(Type::Unknown),
+        (Type::Data(DataType::Array(_)), _) | (_, Type::Data(DataType::Array(_))) |
+        (Type::Data(DataType::Named(_)), _) | (_, Type::Data(DataType::Named(_))) |
+        (Type::Data(DataType::Function(..)), _) | (_, Type::Data(DataType::Function(..))) => return None,
+        (Type::Data(left), Type::Data(right)) if left == right => (left, right),
+        _ => return None,
+    };
+    let boolean = Some(Type::Data(DataType::Boolean));
+    match (op, left) {
+        (BinOp::Plus, _) if *left != DataType::Boolean => Some(Type::Data(left.clone())),
+        (BinOp::Minus, DataType::Integer) | (BinOp::Minus, DataType::Float) |
+        (BinOp::Multi, DataType::Integer) | (BinOp::Multi, DataType::Float) |
+        (BinOp::Divide, DataType::Integer) | (BinOp::Divide, DataType::Float) |
+        (BinOp::Modulo, DataType::Integer) | (BinOp::Modulo, DataType::Float) => Some(Type::Data(right.clone())),
+        (BinOp::Eq, _) | (BinOp::Neq, _) => boolean,
+        (BinOp::Gt, _) | (BinOp::Ge, _) | (BinOp::Lt, _) | (BinOp::Le, _) if *left != DataType::Boolean => boolean,
+        (BinOp::And, DataType::Boolean) | (BinOp::Or, DataType::Boolean) => boolean,
+        _ => None,
+    }
+}
+
+/// checks if every path through the block ends with a return statement
+/// or an endless loop
+fn block_returns(block: &Block) -> bool {
+    block.statements.iter().any(|stmt| match &stmt.kind {
+        StatementKind::Expression(expr) => expression_returns(expr),
+        StatementKind::Declaration(..) | StatementKind::Constant(..) |
+        StatementKind::Struct(..) | StatementKind::Enum(..) | StatementKind::Impl(..) => false,
+    }) || block.value.as_deref().is_some_and(expression_returns)
+}
+
+fn expression_returns(expr: &Expression) -> bool {
+    match &expr.kind {
+        ExpressionKind::Return(_) => true,
+        ExpressionKind::If(_, if_block, Some(else_block)) => block_returns(if_block) && block_returns(else_block),
+        ExpressionKind::Loop(block) => !breaks(block),
+        ExpressionKind::Match(_, arms) => arms.iter().all(|arm| expression_returns(&arm.value)),
+        ExpressionKind::Block(block) => block_returns(block),
+        _ => false,
+    }
+}
+
+/// checks if the block never completes, because it returns on every path or
+/// its statements contain a break or continue which is always executed
+pub fn diverges(block: &Block) -> bool {
+    block_returns(block) || block.statements.iter().any(|stmt| matches!(&stmt.kind,
+        StatementKind::Expression(Expression { kind: ExpressionKind::Break(_) | ExpressionKind::Continue, .. })))
+}
+
+/// checks if the block contains a break which leaves the loop around this block
+fn breaks(block: &Block) -> bool {
+    block.statements.iter().any(|stmt| match &stmt.kind {
+        StatementKind::Expression(expr) => expression_breaks(expr),
+        StatementKind::Declaration(..) | StatementKind::Constant(..) |
+        StatementKind::Struct(..) | StatementKind::Enum(..) | StatementKind::Impl(..) => false,
+    }) || block.value.as_deref().is_some_and(expression_breaks)
+}
+
+fn expression_breaks(expr: &Expression) -> bool {
+    match &expr.kind {
+        ExpressionKind::Break(_) => true,
+        ExpressionKind::If(_, if_block, else_block) => breaks(if_block) || else_block.iter().any(breaks),
+        ExpressionKind::Match(_, arms) => arms.iter().any(|arm| expression_breaks(&arm.value)),
+        ExpressionKind::Block(block) => breaks(block),
+        _ => false,
+    }
+}
+
+/// checks if the pattern matches every value, a missing pattern is a wildcard
+fn is_wildcard(pattern: Option<&Pattern>) -> bool {
+    pattern.is_none_or(|pattern| matches!(pattern.kind, PatternKind::Wildcard | PatternKind::Binding(_)))
+}
+
+/// returns the row without its first pattern and with the patterns of the fields in front,
+/// or None if the first pattern does not match values of the constructor.
+/// The constructor is the full name of a variant like "Shape::Circle" or a boolean literal
+fn specialize<'a>(row: &[Option<&'a Pattern>], constructor: &str, arity: usize) -> Option<Vec<Option<&'a Pattern>>> {
+    let fields: Vec<Option<&Pattern>> = match row[0].map(|pattern| &pattern.kind) {
+        Some(PatternKind::Variant(enum_name, variant, fields)) => {
+            if associated_name(enum_name, variant) != constructor {
+                return None;
+            }
+            fields.iter().map(Some).collect()
+        },
+        Some(PatternKind::Literal(DataValue::Boolean(b))) if b.to_string() != constructor => return None,
+        Some(PatternKind::Literal(_)) => Vec::new(),
+        _ => vec![None; arity],
+    };
+    Some(fields.into_iter().chain(row[1..].iter().copied()).collect())
+}
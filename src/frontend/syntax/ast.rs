@@ -1,39 +1,53 @@
 
 use crate::frontend::syntax::{DataType, DataValue};
+use crate::frontend::syntax::span::Span;
 
-///// Represents an Id for identify an Symbol/Variable on the Stack,
-///// like ' let x = 5;'  so we replace 'x' intern with an unique id (SymbolId)
-//#[derive(Ord, PartialOrd, Eq, PartialEq,Copy, Clone,Debug,Hash)]
-//pub struct SymbolId {
-//    id: u32
-//}
-//
-//impl SymbolId {
-//
-//    pub fn new(id: u32) -> SymbolId {
-//        SymbolId{id}
-//    }
-//
-//    /// Returns a new SymbolId which is the successor of this SymbolId
-//    pub fn successor(&self) -> SymbolId {
-//        SymbolId{id: self.id + 1}
-//    }
-//
-//    /// returns this id as u32
-//    pub fn as_u32(&self) -> u32{
-//        self.id
-//    }
-//}
-//
-//impl From<u32> for SymbolId {
-//    fn from(n: u32) -> Self {
-//        SymbolId::new(n)
-//    }
-//}
+use std::fmt;
+
+/// Represents an Id for identify an Symbol/Variable on the Stack,
+/// like ' let x = 5;'  so we replace 'x' intern with an unique id (SymbolId)
+#[derive(Ord, PartialOrd, Eq, PartialEq,Copy, Clone,Debug,Hash,Default)]
+pub struct SymbolId {
+    id: u32
+}
+
+impl SymbolId {
+
+    pub fn new(id: u32) -> SymbolId {
+        SymbolId{id}
+    }
+
+    /// returns this id as u32
+    pub fn as_u32(&self) -> u32{
+        self.id
+    }
+}
+
+impl From<u32> for SymbolId {
+    fn from(n: u32) -> Self {
+        SymbolId::new(n)
+    }
+}
+
+/// Identifies a single Expression or VariableBinding in the AST.
+/// The parser gives every node an unique id, so later passes can
+/// store information about a node in a side table
+#[derive(Ord, PartialOrd, Eq, PartialEq,Copy, Clone,Debug,Hash,Default)]
+pub struct NodeId {
+    id: u32
+}
+
+impl NodeId {
+
+    /// Returns a new NodeId which is the successor of this NodeId
+    pub fn successor(&self) -> NodeId {
+        NodeId{id: self.id + 1}
+    }
+}
 
 /// Representation of the abstract frontend.syntax tree (short AST).
 /// represents the program in memory
-#[derive(PartialOrd, PartialEq,Clone,Debug)]
+#[derive(PartialEq,Clone,Debug)]
 pub struct AbstractSyntaxTree {
     pub nodes: Vec<Statement>,
 }
@@ -44,60 +58,170 @@ impl AbstractSyntaxTree{
     }
 }
 
-/// Represents an Statement
-#[derive(PartialOrd, PartialEq,Clone,Debug)]
+/// Represents an Statement.
+/// Two statements are equal if their kinds are equal, the span is ignored
+#[derive(Clone,Debug)]
 pub struct Statement {
     pub kind: StatementKind,
+    pub span: Span,
 }
 impl Statement{
-    pub fn new(kind: StatementKind) -> Statement{
-        Statement{kind}
+    pub fn new(kind: StatementKind, span: Span) -> Statement{
+        Statement{kind, span}
     }
 }
 
-#[derive(PartialOrd, PartialEq,Clone,Debug)]
+impl PartialEq for Statement {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind
+    }
+}
+
+#[derive(PartialEq,Clone,Debug)]
 pub enum StatementKind {
     Declaration(VariableBinding,Expression),
+    /// Declaration of a constant like "const MAX: int = 10 * 2;".
+    /// The value can only contain literals, operators and other constants
+    Constant(VariableBinding,Expression),
+    /// Declaration of a struct with its name and its fields like "struct Point { x: float, y: float }"
+    Struct(String,Vec<VariableBinding>),
+    /// Declaration of an enum with its name and its variants like "enum Shape { Circle(float), Rect(float, float) }"
+    Enum(String,Vec<Variant>),
+    /// functions of a type like "impl Point { fn length(self): float {...} }",
+    /// contains the name of the type and the FnDecl of every function
+    Impl(String,Vec<Expression>),
     Expression(Expression),
 }
 
-//TODO example
+/// name of the receiver argument of a method
+pub const SELF: &str = "self";
+
+/// name of every lambda, it is used in errors about the lambda like the name of a function
+pub const LAMBDA: &str = "<lambda>";
+
+/// returns the name of a function declared in the impl block of a type like "Point::new".
+/// Calls of these functions use this name, methods are found by it at runtime
+pub fn associated_name(type_name: &str, function: &str) -> String {
+    format!("{}::{}", type_name, function)
+}
+
+/// splits a full name like "Shape::Circle" into the name of the type and the name of its function or variant
+pub fn split_associated_name(name: &str) -> Option<(&str, &str)> {
+    name.split_once("::")
+}
+
+/// Variant of an enum declaration like "Rect(float, float)" with the types of its fields.
+/// Two variants are equal if their names and fields are equal, the span is ignored
+#[derive(Debug,Clone)]
+pub struct Variant {
+    pub name: String,
+    pub fields: Vec<DataType>,
+    pub span: Span,
+}
+
+impl PartialEq for Variant {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.fields == other.fields
+    }
+}
+
 /// Represents an Binding of a value to a symbol (name of a variable)
-#[derive(PartialEq, PartialOrd,Hash,Debug,Clone,Ord, Eq)]
+/// like "x: int" in "let x: int = 5;" or in the argument list of a function.
+/// Two bindings are equal if their type and symbol are equal, the id and span are ignored
+#[derive(Debug,Clone)]
 pub struct VariableBinding {
+    pub id: NodeId,
     pub data_type: DataType,
     pub symbol: String,
+    pub span: Span,
 }
 impl VariableBinding{
-    pub fn new(data_type: DataType,symbol: String) -> VariableBinding{
-        VariableBinding{data_type,symbol}
+    pub fn new(id: NodeId, data_type: DataType,symbol: String, span: Span) -> VariableBinding{
+        VariableBinding{id,data_type,symbol,span}
+    }
+}
+
+impl PartialEq for VariableBinding {
+    fn eq(&self, other: &Self) -> bool {
+        self.data_type == other.data_type && self.symbol == other.symbol
+    }
+}
+
+/// Represents an Expression.
+/// Two expressions are equal if their kinds are equal, the id and span are ignored
+#[derive(Clone,Debug)]
+pub struct Expression {
+    pub id: NodeId,
+    pub kind: ExpressionKind,
+    pub span: Span,
+}
+impl Expression {
+    pub fn new(id: NodeId, kind: ExpressionKind, span: Span) -> Expression {
+        Expression{id, kind, span}
+    }
+}
+
+impl PartialEq for Expression {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind
     }
 }
 
 /// Enum of all Expressions
-#[derive(PartialOrd, PartialEq,Clone,Debug)]
-pub enum Expression {
-    /// call of an std function or a user created function,
+#[derive(PartialEq,Clone,Debug)]
+pub enum ExpressionKind {
+    /// call of an std function, a user created function or a variable which holds a function,
     /// String represents the function name
     FnCall(String,Vec<Expression>),
     /// Declaration of a new Function, String = Name,Block of statements in the function Body, Option with possible arguments
     /// and an Option of an Returned DataType
     FnDecl(String,Block,Option<Vec<VariableBinding>>,Option<DataType>),
+    /// anonymous function like "fn(x: int): int { return x + n; }" with its arguments, its optional
+    /// return type and its body. It captures the values of the variables it uses from its surroundings
+    Lambda(Vec<VariableBinding>,Option<DataType>,Block),
+    /// call of the function value returned by an expression like "adders[0](1)" or "make(2)(3)",
+    /// contains the called expression and the arguments
+    Call(Box<Expression>,Vec<Expression>),
     /// Unary Operator Expression like "!isValid"
     UnaryOp(UnOp,Box<Expression>),
     /// binary operator like "*" or "!="
     BinaryOp(Box<Expression>,BinOp,Box<Expression>),
-    /// If statement with an optional else block.
+    /// If expression with an optional else block.
     /// if "expression " {block} else {block}
+    /// With an else block its value is the value of the executed block, without one it produces no value
     If(Box<Expression>,Block,Option<Block>), //Expression must be boxed because of recursion
+    /// match of a value against the patterns of its arms like "match shape { Shape::Circle(r) => r, _ => 0.0 }".
+    /// The value of the first arm whose pattern matches is the value of the match
+    Match(Box<Expression>,Vec<MatchArm>),
+    /// block of statements used as an expression like the value of a match arm, its value is the value of the block
+    Block(Block),
     /// single variable like "counter"
     Symbol(String),
     /// Assinment of a symbol/variable
     Assignment(String,Box<Expression>),
+    /// array literal like "[1, 2, 3]", every element has the same type
+    Array(Vec<Expression>),
+    /// element of an array like "numbers[i]", contains the array and the index
+    Index(Box<Expression>,Box<Expression>),
+    /// assignment of an array element like "numbers[i] = 5", contains the array, the index and the value
+    IndexAssignment(Box<Expression>,Box<Expression>,Box<Expression>),
+    /// call of a built-in method of a value like "numbers.push(4)",
+    /// contains the value, the method name and the arguments
+    MethodCall(Box<Expression>,String,Vec<Expression>),
+    /// construction of a struct like "Point { x: 1.0, y: 2.0 }", contains the name of the struct
+    /// and the value of every field in the order of the source code
+    StructLiteral(String,Vec<(String,Expression)>),
+    /// field of a struct like "p.x"
+    Field(Box<Expression>,String),
+    /// assignment of a field like "p.x = 1.0", contains the struct, the field name and the value
+    FieldAssignment(Box<Expression>,String,Box<Expression>),
     /// represents a literal like "42" or "foobar"
     Literal(DataValue),
-    /// Break of an loop
-    Break,
+    /// string with embedded expressions like "value is {x + 1}", contains the text and the
+    /// expressions in the order of the source code. Its value is the text with the value of every expression
+    Interpolation(Vec<InterpolationPart>),
+    /// Break of an loop, a "loop" can be left with a value like "break total;" which is the value of the loop
+    Break(Option<Box<Expression>>),
     /// Continue of an loop
     Continue,
     /// Return statement, can return an value or nothing
@@ -107,6 +231,59 @@ pub enum Expression {
     WhileLoop(Box<Expression>,Block),
     /// loop{block}, loops until break or return statement
     Loop(Block),
+    /// for loop over a range of integers like "for i: int in 0..10 step 2 {block}".
+    /// Contains the loop variable, the start, the exclusive end, the optional step and the body.
+    /// The range is counted down if the step is negative
+    ForLoop(VariableBinding,Box<Expression>,Box<Expression>,Option<Box<Expression>>,Block),
+}
+
+/// Arm of a match expression like "Shape::Rect(w, h) => w * h"
+#[derive(PartialEq,Clone,Debug)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub value: Expression,
+}
+
+/// Part of an interpolated string
+#[derive(PartialEq,Clone,Debug)]
+pub enum InterpolationPart {
+    Text(String),
+    /// embedded expression like "x + 1" in "value is {x + 1}"
+    Value(Expression),
+}
+
+/// Represents a pattern of a match arm.
+/// Two patterns are equal if their kinds are equal, the id and span are ignored
+#[derive(Clone,Debug)]
+pub struct Pattern {
+    pub id: NodeId,
+    pub kind: PatternKind,
+    pub span: Span,
+}
+
+impl Pattern {
+    pub fn new(id: NodeId, kind: PatternKind, span: Span) -> Pattern {
+        Pattern{id, kind, span}
+    }
+}
+
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind
+    }
+}
+
+#[derive(PartialEq,Clone,Debug)]
+pub enum PatternKind {
+    /// "_" matches every value
+    Wildcard,
+    /// literal like "1" or "-2.5", matches every equal value
+    Literal(DataValue),
+    /// name which matches every value and binds it to a new variable
+    Binding(String),
+    /// variant of an enum like "Shape::Rect(w, h)", contains the name of the enum,
+    /// the name of the variant and a pattern for every field
+    Variant(String,String,Vec<Pattern>),
 }
 
 /// Enum of binary operators
@@ -120,6 +297,8 @@ pub enum BinOp {
     Multi,
     /// division Operator "3/4"
     Divide,
+    /// remainder of a division "7 % 3"
+    Modulo,
     /// Equal Operator "a == b"
     Eq,
     /// Not Equal Operator " a != b"
@@ -136,6 +315,28 @@ pub enum BinOp {
     Or,
 }
 
+/// writes the operator like it is written in the source code
+impl fmt::Display for BinOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let symbol = match self {
+            BinOp::Plus => "+",
+            BinOp::Minus => "-",
+            BinOp::Multi => "*",
+            BinOp::Divide => "/",
+            BinOp::Modulo => "%",
+            BinOp::Eq => "==",
+            BinOp::Neq => "!=",
+            BinOp::Gt => ">",
+            BinOp::Ge => ">=",
+            BinOp::Lt => "<",
+            BinOp::Le => "<=",
+            BinOp::And => "and",
+            BinOp::Or => "or",
+        };
+        f.write_str(symbol)
+    }
+}
+
 /// Enum of unary operators
 #[derive(Ord, PartialOrd, Eq, PartialEq,Copy, Clone,Hash,Debug)]
 pub enum UnOp {
@@ -144,14 +345,41 @@ pub enum UnOp {
     Minus,
 }
 
+/// writes the operator like it is written in the source code
+impl fmt::Display for UnOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let symbol = match self {
+            UnOp::Negation => "!",
+            UnOp::Minus => "-",
+        };
+        f.write_str(symbol)
+    }
+}
+
 /// represents an block of statements like if {block} else {block}
-/// or an function call like fn doSomething(){block}
-#[derive(PartialOrd, PartialEq,Clone,Debug)]
+/// or an function call like fn doSomething(){block}.
+/// Two blocks are equal if their statements and values are equal, the span is ignored
+#[derive(Clone,Debug)]
 pub struct Block {
     pub statements: Vec<Statement>,
+    /// expression behind the last statement without a semicolon like "x * 2" in "{ let x: int = 2; x * 2 }",
+    /// it is the value of the block
+    pub value: Option<Box<Expression>>,
+    pub span: Span,
 }
 impl Block{
-    pub fn new(stmts: Vec<Statement>) -> Block{
-        Block{statements: stmts}
+    pub fn new(stmts: Vec<Statement>, span: Span) -> Block{
+        Block{statements: stmts, value: None, span}
+    }
+
+    /// creates a block which ends with the expression of its value
+    pub fn with_value(stmts: Vec<Statement>, value: Expression, span: Span) -> Block{
+        Block{statements: stmts, value: Some(Box::new(value)), span}
+    }
+}
+
+impl PartialEq for Block {
+    fn eq(&self, other: &Self) -> bool {
+        self.statements == other.statements && self.value == other.value
     }
 }
\ No newline at end of file
@@ -1,21 +1,54 @@
 pub mod ast;
 pub mod token;
+pub mod span;
+pub mod printer;
 
+use std::fmt;
 
-/// Represents basic data types
+
+/// Represents basic data types, the value of a number literal is converted and validated by the parser
 #[derive(PartialEq,PartialOrd,Clone,Debug)]
 pub enum DataValue {
-    Integer(String),
-    Float(String),
+    Integer(i64),
+    Float(f64),
     Boolean(bool),
     String(String),
 }
 
 /// Enum of all standard data types
-#[derive(Eq, PartialEq,Ord, PartialOrd,Copy, Clone,Hash,Debug)]
+#[derive(Eq, PartialEq,Ord, PartialOrd, Clone,Hash,Debug)]
 pub enum DataType{
     Float,
     Integer,
     Boolean,
     String,
+    /// array with elements of the boxed type like "[int]"
+    Array(Box<DataType>),
+    /// user defined type like a struct, referred to by its name
+    Named(String),
+    /// function value like "fn(int, int): int" with the types of its arguments and its optional return type
+    Function(Vec<DataType>, Option<Box<DataType>>),
+}
+
+/// writes the data type like it is written in the source code
+impl fmt::Display for DataType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            DataType::Float => "float",
+            DataType::Integer => "int",
+            DataType::Boolean => "boolean",
+            DataType::String => "string",
+            DataType::Array(element) => return write!(f, "[{}]", element),
+            DataType::Named(name) => name,
+            DataType::Function(args, return_type) => {
+                let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+                write!(f, "fn({})", args.join(", "))?;
+                return match return_type {
+                    Some(return_type) => write!(f, ": {}", return_type),
+                    None => Ok(()),
+                };
+            },
+        };
+        f.write_str(name)
+    }
 }
\ No newline at end of file
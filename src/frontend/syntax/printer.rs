@@ -0,0 +1,425 @@
+use crate::frontend::syntax::ast::{AbstractSyntaxTree, Block, Expression, ExpressionKind, Statement, StatementKind, BinOp, SELF};
+use crate::frontend::syntax::ast::{InterpolationPart, Pattern, PatternKind, VariableBinding};
+use crate::frontend::syntax::{DataType, DataValue};
+
+const INDENT: &str = "    ";
+
+/// prints the AbstractSyntaxTree as formatted source code.
+/// Parsing the printed source code results in the same tree
+pub fn print(ast: &AbstractSyntaxTree) -> String {
+    let mut printer = Printer { out: String::new(), depth: 0 };
+    for (i, stmt) in ast.nodes.iter().enumerate() {
+        if i > 0 {
+            printer.out.push('\n');
+        }
+        printer.statement(stmt);
+    }
+    printer.out
+}
+
+struct Printer {
+    out: String,
+    /// number of blocks around the current statement
+    depth: usize,
+}
+
+impl Printer {
+
+    fn statement(&mut self, stmt: &Statement) {
+        self.indent();
+        match &stmt.kind {
+            StatementKind::Declaration(binding, value) | StatementKind::Constant(binding, value) => {
+                let keyword = if let StatementKind::Constant(..) = stmt.kind { "const" } else { "let" };
+                self.out.push_str(&format!("{} {}: {} = ", keyword, binding.symbol, binding.data_type));
+                self.expression(value);
+                self.out.push(';');
+            },
+            // structs are only declared at the top level
+            StatementKind::Struct(name, fields) => {
+                self.out.push_str(&format!("struct {} {{\n", name));
+                for field in fields {
+                    self.out.push_str(&format!("{}{}: {},\n", INDENT, field.symbol, field.data_type));
+                }
+                self.out.push('}');
+            },
+            // enums are only declared at the top level
+            StatementKind::Enum(name, variants) => {
+                self.out.push_str(&format!("enum {} {{\n", name));
+                for variant in variants {
+                    self.out.push_str(&format!("{}{}", INDENT, variant.name));
+                    if !variant.fields.is_empty() {
+                        let fields: Vec<String> = variant.fields.iter().map(|field| field.to_string()).collect();
+                        self.out.push_str(&format!("({})", fields.join(", ")));
+                    }
+                    self.out.push_str(",\n");
+                }
+                self.out.push('}');
+            },
+            // impl blocks are only declared at the top level, their functions are separated by empty lines
+            StatementKind::Impl(name, functions) => {
+                self.out.push_str(&format!("impl {} {{\n", name));
+                self.depth += 1;
+                for (i, function) in functions.iter().enumerate() {
+                    if i > 0 {
+                        self.out.push('\n');
+                    }
+                    self.out.push_str(INDENT);
+                    self.expression(function);
+                    self.out.push('\n');
+                }
+                self.depth -= 1;
+                self.out.push('}');
+            },
+            StatementKind::Expression(expr) => {
+                self.expression(expr);
+                if !ends_with_block(expr) {
+                    self.out.push(';');
+                }
+            },
+        }
+        self.out.push('\n');
+    }
+
+    /// prints the statements of the block on their own lines followed by the value of the block
+    fn block(&mut self, block: &Block) {
+        if block.statements.is_empty() && block.value.is_none() {
+            self.out.push_str("{}");
+            return;
+        }
+        self.out.push_str("{\n");
+        self.depth += 1;
+        for (i, stmt) in block.statements.iter().enumerate() {
+            match &stmt.kind {
+                // the last expression without a semicolon would be the value of the block
+                StatementKind::Expression(expr) if i + 1 == block.statements.len() && block.value.is_none() &&
+                    is_block_value(expr) => {
+                    self.indent();
+                    self.expression(expr);
+                    self.out.push_str(";\n");
+                },
+                _ => self.statement(stmt),
+            }
+        }
+        if let Some(value) = &block.value {
+            self.indent();
+            self.expression(value);
+            self.out.push('\n');
+        }
+        self.depth -= 1;
+        self.indent();
+        self.out.push('}');
+    }
+
+    fn expression(&mut self, expr: &Expression) {
+        match &expr.kind {
+            ExpressionKind::FnCall(name, args) => {
+                self.out.push_str(name);
+                self.list(args, '(', ')');
+            },
+            ExpressionKind::FnDecl(name, body, args, return_type) => {
+                self.out.push_str(&format!("fn {}", name));
+                self.signature(args.as_deref().unwrap_or_default(), return_type.as_ref());
+                self.block(body);
+            },
+            ExpressionKind::Lambda(args, return_type, body) => {
+                self.out.push_str("fn");
+                self.signature(args, return_type.as_ref());
+                self.block(body);
+            },
+            ExpressionKind::Call(callee, args) => {
+                self.operand(callee, !is_postfix(callee));
+                self.list(args, '(', ')');
+            },
+            ExpressionKind::UnaryOp(op, operand) => {
+                self.out.push_str(&op.to_string());
+                let parenthesize = matches!(operand.kind,
+                    ExpressionKind::BinaryOp(..) | ExpressionKind::Assignment(..) |
+                    ExpressionKind::IndexAssignment(..) | ExpressionKind::FieldAssignment(..));
+                self.operand(operand, parenthesize);
+            },
+            ExpressionKind::BinaryOp(left, op, right) => {
+                // operators of the same precedence are left associative,
+                // so only the right operand needs parenthesis in this case
+                let left_parenthesis = binding_power(left).is_some_and(|p| p < precedence(*op));
+                let right_parenthesis = binding_power(right).is_some_and(|p| p <= precedence(*op));
+                self.operand(left, left_parenthesis);
+                self.out.push_str(&format!(" {} ", op));
+                self.operand(right, right_parenthesis);
+            },
+            ExpressionKind::If(condition, then_block, else_block) => {
+                self.out.push_str("if ");
+                self.expression(condition);
+                self.out.push(' ');
+                self.block(then_block);
+                if let Some(else_block) = else_block {
+                    self.out.push_str(" else ");
+                    self.block(else_block);
+                }
+            },
+            ExpressionKind::Symbol(name) => self.out.push_str(name),
+            ExpressionKind::Assignment(name, value) => {
+                self.out.push_str(&format!("{} = ", name));
+                self.expression(value);
+            },
+            ExpressionKind::Literal(value) => self.out.push_str(&literal(value)),
+            ExpressionKind::Interpolation(parts) => {
+                self.out.push('"');
+                for part in parts {
+                    match part {
+                        InterpolationPart::Text(text) => self.out.push_str(&escape(text)),
+                        InterpolationPart::Value(value) => {
+                            self.out.push('{');
+                            self.expression(value);
+                            self.out.push('}');
+                        },
+                    }
+                }
+                self.out.push('"');
+            },
+            ExpressionKind::Break(value) => {
+                self.out.push_str("break");
+                if let Some(value) = value {
+                    self.out.push(' ');
+                    self.expression(value);
+                }
+            },
+            ExpressionKind::Continue => self.out.push_str("continue"),
+            ExpressionKind::Return(value) => {
+                self.out.push_str("return");
+                if let Some(value) = value {
+                    self.out.push(' ');
+                    self.expression(value);
+                }
+            },
+            ExpressionKind::WhileLoop(condition, body) => {
+                self.out.push_str("while ");
+                self.expression(condition);
+                self.out.push(' ');
+                self.block(body);
+            },
+            ExpressionKind::Loop(body) => {
+                self.out.push_str("loop ");
+                self.block(body);
+            },
+            ExpressionKind::ForLoop(variable, start, end, step, body) => {
+                self.out.push_str(&format!("for {}: {} in ", variable.symbol, variable.data_type));
+                self.expression(start);
+                self.out.push_str("..");
+                self.expression(end);
+                if let Some(step) = step {
+                    self.out.push_str(" step ");
+                    self.expression(step);
+                }
+                self.out.push(' ');
+                self.block(body);
+            },
+            ExpressionKind::Array(elements) => self.list(elements, '[', ']'),
+            ExpressionKind::Index(array, index) => {
+                self.operand(array, !is_postfix(array));
+                self.out.push('[');
+                self.expression(index);
+                self.out.push(']');
+            },
+            ExpressionKind::IndexAssignment(array, index, value) => {
+                self.operand(array, !is_postfix(array));
+                self.out.push('[');
+                self.expression(index);
+                self.out.push_str("] = ");
+                self.expression(value);
+            },
+            ExpressionKind::MethodCall(receiver, name, args) => {
+                self.operand(receiver, !is_postfix(receiver));
+                self.out.push_str(&format!(".{}", name));
+                self.list(args, '(', ')');
+            },
+            ExpressionKind::StructLiteral(name, fields) => {
+                self.out.push_str(&format!("{} {{ ", name));
+                for (i, (field, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        self.out.push_str(", ");
+                    }
+                    self.out.push_str(&format!("{}: ", field));
+                    self.expression(value);
+                }
+                self.out.push_str(" }");
+            },
+            ExpressionKind::Field(object, field) => {
+                self.operand(object, !is_postfix(object));
+                self.out.push_str(&format!(".{}", field));
+            },
+            ExpressionKind::FieldAssignment(object, field, value) => {
+                self.operand(object, !is_postfix(object));
+                self.out.push_str(&format!(".{} = ", field));
+                self.expression(value);
+            },
+            // every arm is printed on its own line, arms with a block need no comma
+            ExpressionKind::Match(value, arms) => {
+                self.out.push_str("match ");
+                self.expression(value);
+                self.out.push_str(" {\n");
+                self.depth += 1;
+                for arm in arms {
+                    self.indent();
+                    self.pattern(&arm.pattern);
+                    self.out.push_str(" => ");
+                    self.expression(&arm.value);
+                    if !matches!(arm.value.kind, ExpressionKind::Block(_)) {
+                        self.out.push(',');
+                    }
+                    self.out.push('\n');
+                }
+                self.depth -= 1;
+                self.indent();
+                self.out.push('}');
+            },
+            ExpressionKind::Block(block) => self.block(block),
+        }
+    }
+
+    /// prints the arguments and the return type of a function followed by a space
+    fn signature(&mut self, args: &[VariableBinding], return_type: Option<&DataType>) {
+        let args: Vec<String> = args.iter()
+            .map(|arg| match arg.symbol.as_str() {
+                SELF => SELF.into(),
+                _ => format!("{}: {}", arg.symbol, arg.data_type),
+            })
+            .collect();
+        self.out.push_str(&format!("({})", args.join(", ")));
+        if let Some(return_type) = return_type {
+            self.out.push_str(&format!(": {}", return_type));
+        }
+        self.out.push(' ');
+    }
+
+    fn pattern(&mut self, pattern: &Pattern) {
+        match &pattern.kind {
+            PatternKind::Wildcard => self.out.push('_'),
+            PatternKind::Binding(name) => self.out.push_str(name),
+            PatternKind::Literal(value) => self.out.push_str(&literal(value)),
+            PatternKind::Variant(name, variant, fields) => {
+                self.out.push_str(&format!("{}::{}", name, variant));
+                if !fields.is_empty() {
+                    self.out.push('(');
+                    for (i, field) in fields.iter().enumerate() {
+                        if i > 0 {
+                            self.out.push_str(", ");
+                        }
+                        self.pattern(field);
+                    }
+                    self.out.push(')');
+                }
+            },
+        }
+    }
+
+    /// prints the expressions separated by commas between the brackets
+    fn list(&mut self, exprs: &[Expression], open: char, close: char) {
+        self.out.push(open);
+        for (i, expr) in exprs.iter().enumerate() {
+            if i > 0 {
+                self.out.push_str(", ");
+            }
+            self.expression(expr);
+        }
+        self.out.push(close);
+    }
+
+    fn indent(&mut self) {
+        for _ in 0..self.depth {
+            self.out.push_str(INDENT);
+        }
+    }
+
+    fn operand(&mut self, expr: &Expression, parenthesize: bool) {
+        if parenthesize {
+            self.out.push('(');
+            self.expression(expr);
+            self.out.push(')');
+        } else {
+            self.expression(expr);
+        }
+    }
+}
+
+/// returns true if the expression is written with a block at its end,
+/// these expression statements are not terminated by a semicolon
+fn ends_with_block(expr: &Expression) -> bool {
+    matches!(expr.kind,
+        ExpressionKind::FnDecl(..) | ExpressionKind::If(..) | ExpressionKind::WhileLoop(..) |
+        ExpressionKind::Loop(..) | ExpressionKind::ForLoop(..) | ExpressionKind::Match(..) |
+        ExpressionKind::Block(_))
+}
+
+/// returns true if the expression is parsed as the value of a block when it is the
+/// last statement of the block, because the semicolon behind it is optional
+fn is_block_value(expr: &Expression) -> bool {
+    matches!(expr.kind,
+        ExpressionKind::If(..) | ExpressionKind::Loop(..) | ExpressionKind::Match(..) | ExpressionKind::Block(_))
+}
+
+/// returns true if the expression can be written in front of an index or a method call
+/// without parenthesis
+fn is_postfix(expr: &Expression) -> bool {
+    matches!(expr.kind,
+        ExpressionKind::Symbol(_) | ExpressionKind::Literal(_) | ExpressionKind::Interpolation(_) |
+        ExpressionKind::FnCall(..) | ExpressionKind::Call(..) |
+        ExpressionKind::Array(_) | ExpressionKind::Index(..) | ExpressionKind::MethodCall(..) |
+        ExpressionKind::StructLiteral(..) | ExpressionKind::Field(..))
+}
+
+/// returns the precedence of the operator of a binary operation or an assignment,
+/// or None if the expression binds stronger than every operator
+fn binding_power(expr: &Expression) -> Option<u8> {
+    match &expr.kind {
+        ExpressionKind::BinaryOp(_, op, _) => Some(precedence(*op)),
+        ExpressionKind::Assignment(..) | ExpressionKind::IndexAssignment(..) |
+        ExpressionKind::FieldAssignment(..) => Some(0),
+        _ => None,
+    }
+}
+
+/// precedence of the binary operators like they are parsed, a higher value binds stronger
+fn precedence(op: BinOp) -> u8 {
+    match op {
+        BinOp::Or => 1,
+        BinOp::And => 2,
+        BinOp::Eq | BinOp::Neq => 3,
+        BinOp::Gt | BinOp::Ge | BinOp::Lt | BinOp::Le => 4,
+        BinOp::Plus | BinOp::Minus => 5,
+        BinOp::Multi | BinOp::Divide | BinOp::Modulo => 6,
+    }
+}
+
+/// writes the value like a literal in the source code, a float always has a fraction or an exponent
+fn literal(value: &DataValue) -> String {
+    match value {
+        DataValue::Integer(n) => n.to_string(),
+        DataValue::Float(n) => format!("{:?}", n),
+        DataValue::Boolean(b) => b.to_string(),
+        DataValue::String(s) => string_literal(s),
+    }
+}
+
+/// writes the string as literal in quotes
+fn string_literal(s: &str) -> String {
+    format!("\"{}\"", escape(s))
+}
+
+/// escapes quotes, backslashes, curly brackets and control characters of the text of a string literal
+fn escape(s: &str) -> String {
+    let mut literal = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '{' => literal.push_str("\\{"),
+            '}' => literal.push_str("\\}"),
+            '\n' => literal.push_str("\\n"),
+            '\t' => literal.push_str("\\t"),
+            '\r' => literal.push_str("\\r"),
+            c if c.is_control() => literal.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => literal.push(c),
+        }
+    }
+    literal
+}
@@ -0,0 +1,24 @@
+/// Range of bytes in the source code which belongs to a token or node of the AbstractSyntaxTree.
+/// start is inclusive, end is exclusive. line and column describe the start position
+/// and are counted from 1, the column is counted in characters and not in bytes.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Copy, Clone, Hash, Debug, Default)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Span {
+
+    pub fn new(start: usize, end: usize, line: usize, column: usize) -> Span {
+        Span { start, end, line, column }
+    }
+
+    /// returns a new Span which begins at the start of this span
+    /// and ends at the end of the other span
+    pub fn to(&self, other: Span) -> Span {
+        Span { end: other.end, ..*self }
+    }
+
+}
@@ -1,11 +1,22 @@
+use std::fmt;
 use std::sync::mpsc::Receiver;
+use crate::frontend::lexer::LexerError;
+use crate::frontend::syntax::span::Span;
 
 /// Defines the Type of a Token.
 /// Types means in this case its meaning
 #[derive(Copy, Clone,Eq, PartialEq,Ord, PartialOrd,Hash,Debug)]
 pub enum TokenType {
     Let,
+    Const,
+    Struct,
+    Enum,
+    Impl,
+    /// `self`, the receiver of a method
+    SelfValue,
     For,
+    In,
+    Step,
     Loop,
     Break,
     Continue,
@@ -15,6 +26,7 @@ pub enum TokenType {
     Assign,
     If,
     Else,
+    Match,
     Boolean,
     Integer,
     Float,
@@ -26,16 +38,27 @@ pub enum TokenType {
     Identifier,
     SeparatorCurvedBracketOpen,
     SeparatorCurvedBracketClosed,
+    SeparatorSquareBracketOpen,
+    SeparatorSquareBracketClosed,
     SeparatorBracketOpen,
     SeparatorBracketClose,
     SeparatorSemiColon,
     SeparatorColon,
     SeparatorComma,
     SeparatorDot,
+    /// `..` between the bounds of a range
+    SeparatorRange,
+    /// `::` between the name of a type and the name of its function
+    SeparatorPath,
+    /// `=>` between the pattern and the value of a match arm
+    SeparatorArrow,
+    /// `_`, the pattern which matches every value
+    Underscore,
     OperatorPlus,
     OperatorMinus,
     OperatorMultiplication,
     OperatorDivide,
+    OperatorModulo,
     OperatorEqual,
     OperatorNotEqual,
     OperatorNegation,
@@ -46,10 +69,86 @@ pub enum TokenType {
     LiteralInteger,
     LiteralFloat,
     LiteralString,
+    /// text of an interpolated string in front of its first embedded expression like `"value is {`
+    InterpolationStart,
+    /// text between two embedded expressions like `} and {`
+    InterpolationMiddle,
+    /// text behind the last embedded expression like `} items"`
+    InterpolationEnd,
     /// End of File
     EoF,
 }
 
+/// Describes the token type like it is written in the source code,
+/// keywords and punctuation are quoted, e.g. `fn` or `{`
+impl fmt::Display for TokenType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let description = match self {
+            TokenType::Let => "`let`",
+            TokenType::Const => "`const`",
+            TokenType::Struct => "`struct`",
+            TokenType::Enum => "`enum`",
+            TokenType::Impl => "`impl`",
+            TokenType::SelfValue => "`self`",
+            TokenType::For => "`for`",
+            TokenType::In => "`in`",
+            TokenType::Step => "`step`",
+            TokenType::Loop => "`loop`",
+            TokenType::Break => "`break`",
+            TokenType::Continue => "`continue`",
+            TokenType::Return => "`return`",
+            TokenType::While => "`while`",
+            TokenType::Fn => "`fn`",
+            TokenType::Assign => "`=`",
+            TokenType::If => "`if`",
+            TokenType::Else => "`else`",
+            TokenType::Match => "`match`",
+            TokenType::Boolean => "`boolean`",
+            TokenType::Integer => "`int`",
+            TokenType::Float => "`float`",
+            TokenType::String => "`string`",
+            TokenType::And => "`and`",
+            TokenType::Or => "`or`",
+            TokenType::BooleanTrue => "`true`",
+            TokenType::BooleanFalse => "`false`",
+            TokenType::Identifier => "identifier",
+            TokenType::SeparatorCurvedBracketOpen => "`{`",
+            TokenType::SeparatorCurvedBracketClosed => "`}`",
+            TokenType::SeparatorSquareBracketOpen => "`[`",
+            TokenType::SeparatorSquareBracketClosed => "`]`",
+            TokenType::SeparatorBracketOpen => "`(`",
+            TokenType::SeparatorBracketClose => "`)`",
+            TokenType::SeparatorSemiColon => "`;`",
+            TokenType::SeparatorColon => "`:`",
+            TokenType::SeparatorComma => "`,`",
+            TokenType::SeparatorDot => "`.`",
+            TokenType::SeparatorRange => "`..`",
+            TokenType::SeparatorPath => "`::`",
+            TokenType::SeparatorArrow => "`=>`",
+            TokenType::Underscore => "`_`",
+            TokenType::OperatorPlus => "`+`",
+            TokenType::OperatorMinus => "`-`",
+            TokenType::OperatorMultiplication => "`*`",
+            TokenType::OperatorDivide => "`/`",
+            TokenType::OperatorModulo => "`%`",
+            TokenType::OperatorEqual => "`==`",
+            TokenType::OperatorNotEqual => "`!=`",
+            TokenType::OperatorNegation => "`!`",
+            TokenType::OperatorLessThen => "`<`",
+            TokenType::OperatorLessOrEqual => "`<=`",
+            TokenType::OperatorGreaterThen => "`>`",
+            TokenType::OperatorGreaterOrEqual => "`>=`",
+            TokenType::LiteralInteger => "integer literal",
+            TokenType::LiteralFloat => "float literal",
+            TokenType::LiteralString => "string literal",
+            TokenType::InterpolationStart => "interpolated string",
+            TokenType::InterpolationMiddle | TokenType::InterpolationEnd => "`}` of an embedded expression",
+            TokenType::EoF => "end of file",
+        };
+        f.write_str(description)
+    }
+}
+
 /// Struct to represent an token in our language.
 /// A Token is the smallest unit of our language, its
 /// represents keywords, names of variables (Identifier) or punctuation like ';' ',' '{'
@@ -57,31 +156,42 @@ pub enum TokenType {
 pub struct Token {
     kind: TokenType,
     value: String,
-    start_position: usize,
+    span: Span,
 }
 
 #[allow(dead_code)]
 impl Token {
 
     /// creates a new Token.
-    pub fn new(kind: TokenType, value: String, start_position: usize) -> Token{
-        Token{ kind,value,start_position}
+    pub fn new(kind: TokenType, value: String, span: Span) -> Token{
+        Token{ kind,value,span}
     }
 
-    /// returns the position where this tokens begins.
+    /// returns the byte offset where this tokens begins.
     pub fn start(&self) -> usize {
-        self.start_position
+        self.span.start
     }
 
-    /// returns the position where this token ends.
+    /// returns the byte offset where this token ends.
     pub fn end(&self) -> usize{
-        self.start_position + self.value.len()
+        self.span.end
+    }
+
+    /// returns the range of the source code this token was read from
+    pub fn span(&self) -> Span {
+        self.span
     }
 
     pub fn kind(&self) -> TokenType{
         self.kind
     }
 
+    /// returns the text of this token like it was read from the source code,
+    /// without the quotes of string literals
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
     /// Consumes this tokens and returns the owned value String
     pub fn move_value(self) -> String{
         self.value
@@ -89,41 +199,25 @@ impl Token {
 }
 
 
-/// Stream of Tokens.
+/// Stream of Tokens which are produced by the lexer on another thread.
 pub struct TokenStream {
-    rx: Receiver<Token>,
-    is_closed: bool,
+    rx: Receiver<Result<Token,LexerError>>,
 }
-#[allow(dead_code)]
+
 impl TokenStream{
 
     /// creates a new empty stream with a receiver to fill this stream
-    pub fn new(rx: Receiver<Token>) -> Self{
-        TokenStream{rx,is_closed: false}
+    pub fn new(rx: Receiver<Result<Token,LexerError>>) -> Self{
+        TokenStream{rx}
     }
+}
 
-    /// read next token from this stream and blocks the calling thread till a token is received.
-    /// otherwise it will returns None if the stream closed and will never send a next token.
-    /// The last token is always an EOF-Token, except the producer fails
-    pub fn next(&mut self) -> Option<Token>{
-        match self.rx.recv() {
-            Ok(t) => Some(t),
-            Err(_) => {
-                self.is_closed = true;
-                None
-            },
-        }
-    }
+/// reads the next token and blocks the calling thread till a token is received.
+/// Returns None if the lexer finished and will never send a next token.
+impl Iterator for TokenStream {
+    type Item = Result<Token,LexerError>;
 
-    /// consumes this stream and collects every token.
-    /// this function blocks the calling thread till every token is received
-    pub fn collect(self) -> Vec<Token>{
-        self.rx.iter().collect()
+    fn next(&mut self) -> Option<Self::Item> {
+        self.rx.recv().ok()
     }
-
-    /// returns a boolean if the last token was
-    pub fn is_closed(&self) -> bool{
-        self.is_closed
-    }
-
-}
\ No newline at end of file
+}
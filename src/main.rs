@@ -1,24 +1,168 @@
 use frontend::lexer::Lexer;
 use frontend::parser::ast_parser::*;
+use frontend::parser::token_pattern::ParseError;
+use frontend::diagnostics::{Diagnostic, Severity, Style};
+use frontend::semantic::{resolver, type_checker};
+use frontend::syntax::ast::AbstractSyntaxTree;
+use frontend::syntax::printer;
+use backend::interpreter::{self, RuntimeInterpreter, RuntimeError};
+use backend::value::Value;
+use backend::{bytecode, vm};
+use cli::{Command, Input};
+
+use std::io::{self, Write};
 
 mod frontend;
 mod backend;
+mod cli;
+mod repl;
 #[cfg(test)]
 mod tests;
 
 fn main() {
-    let src = String::from("\
-        fn fibonacci(n: int): int {
-            if n == 1 or n == 2 {
-               return 1;
-            }
+    let command = match cli::parse_args(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, cli::USAGE);
+            std::process::exit(cli::EXIT_USAGE);
+        },
+    };
+    std::process::exit(execute(command));
+}
 
-            return fibonacci(n - 1) + fibonacci(n - 2);
+/// executes the command and returns the exit code
+fn execute(command: Command) -> i32 {
+    match command {
+        Command::Help => {
+            println!("{}", cli::USAGE);
+            cli::EXIT_SUCCESS
+        },
+        Command::Repl => {
+            // the inputs are executed by the tree walking interpreter, which needs a large stack
+            interpreter::with_interpreter_stack(repl::run);
+            cli::EXIT_SUCCESS
+        },
+        Command::Tokens(input) => with_source(&input, print_tokens),
+        Command::Ast(input) => with_source(&input, |name, src| {
+            let ast = parse(name, src)?;
+            let mut stdout = io::stdout().lock();
+            write!(stdout, "{}", printer::print(&ast)).and_then(|_| stdout.flush()).or_else(cli::output_failed)
+        }),
+        Command::Check(input) => with_source(&input, |name, src| {
+            let ast = parse(name, src)?;
+            check(name, src, &ast)
+        }),
+        Command::Run { input, interpret } => {
+            let mut exit_code = cli::EXIT_SUCCESS;
+            let result = with_source(&input, |name, src| {
+                let ast = parse(name, src)?;
+                check(name, src, &ast)?;
+                exit_code = run(ast, interpret)?;
+                Ok(())
+            });
+            if result == cli::EXIT_SUCCESS { exit_code } else { result }
+        },
+    }
+}
+
+/// reads the source code of the input and passes it to the action.
+/// Returns the exit code of the failed action or EXIT_SUCCESS
+fn with_source<F: FnOnce(&str, &str) -> Result<(), i32>>(input: &Input, action: F) -> i32 {
+    let src = match input.read() {
+        Ok(src) => src,
+        Err(e) => {
+            eprintln!("error: could not read {}: {}", input.name(), e);
+            return cli::EXIT_IO_ERROR;
+        },
+    };
+    match action(input.name(), &src) {
+        Ok(()) => cli::EXIT_SUCCESS,
+        Err(exit_code) => exit_code,
+    }
+}
+
+fn report(diagnostic: &Diagnostic, name: &str, src: &str) {
+    eprint!("{}", diagnostic.render(name, src, Style::for_stderr()));
+}
+
+/// prints every token with its position, stops at the first lexer error
+fn print_tokens(name: &str, src: &str) -> Result<(), i32> {
+    let mut stdout = io::stdout().lock();
+    for result in Lexer::new(src) {
+        match result {
+            Ok(token) => {
+                if let Err(e) = writeln!(stdout, "{}", cli::describe_token(&token)) {
+                    return cli::output_failed(e);
+                }
+            },
+            Err(e) => {
+                report(&Diagnostic::from(&e), name, src);
+                return Err(cli::EXIT_LEXER_ERROR);
+            },
         }
-    ");
-    println!("{}",&src);
-    let (ts,_) = Lexer::tokenize(src.clone());
-    let parser = ASTParser::new(ts);
-    let result  = parser.parse();
-    println!("Lexer Result: {:#?}", result);
+    }
+    stdout.flush().or_else(cli::output_failed)
+}
+
+/// parses the source code and reports every lexer and parser error
+fn parse(name: &str, src: &str) -> Result<AbstractSyntaxTree, i32> {
+    ASTParser::new(Lexer::new(src)).parse().map_err(|errors| {
+        for e in &errors {
+            report(&Diagnostic::from(e), name, src);
+        }
+        let lexer_failed = errors.iter().any(|e| matches!(e, ParseError::Lexer(_)));
+        if lexer_failed { cli::EXIT_LEXER_ERROR } else { cli::EXIT_PARSER_ERROR }
+    })
+}
+
+/// resolves the names and checks the types of the program, reports every error and warning
+/// of both passes in the order of their position
+fn check(name: &str, src: &str, ast: &AbstractSyntaxTree) -> Result<(), i32> {
+    let (_, problems) = resolver::resolve(ast);
+    let mut diagnostics: Vec<Diagnostic> = problems.iter().map(Diagnostic::from).collect();
+    if let Err(errors) = type_checker::check(ast) {
+        // both passes report undefined names, they are only reported once
+        let type_errors: Vec<Diagnostic> = errors.iter().map(Diagnostic::from)
+            .filter(|e| !diagnostics.iter().any(|d| d.duplicates(e)))
+            .collect();
+        diagnostics.extend(type_errors);
+    }
+    diagnostics.sort_by_key(|d| d.span().start);
+    for diagnostic in &diagnostics {
+        report(diagnostic, name, src);
+    }
+    if diagnostics.iter().any(|d| d.severity() == Severity::Error) {
+        return Err(cli::EXIT_CHECK_ERROR);
+    }
+    Ok(())
+}
+
+/// executes the program on the vm or the tree walking interpreter.
+/// An integer returned by main is the exit code, every other value is printed
+fn run(ast: AbstractSyntaxTree, interpret: bool) -> Result<i32, i32> {
+    if interpret {
+        // the tree walking interpreter is kept to compare its results with the vm, it needs a large stack
+        interpreter::with_interpreter_stack(move || finish(RuntimeInterpreter::new(ast).start()))
+    } else {
+        finish(bytecode::compile(&ast).and_then(|program| vm::run(&program)))
+    }
+}
+
+/// turns the result of the program into the exit code
+fn finish(result: Result<Value, RuntimeError>) -> Result<i32, i32> {
+    match result {
+        Ok(Value::Integer(n)) | Err(RuntimeError::Exit(n)) => Ok(cli::exit_code(n)),
+        Ok(Value::Void) => Ok(cli::EXIT_SUCCESS),
+        Ok(value) => {
+            let mut stdout = io::stdout().lock();
+            writeln!(stdout, "{}", value).and_then(|_| stdout.flush()).or_else(cli::output_failed)?;
+            Ok(cli::EXIT_SUCCESS)
+        },
+        // the reader of the output needs no more of it, so the program ends quietly
+        Err(RuntimeError::OutputClosed) => Ok(cli::EXIT_SUCCESS),
+        Err(e) => {
+            eprintln!("runtime error: {}", e);
+            Err(cli::EXIT_RUNTIME_ERROR)
+        },
+    }
 }
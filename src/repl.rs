@@ -0,0 +1,224 @@
+use std::io::Write;
+
+use crate::cli;
+use crate::frontend::diagnostics::{Diagnostic, Severity, Style};
+use crate::frontend::lexer::{Lexer, LexerError};
+use crate::frontend::parser::ast_parser::ASTParser;
+use crate::frontend::semantic::resolver::Resolver;
+use crate::frontend::semantic::type_checker::{Type, TypeChecker};
+use crate::frontend::syntax::ast::{AbstractSyntaxTree, NodeId, Statement, StatementKind};
+use crate::frontend::syntax::printer;
+use crate::frontend::syntax::token::TokenType;
+use crate::backend::interpreter::{RuntimeInterpreter, RuntimeError};
+use crate::backend::value::Value;
+
+/// name of the input in diagnostics
+const INPUT_NAME: &str = "<repl>";
+
+const HELP: &str = "\
+enter a function declaration, a statement or an expression to execute it.
+the input continues on the next line while a bracket or a string is open.
+
+commands:
+    :ast <input>      prints the parsed input as formatted source code
+    :tokens <input>   prints every token of the input
+    :type <expr>      prints the type of the expression without executing it
+    :reset            forgets every declared function and variable
+    :help             prints this message
+    :quit             ends the session";
+
+/// Reason why an input of a Session was not executed completely
+#[derive(Debug)]
+pub enum InputError {
+    /// the input contains a lexer, parser, name or type error
+    Invalid(Vec<Diagnostic>),
+    /// the execution of the input failed
+    Runtime(RuntimeError),
+}
+
+/// Interactive session which keeps every declared function and variable
+/// for the following inputs
+pub struct Session {
+    resolver: Resolver,
+    checker: TypeChecker,
+    interpreter: RuntimeInterpreter,
+    /// first NodeId of the next input, so the nodes of every input have unique ids
+    next_id: NodeId,
+}
+
+impl Session {
+
+    pub fn new() -> Self {
+        Session {
+            resolver: Resolver::new(),
+            checker: TypeChecker::new(),
+            interpreter: RuntimeInterpreter::session(),
+            next_id: NodeId::default(),
+        }
+    }
+
+    /// parses, checks and executes a single input and returns the value of an expression.
+    /// An input which contains an error does not change the session.
+    /// Warnings are ignored, because declaring a variable again is common in a session.
+    pub fn evaluate(&mut self, src: &str) -> Result<Value, InputError> {
+        let stmt = self.parse(src)?;
+        let (resolver, checker) = (self.resolver.clone(), self.checker.clone());
+        let result = self.execute(&stmt);
+        if result.is_err() {
+            self.resolver = resolver;
+            self.checker = checker;
+        }
+        result
+    }
+
+    /// returns the type of an expression without executing it
+    pub fn type_of(&mut self, src: &str) -> Result<Type, InputError> {
+        let stmt = self.parse(src)?;
+        let expr = match &stmt.kind {
+            StatementKind::Expression(expr) => expr,
+            StatementKind::Declaration(..) | StatementKind::Constant(..) |
+            StatementKind::Struct(..) | StatementKind::Enum(..) | StatementKind::Impl(..) => {
+                let message = "expected an expression, found a declaration".into();
+                return Err(InputError::Invalid(vec![Diagnostic::error(message, stmt.span)]));
+            },
+        };
+        self.checker.clone().infer_input(expr)
+            .map_err(|errors| InputError::Invalid(errors.iter().map(Diagnostic::from).collect()))
+    }
+
+    fn parse(&mut self, src: &str) -> Result<Statement, InputError> {
+        let parser = ASTParser::new(Lexer::new(src)).with_first_id(self.next_id);
+        let (stmt, next_id) = parser.parse_statement()
+            .map_err(|errors| InputError::Invalid(errors.iter().map(Diagnostic::from).collect()))?;
+        self.next_id = next_id;
+        Ok(stmt)
+    }
+
+    fn execute(&mut self, stmt: &Statement) -> Result<Value, InputError> {
+        let errors: Vec<Diagnostic> = self.resolver.resolve_input(stmt).iter()
+            .map(Diagnostic::from)
+            .filter(|d| d.severity() == Severity::Error)
+            .collect();
+        if !errors.is_empty() {
+            return Err(InputError::Invalid(errors));
+        }
+        self.checker.check_input(stmt)
+            .map_err(|errors| InputError::Invalid(errors.iter().map(Diagnostic::from).collect()))?;
+
+        let resolution = self.resolver.resolution().clone();
+        let slots = self.resolver.session_slots();
+        self.interpreter.execute_input(stmt, resolution, slots).map_err(InputError::Runtime)
+    }
+}
+
+/// reads the inputs from stdin until it ends or :quit is entered
+pub fn run() {
+    // stdin is not locked between the lines, so read_line can read from it while an input is executed
+    let stdin = std::io::stdin();
+    let mut next_line = || {
+        let mut line = String::new();
+        match stdin.read_line(&mut line) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(line.trim_end_matches(['\n', '\r']).to_string()),
+        }
+    };
+    let mut session = Session::new();
+    println!("paper_castle repl, enter :help for help");
+
+    while let Some(input) = read_input(&mut next_line) {
+        let input = input.trim();
+        let (command, argument) = match input.strip_prefix(':') {
+            Some(command) => {
+                let mut parts = command.splitn(2, char::is_whitespace);
+                (parts.next().unwrap_or(""), parts.next().unwrap_or("").trim())
+            },
+            None => ("", input),
+        };
+
+        match command {
+            "" if argument.is_empty() => {},
+            "" => match session.evaluate(argument) {
+                Ok(Value::Void) => {},
+                Ok(value) => println!("{}", value),
+                Err(InputError::Runtime(RuntimeError::Exit(_))) => break,
+                Err(e) => report(&e, argument),
+            },
+            "ast" => match ASTParser::new(Lexer::new(argument)).parse_statement() {
+                Ok((stmt, _)) => print!("{}", printer::print(&AbstractSyntaxTree::new(vec![stmt]))),
+                Err(errors) => {
+                    let errors = errors.iter().map(Diagnostic::from).collect();
+                    report(&InputError::Invalid(errors), argument);
+                },
+            },
+            "tokens" => {
+                for result in Lexer::new(argument) {
+                    match result {
+                        Ok(token) => println!("{}", cli::describe_token(&token)),
+                        Err(e) => report(&InputError::Invalid(vec![Diagnostic::from(&e)]), argument),
+                    }
+                }
+            },
+            "type" => match session.type_of(argument) {
+                Ok(found) => println!("{}", found),
+                Err(e) => report(&e, argument),
+            },
+            "reset" => session = Session::new(),
+            "help" => println!("{}", HELP),
+            "quit" | "q" => break,
+            _ => eprintln!("unknown command `:{}`, enter :help for help", command),
+        }
+    }
+}
+
+/// reads lines until every bracket and string of the input is closed.
+/// Returns None if stdin ends before an input was entered
+fn read_input<F: FnMut() -> Option<String>>(next_line: &mut F) -> Option<String> {
+    let mut input = String::new();
+    loop {
+        print!("{}", if input.is_empty() { ">> " } else { ".. " });
+        // the prompt is only cosmetic, an error while flushing it can be ignored
+        let _ = std::io::stdout().flush();
+        match next_line() {
+            Some(line) => {
+                input.push_str(&line);
+                input.push('\n');
+            },
+            _ if input.is_empty() => return None,
+            // stdin ended, the incomplete input is executed to report its errors
+            _ => return Some(input),
+        }
+        if !is_incomplete(&input) {
+            return Some(input);
+        }
+    }
+}
+
+/// checks with the lexer if a bracket or a string of the input is still open
+pub fn is_incomplete(input: &str) -> bool {
+    let mut depth: i64 = 0;
+    for result in Lexer::new(input) {
+        match result {
+            Ok(token) => match token.kind() {
+                TokenType::SeparatorCurvedBracketOpen | TokenType::SeparatorBracketOpen |
+                TokenType::SeparatorSquareBracketOpen => depth += 1,
+                TokenType::SeparatorCurvedBracketClosed | TokenType::SeparatorBracketClose |
+                TokenType::SeparatorSquareBracketClosed => depth -= 1,
+                _ => {},
+            },
+            Err(LexerError::UnexpectedEndOfString(_)) | Err(LexerError::UnclosedInterpolation(_)) => return true,
+            Err(_) => return false,
+        }
+    }
+    depth > 0
+}
+
+fn report(error: &InputError, src: &str) {
+    match error {
+        InputError::Invalid(diagnostics) => {
+            for diagnostic in diagnostics {
+                eprint!("{}", diagnostic.render(INPUT_NAME, src, Style::for_stderr()));
+            }
+        },
+        InputError::Runtime(e) => eprintln!("runtime error: {}", e),
+    }
+}
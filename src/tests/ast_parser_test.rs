@@ -1,7 +1,10 @@
-use crate::frontend::lexer::Lexer;
+use crate::frontend::lexer::{Lexer, LexerError};
 use crate::frontend::parser::ast_parser::ASTParser;
 use crate::frontend::syntax::{DataType, DataValue};
 use crate::frontend::syntax::ast::*;
+use crate::frontend::syntax::span::Span;
+use crate::frontend::syntax::token::TokenType;
+use crate::frontend::parser::token_pattern::ParseError;
 
 #[test]
 fn fun_main_function(){
@@ -15,28 +18,27 @@ fn fun_main_function(){
     }");
 
 
-    let (ts,_) = Lexer::tokenize(src);
-    let parser = ASTParser::new(ts);
+    let parser = ASTParser::new(Lexer::new(&src));
     let ast= parser.parse().expect("expect abstract syntax tree");
 
     // a is the first and only symbol
-    let binding = VariableBinding::new(DataType::Boolean,"a".into());
-    let boolean_expression = Expression::Literal(DataValue::Boolean(false));
-    let a_declaration = Statement::new(StatementKind::Declaration(binding,boolean_expression));
+    let binding = binding_of(DataType::Boolean,"a".into());
+    let boolean_expression = expr(ExpressionKind::Literal(DataValue::Boolean(false)));
+    let a_declaration = stmt(StatementKind::Declaration(binding,boolean_expression));
 
-    let a = Expression::Symbol("a".into());
-    let if_condition = Expression::UnaryOp(UnOp::Negation, Box::new(a));
-    let message = Expression::Literal(DataValue::String("a is false".into()));
+    let a = expr(ExpressionKind::Symbol("a".into()));
+    let if_condition = expr(ExpressionKind::UnaryOp(UnOp::Negation, Box::new(a)));
+    let message = expr(ExpressionKind::Literal(DataValue::String("a is false".into())));
     let args = vec![message];
-    let print_call = Expression::FnCall("print".into(), args);
-    let print_stmt = Statement::new(StatementKind::Expression(print_call));
-    let block = Block::new(vec![print_stmt]);
-    let if_stmt_kind = StatementKind::Expression(Expression::If(Box::new(if_condition), block, None));
-    let if_expr = Statement::new(if_stmt_kind);
+    let print_call = expr(ExpressionKind::FnCall("print".into(), args));
+    let print_stmt = stmt(StatementKind::Expression(print_call));
+    let block = block_of(vec![print_stmt]);
+    // the if at the end of the block is its value
+    let if_expr = expr(ExpressionKind::If(Box::new(if_condition), block, None));
 
-    let block = Block::new(vec![a_declaration,if_expr]);
-    let main_expression = Expression::FnDecl("main".into(), block, None, None);
-    let main_function = Statement::new(StatementKind::Expression(main_expression));
+    let block = block_with(vec![a_declaration], if_expr);
+    let main_expression = expr(ExpressionKind::FnDecl("main".into(), block, None, None));
+    let main_function = stmt(StatementKind::Expression(main_expression));
 
     let expected_ast = AbstractSyntaxTree::new(vec![main_function]);
     assert_eq!(expected_ast,ast);
@@ -49,15 +51,14 @@ fn function_with_return_type_test(){
         return \"a b c d e f g\";\
     }");
 
-    let (ts,_) = Lexer::tokenize(src.clone());
-    let ast = ASTParser::new(ts).parse().expect("expected abstract syntax tree");
+    let ast = ASTParser::new(Lexer::new(&src)).parse().expect("expected abstract syntax tree");
 
-    let return_str = Expression::Literal(DataValue::String("a b c d e f g".into()));
-    let return_expr = Expression::Return(Some(Box::new(return_str)));
-    let return_stmt = Statement::new(StatementKind::Expression(return_expr));
-    let block = Block::new(vec![return_stmt]);
-    let test_fn_decl = StatementKind::Expression(Expression::FnDecl("test".into(), block, None, Some(DataType::String)));
-    let test_fn = Statement::new(test_fn_decl);
+    let return_str = expr(ExpressionKind::Literal(DataValue::String("a b c d e f g".into())));
+    let return_expr = expr(ExpressionKind::Return(Some(Box::new(return_str))));
+    let return_stmt = stmt(StatementKind::Expression(return_expr));
+    let block = block_of(vec![return_stmt]);
+    let test_fn_decl = StatementKind::Expression(expr(ExpressionKind::FnDecl("test".into(), block, None, Some(DataType::String))));
+    let test_fn = stmt(test_fn_decl);
 
     let expected_ast = AbstractSyntaxTree::new(vec![test_fn]);
     assert_eq!(expected_ast,ast, "We are comparing two ast build from this source: {}",src);
@@ -72,26 +73,25 @@ fn function_with_arguments() {
         return solution;\
     }");
     println!("src: {}",&src);
-    let (ts,handle) = Lexer::tokenize(src);
-    let ast = ASTParser::new(ts).parse().expect("Expected Abstract Syntax Tree");
-
-    let return_value = Expression::Symbol("solution".into());
-    let return_expression = Expression::Return(Some(Box::new(return_value)));
-    let return_statement = Statement::new(StatementKind::Expression(return_expression));
-    let y = Expression::Symbol("y".into());
-    let x = Expression::Symbol("x".into());
-    let multiplication = Expression::BinaryOp(Box::new(x), BinOp::Multi, Box::new(y));
-    let solution_binding = VariableBinding::new(DataType::Integer,"solution".into());
-    let assignment = Statement::new(StatementKind::Declaration(solution_binding,multiplication));
+    let ast = ASTParser::new(Lexer::new(&src)).parse().expect("Expected Abstract Syntax Tree");
+
+    let return_value = expr(ExpressionKind::Symbol("solution".into()));
+    let return_expression = expr(ExpressionKind::Return(Some(Box::new(return_value))));
+    let return_statement = stmt(StatementKind::Expression(return_expression));
+    let y = expr(ExpressionKind::Symbol("y".into()));
+    let x = expr(ExpressionKind::Symbol("x".into()));
+    let multiplication = expr(ExpressionKind::BinaryOp(Box::new(x), BinOp::Multi, Box::new(y)));
+    let solution_binding = binding_of(DataType::Integer,"solution".into());
+    let assignment = stmt(StatementKind::Declaration(solution_binding,multiplication));
 
     //Function declaration
     let args = vec![
-        VariableBinding::new(DataType::Integer, "x".into()),
-        VariableBinding::new(DataType::Integer,"y".into())
+        binding_of(DataType::Integer, "x".into()),
+        binding_of(DataType::Integer,"y".into())
     ];
-    let test_fn_block = Block::new(vec![assignment,return_statement]);
-    let fn_declaration = Expression::FnDecl("calculate".into(), test_fn_block, Some(args), Some(DataType::Integer));
-    let test_fn = Statement::new(StatementKind::Expression(fn_declaration));
+    let test_fn_block = block_of(vec![assignment,return_statement]);
+    let fn_declaration = expr(ExpressionKind::FnDecl("calculate".into(), test_fn_block, Some(args), Some(DataType::Integer)));
+    let test_fn = stmt(StatementKind::Expression(fn_declaration));
 
     let expected_ast = AbstractSyntaxTree::new(vec![test_fn]);
     assert_eq!(ast,expected_ast);
@@ -106,58 +106,569 @@ fn while_test() {
         }
     }");
 
-    let (ts,_) = Lexer::tokenize(src);
-    let ast = ASTParser::new(ts).parse().expect("Expected Abstract Syntax Tree");
+    let ast = ASTParser::new(Lexer::new(&src)).parse().expect("Expected Abstract Syntax Tree");
 
-    let var_start = Expression::Symbol("start".into());
-    let constant =  Expression::Literal(DataValue::Integer("1".into()));
-    let increment = Expression::BinaryOp(Box::from(var_start), BinOp::Plus, Box::from(constant));
-    let assignment = Expression::Assignment("start".into(), Box::from(increment));
-    let while_condition = Expression::BinaryOp(Box::from(Expression::Symbol("start".into())), BinOp::Lt, Box::from(Expression::Symbol("end".into())));
+    let var_start = expr(ExpressionKind::Symbol("start".into()));
+    let constant =  expr(ExpressionKind::Literal(DataValue::Integer(1)));
+    let increment = expr(ExpressionKind::BinaryOp(Box::from(var_start), BinOp::Plus, Box::from(constant)));
+    let assignment = expr(ExpressionKind::Assignment("start".into(), Box::from(increment)));
+    let while_condition = expr(ExpressionKind::BinaryOp(Box::from(expr(ExpressionKind::Symbol("start".into()))), BinOp::Lt, Box::from(expr(ExpressionKind::Symbol("end".into())))));
 
-    let stmts = vec![Statement::new(StatementKind::Expression(assignment))];
-    let while_block = Block::new(stmts);
+    let stmts = vec![stmt(StatementKind::Expression(assignment))];
+    let while_block = block_of(stmts);
 
-    let while_stmt = Statement::new(
-        StatementKind::Expression(Expression::WhileLoop(Box::from(while_condition), while_block)));
+    let while_stmt = stmt(
+        StatementKind::Expression(expr(ExpressionKind::WhileLoop(Box::from(while_condition), while_block))));
 
     let function_stmts = vec![while_stmt];
-    let start = VariableBinding::new(DataType::Integer,"start".into());
-    let end = VariableBinding::new(DataType::Integer,"end".into());
-    let fn_decl = Expression::FnDecl("count".into(),Block::new(function_stmts), Some(vec![start,end]), None);
-    let function = Statement::new(StatementKind::Expression(fn_decl));
+    let start = binding_of(DataType::Integer,"start".into());
+    let end = binding_of(DataType::Integer,"end".into());
+    let fn_decl = expr(ExpressionKind::FnDecl("count".into(),block_of(function_stmts), Some(vec![start,end]), None));
+    let function = stmt(StatementKind::Expression(fn_decl));
     let expected_ast = AbstractSyntaxTree::new(vec![function]);
     assert_eq!(ast,expected_ast);
 }
 
+#[test]
+fn for_loop_test() {
+    let src = String::from("\
+    fn main() {
+        for i: int in 0..n step -2 {
+            break;
+        }
+        for j: int in 1..10 {}
+    }");
+    let ast = ASTParser::new(Lexer::new(&src)).parse().expect("expected abstract syntax tree");
+
+    let literal = |n: i64| Box::new(expr(ExpressionKind::Literal(DataValue::Integer(n))));
+    let step = literal(-2);
+    let end = Box::new(expr(ExpressionKind::Symbol("n".into())));
+    let break_stmt = stmt(StatementKind::Expression(expr(ExpressionKind::Break(None))));
+    let first = expr(ExpressionKind::ForLoop(binding_of(DataType::Integer, "i".into()), literal(0), end, Some(step), block_of(vec![break_stmt])));
+    let second = expr(ExpressionKind::ForLoop(binding_of(DataType::Integer, "j".into()), literal(1), literal(10), None, block_of(vec![])));
+    let body = block_of(vec![stmt(StatementKind::Expression(first)), stmt(StatementKind::Expression(second))]);
+    let main = expr(ExpressionKind::FnDecl("main".into(), body, None, None));
+    assert_eq!(AbstractSyntaxTree::new(vec![stmt(StatementKind::Expression(main))]), ast);
+
+    let src = String::from("fn main() { for i: int in 0..10 by 2 {} }");
+    let errors = ASTParser::new(Lexer::new(&src)).parse().expect_err("expected missing step");
+    match errors.first() {
+        Some(ParseError::WrongToken(found, expected)) => {
+            assert_eq!("by", found.value());
+            assert_eq!(&vec![TokenType::Step, TokenType::SeparatorCurvedBracketOpen], expected);
+        },
+        other => panic!("expected wrong token, found {:?}", other),
+    }
+}
+
 #[test]
 fn math_expression() {
     let src = String::from("fn doMath() {\
         let x: float = a - b * ( c/d + e/f);
     }");
 
-    let c = Expression::Symbol(String::from("c"));
-    let d = Expression::Symbol(String::from("d"));
-    let e = Expression::Symbol(String::from("e"));
-    let f = Expression::Symbol(String::from("f"));
-
-    let a = Expression::Symbol(String::from("a"));
-    let b = Expression::Symbol(String::from("b"));
-
-    let c_div_d = Expression::BinaryOp(Box::from(c), BinOp::Divide, Box::from(d));
-    let e_div_f = Expression::BinaryOp(Box::from(e), BinOp::Divide, Box::from(f));
-    let addition = Expression::BinaryOp(Box::from(c_div_d), BinOp::Plus, Box::from(e_div_f));
-    let multiplication = Expression::BinaryOp(Box::from(b), BinOp::Multi, Box::from(addition));
-    let subtraction = Expression::BinaryOp(Box::from(a), BinOp::Minus, Box::from(multiplication));
-    let binding = VariableBinding::new(DataType::Float,"x".into());
-    let let_stmt =  Statement::new(StatementKind::Declaration(binding,subtraction));
-
-    let function_block = Block::new(vec![let_stmt]);
-    let fn_decl = Expression::FnDecl("doMath".into(),function_block,None,None);
-    let fn_stmt = Statement::new(StatementKind::Expression(fn_decl));
+    let c = expr(ExpressionKind::Symbol(String::from("c")));
+    let d = expr(ExpressionKind::Symbol(String::from("d")));
+    let e = expr(ExpressionKind::Symbol(String::from("e")));
+    let f = expr(ExpressionKind::Symbol(String::from("f")));
+
+    let a = expr(ExpressionKind::Symbol(String::from("a")));
+    let b = expr(ExpressionKind::Symbol(String::from("b")));
+
+    let c_div_d = expr(ExpressionKind::BinaryOp(Box::from(c), BinOp::Divide, Box::from(d)));
+    let e_div_f = expr(ExpressionKind::BinaryOp(Box::from(e), BinOp::Divide, Box::from(f)));
+    let addition = expr(ExpressionKind::BinaryOp(Box::from(c_div_d), BinOp::Plus, Box::from(e_div_f)));
+    let multiplication = expr(ExpressionKind::BinaryOp(Box::from(b), BinOp::Multi, Box::from(addition)));
+    let subtraction = expr(ExpressionKind::BinaryOp(Box::from(a), BinOp::Minus, Box::from(multiplication)));
+    let binding = binding_of(DataType::Float,"x".into());
+    let let_stmt =  stmt(StatementKind::Declaration(binding,subtraction));
+
+    let function_block = block_of(vec![let_stmt]);
+    let fn_decl = expr(ExpressionKind::FnDecl("doMath".into(),function_block,None,None));
+    let fn_stmt = stmt(StatementKind::Expression(fn_decl));
     let expected_ast = AbstractSyntaxTree::new(vec![fn_stmt]);
 
-    let (ts,_) = Lexer::tokenize(src);
-    let ast = ASTParser::new(ts).parse().expect("ast parsing failed");
+    let ast = ASTParser::new(Lexer::new(&src)).parse().expect("ast parsing failed");
     assert_eq!(expected_ast,ast);
-}
\ No newline at end of file
+}
+
+#[test]
+fn operator_chain_test() {
+    let src = String::from("fn main() {\
+        x = 1 + 2 * 3 - 4 / 2;
+        x = a and b and c or d;
+        x = n - 1 - 2 < m == true != false;
+    }");
+    let ast = ASTParser::new(Lexer::new(&src)).parse().expect("ast parsing failed");
+
+    let int = |n: i64| expr(ExpressionKind::Literal(DataValue::Integer(n)));
+    let boolean = |b: bool| expr(ExpressionKind::Literal(DataValue::Boolean(b)));
+    let symbol = |name: &str| expr(ExpressionKind::Symbol(name.into()));
+    let op = |left: Expression, op: BinOp, right: Expression| expr(ExpressionKind::BinaryOp(Box::new(left), op, Box::new(right)));
+    let assign = |value: Expression| stmt(StatementKind::Expression(expr(ExpressionKind::Assignment("x".into(), Box::new(value)))));
+
+    // ((1 + (2 * 3)) - (4 / 2))
+    let arithmetic = op(op(int(1), BinOp::Plus, op(int(2), BinOp::Multi, int(3))), BinOp::Minus, op(int(4), BinOp::Divide, int(2)));
+    // (((a and b) and c) or d)
+    let logic = op(op(op(symbol("a"), BinOp::And, symbol("b")), BinOp::And, symbol("c")), BinOp::Or, symbol("d"));
+    // ((((n - 1) - 2) < m) == true) != false
+    let difference = op(op(symbol("n"), BinOp::Minus, int(1)), BinOp::Minus, int(2));
+    let comparison = op(op(op(difference, BinOp::Lt, symbol("m")), BinOp::Eq, boolean(true)), BinOp::Neq, boolean(false));
+
+    let body = block_of(vec![assign(arithmetic), assign(logic), assign(comparison)]);
+    let main = expr(ExpressionKind::FnDecl("main".into(), body, None, None));
+    assert_eq!(AbstractSyntaxTree::new(vec![stmt(StatementKind::Expression(main))]), ast);
+}
+
+#[test]
+fn spans_test() {
+    let src = String::from("fn main() {\n    let x: int = (1 + 2) * 3;\n    x = x;\n}");
+    let ast = ASTParser::new(Lexer::new(&src)).parse().expect("ast parsing failed");
+
+    let main = &ast.nodes[0];
+    assert_eq!(Span::new(0,54,1,1), main.span);
+    let block = match &main.kind {
+        StatementKind::Expression(Expression{kind: ExpressionKind::FnDecl(_, block, _, _), ..}) => block,
+        other => panic!("expected function declaration, found {:?}", other),
+    };
+    assert_eq!(Span::new(10,54,1,11), block.span);
+
+    let let_stmt = &block.statements[0];
+    assert_eq!(Span::new(16,41,2,5), let_stmt.span);
+    let (binding, value) = match &let_stmt.kind {
+        StatementKind::Declaration(binding, value) => (binding, value),
+        other => panic!("expected declaration, found {:?}", other),
+    };
+    assert_eq!(Span::new(20,26,2,9), binding.span);
+    assert_eq!(Span::new(29,40,2,18), value.span);
+    if let ExpressionKind::BinaryOp(left, _, right) = &value.kind {
+        assert_eq!(Span::new(29,36,2,18), left.span);
+        assert_eq!(Span::new(39,40,2,28), right.span);
+    } else {
+        panic!("expected binary operation, found {:?}", value);
+    }
+
+    let assignment = &block.statements[1];
+    assert_eq!(Span::new(46,52,3,5), assignment.span);
+}
+
+#[test]
+fn error_recovery_test() {
+    let src = String::from("\
+    fn first() {
+        let a: int = ;
+        let b: int = 2
+        b = 3;
+        if a = = b { b = 4; }
+        b = 5;
+    }
+    fn second(x int) {
+        return 1;
+    }
+    fn third(): int {
+        return 3;
+    }");
+
+    let (ast, errors) = ASTParser::new(Lexer::new(&src)).parse_with_recovery();
+
+    assert_eq!(4, errors.len(), "{:#?}", errors);
+    let lines: Vec<usize> = errors.iter().map(|e| e.span().line).collect();
+    // missing expression, missing ';', second '=' and missing ':'
+    assert_eq!(vec![2, 4, 5, 8], lines);
+
+    let five = expr(ExpressionKind::Literal(DataValue::Integer(5)));
+    let assignment = expr(ExpressionKind::Assignment("b".into(), Box::new(five)));
+    let first = expr(ExpressionKind::FnDecl("first".into(), block_of(vec![stmt(StatementKind::Expression(assignment))]), None, None));
+    let three = expr(ExpressionKind::Literal(DataValue::Integer(3)));
+    let return_stmt = stmt(StatementKind::Expression(expr(ExpressionKind::Return(Some(Box::new(three))))));
+    let third = expr(ExpressionKind::FnDecl("third".into(), block_of(vec![return_stmt]), None, Some(DataType::Integer)));
+    let expected_ast = AbstractSyntaxTree::new(vec![
+        stmt(StatementKind::Expression(first)),
+        stmt(StatementKind::Expression(third)),
+    ]);
+    assert_eq!(expected_ast, ast);
+}
+
+#[test]
+fn missing_closing_bracket_test() {
+    let src = String::from("fn a() {\n    x = 1;\nfn b() {}");
+    let (ast, errors) = ASTParser::new(Lexer::new(&src)).parse_with_recovery();
+
+    match errors.as_slice() {
+        [ParseError::WrongToken(found, expected)] => {
+            assert_eq!(TokenType::Fn, found.kind());
+            assert_eq!(&vec![TokenType::SeparatorCurvedBracketClosed], expected);
+        },
+        other => panic!("expected a single missing bracket, found {:?}", other),
+    }
+    assert_eq!(2, ast.nodes.len());
+}
+
+#[test]
+fn lexer_failure_test() {
+    let src = String::from("fn main() { let s: string = \"not closed; }");
+    let (ast, errors) = ASTParser::new(Lexer::new(&src)).parse_with_recovery();
+
+    // the errors caused by the unfinished function behind the lexer error are not reported
+    assert_eq!(vec![ParseError::Lexer(LexerError::UnexpectedEndOfString(Span::new(28,42,1,29)))], errors);
+    assert_eq!(1, ast.nodes.len());
+}
+
+#[test]
+fn token_stream_test() {
+    let src = "fn main() { x = 1 + 2; }";
+    let threaded = ASTParser::new(Lexer::tokenize(src.into())).parse().expect("ast parsing failed");
+    let borrowed = ASTParser::new(Lexer::new(src)).parse().expect("ast parsing failed");
+    assert_eq!(borrowed, threaded);
+
+    let (ast, errors) = ASTParser::new(Lexer::tokenize("fn main() { x = 1 @ 2; }".into())).parse_with_recovery();
+    assert_eq!(vec![ParseError::Lexer(LexerError::UnknownCharacter('@', Span::new(18,19,1,19)))], errors);
+    assert_eq!(1, ast.nodes.len());
+}
+
+#[test]
+fn parse_statement_test() {
+    let parse = |src: &str| ASTParser::new(Lexer::new(src)).parse_statement().map(|(stmt, _)| stmt);
+
+    let sum = expr(ExpressionKind::BinaryOp(Box::new(expr(ExpressionKind::Symbol("a".into()))), BinOp::Plus, Box::new(expr(ExpressionKind::Symbol("b".into())))));
+    assert_eq!(Ok(stmt(StatementKind::Expression(sum.clone()))), parse("a + b"));
+    assert_eq!(Ok(stmt(StatementKind::Expression(sum))), parse("a + b;"));
+    let one = expr(ExpressionKind::Literal(DataValue::Integer(1)));
+    assert_eq!(Ok(stmt(StatementKind::Declaration(binding_of(DataType::Integer, "x".into()), one))), parse("let x: int = 1;"));
+    assert!(matches!(parse("fn f() {}"), Ok(Statement { kind: StatementKind::Expression(Expression { kind: ExpressionKind::FnDecl(..), .. }), .. })));
+
+    match parse("a + b c").as_ref().map_err(Vec::as_slice) {
+        Err([ParseError::WrongToken(found, expected)]) => {
+            assert_eq!("c", found.value());
+            assert_eq!(&vec![TokenType::EoF], expected);
+        },
+        other => panic!("expected a single wrong token, found {:?}", other),
+    }
+
+    // the ids of the next statement continue after the ids of the first statement
+    let (first, next_id) = ASTParser::new(Lexer::new("a + b")).parse_statement().expect("expected statement");
+    let (second, _) = ASTParser::new(Lexer::new("c")).with_first_id(next_id).parse_statement().expect("expected statement");
+    match (&first.kind, &second.kind) {
+        (StatementKind::Expression(first), StatementKind::Expression(second)) => assert!(first.id < second.id),
+        other => panic!("expected expressions, found {:?}", other),
+    }
+}
+
+#[test]
+fn global_declarations_test() {
+    let src = "\
+    const LIMIT: int = 10;
+    let counter: int = LIMIT;
+    fn main() {
+        const STEP: int = 2;
+    }";
+    let ast = ASTParser::new(Lexer::new(src)).parse().expect("expect abstract syntax tree");
+
+    let ten = expr(ExpressionKind::Literal(DataValue::Integer(10)));
+    let limit = expr(ExpressionKind::Symbol("LIMIT".into()));
+    let two = expr(ExpressionKind::Literal(DataValue::Integer(2)));
+    let step = stmt(StatementKind::Constant(binding_of(DataType::Integer, "STEP".into()), two));
+    let main = expr(ExpressionKind::FnDecl("main".into(), block_of(vec![step]), None, None));
+    let expected_ast = AbstractSyntaxTree::new(vec![
+        stmt(StatementKind::Constant(binding_of(DataType::Integer, "LIMIT".into()), ten)),
+        stmt(StatementKind::Declaration(binding_of(DataType::Integer, "counter".into()), limit)),
+        stmt(StatementKind::Expression(main)),
+    ]);
+    assert_eq!(expected_ast, ast);
+
+    // other statements are not allowed outside of functions
+    let (ast, errors) = ASTParser::new(Lexer::new("x = 1;\nlet y: int = 2;")).parse_with_recovery();
+    match errors.as_slice() {
+        [ParseError::WrongToken(found, expected)] => {
+            assert_eq!("x", found.value());
+            assert_eq!(&vec![TokenType::Fn, TokenType::Let, TokenType::Const, TokenType::Struct,
+                             TokenType::Enum, TokenType::Impl], expected);
+        },
+        other => panic!("expected a single wrong token, found {:?}", other),
+    }
+    assert_eq!(1, ast.nodes.len());
+}
+
+#[test]
+fn arrays_test() {
+    let src = "\
+    fn main() {
+        let grid: [[int]] = [[1], []];
+        grid[0][0] = grid[1].pop();
+    }";
+    let ast = ASTParser::new(Lexer::new(src)).parse().expect("expect abstract syntax tree");
+
+    let int_array = DataType::Array(Box::new(DataType::Integer));
+    let one = expr(ExpressionKind::Literal(DataValue::Integer(1)));
+    let literal = expr(ExpressionKind::Array(vec![
+        expr(ExpressionKind::Array(vec![one])),
+        expr(ExpressionKind::Array(vec![])),
+    ]));
+    let declaration = stmt(StatementKind::Declaration(binding_of(DataType::Array(Box::new(int_array)), "grid".into()), literal));
+    let index = |array: Expression, n: i64| expr(ExpressionKind::Index(
+        Box::new(array),
+        Box::new(expr(ExpressionKind::Literal(DataValue::Integer(n)))),
+    ));
+    let grid = || expr(ExpressionKind::Symbol("grid".into()));
+    let pop = expr(ExpressionKind::MethodCall(Box::new(index(grid(), 1)), "pop".into(), vec![]));
+    let assignment = expr(ExpressionKind::IndexAssignment(
+        Box::new(index(grid(), 0)),
+        Box::new(expr(ExpressionKind::Literal(DataValue::Integer(0)))),
+        Box::new(pop),
+    ));
+    let body = block_of(vec![declaration, stmt(StatementKind::Expression(assignment))]);
+    let main = expr(ExpressionKind::FnDecl("main".into(), body, None, None));
+    assert_eq!(AbstractSyntaxTree::new(vec![stmt(StatementKind::Expression(main))]), ast);
+
+    let errors = ASTParser::new(Lexer::new("fn main() { len() = 1; }")).parse().expect_err("expected parse error");
+    assert!(matches!(errors.as_slice(), [ParseError::GrammarMistake(..)]), "{:?}", errors);
+}
+
+#[test]
+fn structs_test() {
+    let src = "\
+    struct Point { x: float, y: float, }
+    fn main() {
+        let p: Point = Point { y: 2.0, x: 1.0 };
+        p.x = p.y;
+    }";
+    let ast = ASTParser::new(Lexer::new(src)).parse().expect("expect abstract syntax tree");
+
+    let fields = vec![binding_of(DataType::Float, "x".into()), binding_of(DataType::Float, "y".into())];
+    let declaration = stmt(StatementKind::Struct("Point".into(), fields));
+    let float = |n: f64| expr(ExpressionKind::Literal(DataValue::Float(n)));
+    let literal = expr(ExpressionKind::StructLiteral("Point".into(), vec![("y".into(), float(2.0)), ("x".into(), float(1.0))]));
+    let p = || Box::new(expr(ExpressionKind::Symbol("p".into())));
+    let let_p = stmt(StatementKind::Declaration(binding_of(DataType::Named("Point".into()), "p".into()), literal));
+    let read = expr(ExpressionKind::Field(p(), "y".into()));
+    let assignment = expr(ExpressionKind::FieldAssignment(p(), "x".into(), Box::new(read)));
+    let body = block_of(vec![let_p, stmt(StatementKind::Expression(assignment))]);
+    let main = expr(ExpressionKind::FnDecl("main".into(), body, None, None));
+    assert_eq!(AbstractSyntaxTree::new(vec![declaration, stmt(StatementKind::Expression(main))]), ast);
+
+    for src in &["struct Empty {}", "struct P { x: int, x: int }", "fn main() { let p: P = P { x: 1, x: 2 }; }"] {
+        let errors = ASTParser::new(Lexer::new(src)).parse().expect_err("expected parse error");
+        assert!(matches!(errors.as_slice(), [ParseError::GrammarMistake(..)]), "{}: {:?}", src, errors);
+    }
+}
+
+#[test]
+fn impl_test() {
+    let src = "\
+    impl Point {
+        fn scale(self, f: float) {}
+    }
+    fn main() {
+        p.scale(2.0);
+        Point::new(1.0);
+    }";
+    let ast = ASTParser::new(Lexer::new(src)).parse().expect("expect abstract syntax tree");
+
+    let float = |n: f64| expr(ExpressionKind::Literal(DataValue::Float(n)));
+    let args = vec![binding_of(DataType::Named("Point".into()), "self".into()), binding_of(DataType::Float, "f".into())];
+    let scale = expr(ExpressionKind::FnDecl("scale".into(), block_of(vec![]), Some(args), None));
+    let declaration = stmt(StatementKind::Impl("Point".into(), vec![scale]));
+    let method = expr(ExpressionKind::MethodCall(Box::new(expr(ExpressionKind::Symbol("p".into()))), "scale".into(), vec![float(2.0)]));
+    let new = expr(ExpressionKind::FnCall("Point::new".into(), vec![float(1.0)]));
+    let body = block_of(vec![stmt(StatementKind::Expression(method)), stmt(StatementKind::Expression(new))]);
+    let main = expr(ExpressionKind::FnDecl("main".into(), body, None, None));
+    assert_eq!(AbstractSyntaxTree::new(vec![declaration, stmt(StatementKind::Expression(main))]), ast);
+
+    for src in &["fn f(self) {}", "impl P { fn f(x: int, self) {} }", "impl P { let x: int = 1; }"] {
+        let errors = ASTParser::new(Lexer::new(src)).parse().expect_err("expected parse error");
+        assert!(!errors.is_empty(), "{}", src);
+    }
+}
+
+#[test]
+fn enum_match_test() {
+    let src = "\
+    enum Shape { Circle(float), Empty }
+    fn main() {
+        match Shape::Circle(1.0) {
+            Shape::Circle(r) => r,
+            Shape::Empty => {}
+            _ => -1,
+        }
+        let e: Shape = Shape::Empty;
+    }";
+    let ast = ASTParser::new(Lexer::new(src)).parse().expect("expect abstract syntax tree");
+
+    let variant = |name: &str, fields: Vec<DataType>| Variant { name: name.into(), fields, span: Span::default() };
+    let declaration = stmt(StatementKind::Enum("Shape".into(), vec![variant("Circle", vec![DataType::Float]), variant("Empty", vec![])]));
+    let circle = expr(ExpressionKind::FnCall("Shape::Circle".into(), vec![expr(ExpressionKind::Literal(DataValue::Float(1.0)))]));
+    let arm = |kind: PatternKind, value: Expression| MatchArm { pattern: pattern(kind), value };
+    let r = PatternKind::Binding("r".into());
+    let arms = vec![
+        arm(PatternKind::Variant("Shape".into(), "Circle".into(), vec![pattern(r)]), expr(ExpressionKind::Symbol("r".into()))),
+        arm(PatternKind::Variant("Shape".into(), "Empty".into(), vec![]), expr(ExpressionKind::Block(block_of(vec![])))),
+        arm(PatternKind::Wildcard, expr(ExpressionKind::Literal(DataValue::Integer(-1)))),
+    ];
+    let match_stmt = stmt(StatementKind::Expression(expr(ExpressionKind::Match(Box::new(circle), arms))));
+    let empty = expr(ExpressionKind::Symbol("Shape::Empty".into()));
+    let let_stmt = stmt(StatementKind::Declaration(binding_of(DataType::Named("Shape".into()), "e".into()), empty));
+    let main = expr(ExpressionKind::FnDecl("main".into(), block_of(vec![match_stmt, let_stmt]), None, None));
+    assert_eq!(AbstractSyntaxTree::new(vec![declaration, stmt(StatementKind::Expression(main))]), ast);
+
+    // literal patterns keep the sign of negative numbers
+    let (stmt, _) = ASTParser::new(Lexer::new("match x { -1 => true, \"a\" => false, n => n == 2 }")).parse_statement()
+        .expect("expect statement");
+    let patterns: Vec<PatternKind> = match stmt.kind {
+        StatementKind::Expression(Expression { kind: ExpressionKind::Match(_, arms), .. }) => {
+            arms.into_iter().map(|arm| arm.pattern.kind).collect()
+        },
+        other => panic!("expected a match, found {:?}", other),
+    };
+    assert_eq!(vec![PatternKind::Literal(DataValue::Integer(-1)), PatternKind::Literal(DataValue::String("a".into())),
+                    PatternKind::Binding("n".into())], patterns);
+
+    for src in &["enum E {}", "enum E { A, A }", "enum E { A(int B }", "fn f() { match x { 1 => 2 3 => 4 } }",
+                 "fn f() { match x { 1 + 2 => 3 } }", "fn f() { match x { y: int => 1 } }"] {
+        let errors = ASTParser::new(Lexer::new(src)).parse().expect_err("expected parse error");
+        assert!(!errors.is_empty(), "{}", src);
+    }
+}
+
+#[test]
+fn lambda_test() {
+    let src = "\
+    fn main() {
+        let f: fn(int, fn()): int = fn(x: int, g: fn()): int { return x; };
+        make()(1);
+    }";
+    let ast = ASTParser::new(Lexer::new(src)).parse().expect("expect abstract syntax tree");
+
+    let callback = DataType::Function(vec![], None);
+    let fn_type = DataType::Function(vec![DataType::Integer, callback.clone()], Some(Box::new(DataType::Integer)));
+    let args = vec![binding_of(DataType::Integer, "x".into()), binding_of(callback, "g".into())];
+    let ret = stmt(StatementKind::Expression(expr(ExpressionKind::Return(Some(Box::new(expr(ExpressionKind::Symbol("x".into()))))))));
+    let lambda = expr(ExpressionKind::Lambda(args, Some(DataType::Integer), block_of(vec![ret])));
+    let declaration = stmt(StatementKind::Declaration(binding_of(fn_type, "f".into()), lambda));
+    let make = expr(ExpressionKind::FnCall("make".into(), vec![]));
+    let one = expr(ExpressionKind::Literal(DataValue::Integer(1)));
+    let call = stmt(StatementKind::Expression(expr(ExpressionKind::Call(Box::new(make), vec![one]))));
+    let main = expr(ExpressionKind::FnDecl("main".into(), block_of(vec![declaration, call]), None, None));
+    assert_eq!(AbstractSyntaxTree::new(vec![stmt(StatementKind::Expression(main))]), ast);
+
+    // a lambda at the start of a statement is an expression, not a declaration
+    let (lambda, _) = ASTParser::new(Lexer::new("fn(x: int) {}(1)")).parse_statement().expect("expect statement");
+    assert!(matches!(lambda.kind, StatementKind::Expression(Expression { kind: ExpressionKind::Call(..), .. })), "{:?}", lambda);
+
+    // a lambda at the start of a statement or as value of a block does not end the block
+    let src = "fn make(): fn(int): int { fn(x: int) {}(1); fn(x: int): int { return x * 2; } }";
+    let ast = ASTParser::new(Lexer::new(src)).parse().expect("expect abstract syntax tree");
+    let body = match &ast.nodes[..] {
+        [Statement { kind: StatementKind::Expression(Expression { kind: ExpressionKind::FnDecl(_, body, ..), .. }), .. }] => body,
+        other => panic!("expected a single function, found {:?}", other),
+    };
+    assert!(matches!(&body.statements[..], [Statement { kind: StatementKind::Expression(Expression { kind: ExpressionKind::Call(..), .. }), .. }]));
+    assert!(matches!(body.value.as_deref(), Some(Expression { kind: ExpressionKind::Lambda(..), .. })), "{:?}", body.value);
+
+    for src in &["fn main() { let f: fn(int = 1; }", "fn main() { fn(x) {}; }", "fn main() { fn(): {}; }"] {
+        let errors = ASTParser::new(Lexer::new(src)).parse().expect_err("expected parse error");
+        assert!(!errors.is_empty(), "{}", src);
+    }
+}
+
+#[test]
+fn block_value_test() {
+    let src = "fn main(): int { let x: int = if true { 1 } else { 2 }; loop { break x; } { x } }";
+    let ast = ASTParser::new(Lexer::new(src)).parse().expect("expect abstract syntax tree");
+
+    let int = |value: i64| expr(ExpressionKind::Literal(DataValue::Integer(value)));
+    let x = || expr(ExpressionKind::Symbol("x".into()));
+    let condition = Box::new(expr(ExpressionKind::Literal(DataValue::Boolean(true))));
+    let if_expr = expr(ExpressionKind::If(condition, block_with(vec![], int(1)), Some(block_with(vec![], int(2)))));
+    let declaration = stmt(StatementKind::Declaration(binding_of(DataType::Integer, "x".into()), if_expr));
+    let break_stmt = stmt(StatementKind::Expression(expr(ExpressionKind::Break(Some(Box::new(x()))))));
+    let loop_stmt = stmt(StatementKind::Expression(expr(ExpressionKind::Loop(block_of(vec![break_stmt])))));
+    let value = expr(ExpressionKind::Block(block_with(vec![], x())));
+    let body = block_with(vec![declaration, loop_stmt], value);
+    let main = expr(ExpressionKind::FnDecl("main".into(), body, None, Some(DataType::Integer)));
+    assert_eq!(AbstractSyntaxTree::new(vec![stmt(StatementKind::Expression(main))]), ast);
+
+    for src in &["fn main() { 1 2 }", "fn main() { let x: int = if true { 1 } else { 2 } }", "fn main() { break 1 }"] {
+        let errors = ASTParser::new(Lexer::new(src)).parse().expect_err("expected parse error");
+        assert!(!errors.is_empty(), "{}", src);
+    }
+}
+
+#[test]
+fn interpolation_test() {
+    let src = r#"fn main() { let s: string = "{x}: {x + 1}!"; }"#;
+    let ast = ASTParser::new(Lexer::new(src)).parse().expect("expect abstract syntax tree");
+
+    let x = || expr(ExpressionKind::Symbol("x".into()));
+    let one = expr(ExpressionKind::Literal(DataValue::Integer(1)));
+    let sum = expr(ExpressionKind::BinaryOp(Box::new(x()), BinOp::Plus, Box::new(one)));
+    let parts = vec![
+        InterpolationPart::Value(x()),
+        InterpolationPart::Text(": ".into()),
+        InterpolationPart::Value(sum),
+        InterpolationPart::Text("!".into()),
+    ];
+    let declaration = stmt(StatementKind::Declaration(binding_of(DataType::String, "s".into()), expr(ExpressionKind::Interpolation(parts))));
+    let main = expr(ExpressionKind::FnDecl("main".into(), block_of(vec![declaration]), None, None));
+    assert_eq!(AbstractSyntaxTree::new(vec![stmt(StatementKind::Expression(main))]), ast);
+
+    for src in &[r#"fn main() { "{}"; }"#, r#"fn main() { "{1 2}"; }"#, r#"fn main() { "{let x: int = 1;}"; }"#] {
+        let errors = ASTParser::new(Lexer::new(src)).parse().expect_err("expected parse error");
+        assert!(!errors.is_empty(), "{}", src);
+    }
+}
+
+#[test]
+fn number_literals_test() {
+    let src = "fn main() { let x: int = 0xFF + 0b11 + 0o10 + 1_000; let y: float = 1_0.5e-1; match x { -9223372036854775808 => {} _ => {} } }";
+    let ast = ASTParser::new(Lexer::new(src)).parse().expect("expect abstract syntax tree");
+    let literals = format!("{:?}", ast);
+    for value in &["Integer(255)", "Integer(3)", "Integer(8)", "Integer(1000)", "Float(1.05)", "Integer(-9223372036854775808)"] {
+        assert!(literals.contains(value), "{} in {}", value, literals);
+    }
+
+    for src in &["fn main() { let x: int = 9223372036854775808; }", "fn main() { let x: int = 0x1_0000_0000_0000_0000; }"] {
+        let errors = ASTParser::new(Lexer::new(src)).parse().expect_err("expected parse error");
+        assert!(matches!(&errors[..], [ParseError::IntegerOutOfRange(token)] if token.kind() == TokenType::LiteralInteger), "{:?}", errors);
+    }
+    let largest = "fn main() { let x: int = 9223372036854775807; let y: int = 0x7FFF_FFFF_FFFF_FFFF; }";
+    assert!(ASTParser::new(Lexer::new(largest)).parse().is_ok());
+    let smallest = "fn main() { let x: int = -9223372036854775808; let y: int = 1 - -0x8000_0000_0000_0000; }";
+    let ast = ASTParser::new(Lexer::new(smallest)).parse().expect("expect abstract syntax tree");
+    assert_eq!(2, format!("{:?}", ast).matches("Integer(-9223372036854775808)").count());
+    // the minus of a negated receiver belongs to the whole call, so the literal itself is too large
+    let receiver = "fn main() { let x: int = -9223372036854775808.abs(); }";
+    assert!(ASTParser::new(Lexer::new(receiver)).parse().is_err());
+
+    for src in &["fn main() { let x: float = 1e309; }", "fn main() { let x: float = 1e-400; }"] {
+        let errors = ASTParser::new(Lexer::new(src)).parse().expect_err("expected parse error");
+        assert!(matches!(&errors[..], [ParseError::FloatOutOfRange(token)] if token.kind() == TokenType::LiteralFloat), "{:?}", errors);
+    }
+    let zero = "fn main() { let x: float = 0.0e-400; let y: float = 1.7976931348623157e308; }";
+    assert!(ASTParser::new(Lexer::new(zero)).parse().is_ok());
+}
+
+/// creates an expression without a position in the source code.
+/// spans are ignored when nodes are compared, so the expected trees dont need them
+fn expr(kind: ExpressionKind) -> Expression {
+    Expression::new(NodeId::default(), kind, Span::default())
+}
+
+fn stmt(kind: StatementKind) -> Statement {
+    Statement::new(kind, Span::default())
+}
+
+fn block_of(stmts: Vec<Statement>) -> Block {
+    Block::new(stmts, Span::default())
+}
+
+fn block_with(stmts: Vec<Statement>, value: Expression) -> Block {
+    Block::with_value(stmts, value, Span::default())
+}
+
+fn pattern(kind: PatternKind) -> Pattern {
+    Pattern::new(NodeId::default(), kind, Span::default())
+}
+
+fn binding_of(data_type: DataType, symbol: String) -> VariableBinding {
+    VariableBinding::new(NodeId::default(), data_type, symbol, Span::default())
+}
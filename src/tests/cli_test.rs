@@ -0,0 +1,45 @@
+use crate::cli::{exit_code, output_failed, parse_args, Command, Input, EXIT_IO_ERROR};
+
+use std::io::{Error, ErrorKind};
+
+#[test]
+fn commands_test() {
+    assert_eq!(Ok(Command::Run { input: Input::File("a.pc".into()), interpret: false }), args(&["run", "a.pc"]));
+    assert_eq!(Ok(Command::Run { input: Input::Stdin, interpret: true }), args(&["run", "--interpret", "-"]));
+    assert_eq!(Ok(Command::Check(Input::File("b.pc".into()))), args(&["check", "b.pc"]));
+    assert_eq!(Ok(Command::Tokens(Input::Stdin)), args(&["tokens", "-"]));
+    assert_eq!(Ok(Command::Ast(Input::File("c.pc".into()))), args(&["ast", "c.pc"]));
+    assert_eq!(Ok(Command::Help), args(&["help"]));
+    assert_eq!("<stdin>", Input::Stdin.name());
+}
+
+#[test]
+fn exit_code_test() {
+    assert_eq!(0, exit_code(0));
+    assert_eq!(255, exit_code(255));
+    // ints which the calling process can not receive are not truncated to a success like 256 to 0
+    assert_eq!(255, exit_code(256));
+    assert_eq!(255, exit_code(-1));
+    assert_eq!(255, exit_code(i64::MIN));
+}
+
+#[test]
+fn output_failed_test() {
+    // a reader like `head` which closed the pipe is no error
+    assert_eq!(Ok(()), output_failed(Error::from(ErrorKind::BrokenPipe)));
+    assert_eq!(Err(EXIT_IO_ERROR), output_failed(Error::from(ErrorKind::PermissionDenied)));
+}
+
+#[test]
+fn invalid_arguments_test() {
+    assert_eq!(Err("missing command".into()), args(&[]));
+    assert_eq!(Err("missing file for `run`".into()), args(&["run"]));
+    assert_eq!(Err("unknown command `compile`".into()), args(&["compile", "a.pc"]));
+    assert_eq!(Err("unknown command `compile`".into()), args(&["compile"]));
+    assert_eq!(Err("unexpected argument `b.pc`".into()), args(&["check", "a.pc", "b.pc"]));
+    assert_eq!(Err("unknown option `--interpret` for `check`".into()), args(&["check", "--interpret", "a.pc"]));
+}
+
+fn args(args: &[&str]) -> Result<Command, String> {
+    parse_args(args.iter().map(|arg| arg.to_string()))
+}
@@ -0,0 +1,179 @@
+use crate::frontend::diagnostics::{Diagnostic, Severity, Style};
+use crate::frontend::lexer::Lexer;
+use crate::frontend::parser::ast_parser::ASTParser;
+use crate::frontend::semantic::{resolver, type_checker};
+
+#[test]
+fn wrong_token_report_test() {
+    let src = "fn main() {\n    let x: int = 5\n}";
+    let error = ASTParser::new(Lexer::new(src)).parse().expect_err("expected missing semicolon").remove(0);
+
+    let report = Diagnostic::from(&error).render("main.pc", src, Style::Plain);
+    let expected = "\
+error: unexpected `}`
+ --> main.pc:3:1
+  |
+3 | }
+  | ^ expected `;`
+  |
+  = help: every statement has to end with `;`
+";
+    assert_eq!(expected, report);
+}
+
+#[test]
+fn expected_one_of_report_test() {
+    let src = "fn add(a: int b: int) {}";
+    let error = ASTParser::new(Lexer::new(src)).parse().expect_err("expected missing comma").remove(0);
+
+    let report = Diagnostic::from(&error).render("add.pc", src, Style::Plain);
+    let expected = "\
+error: unexpected identifier `b`
+ --> add.pc:1:15
+  |
+1 | fn add(a: int b: int) {}
+  |               ^ expected one of `,`, `)`
+";
+    assert_eq!(expected, report);
+}
+
+#[test]
+fn lexer_error_report_test() {
+    let src = "fn main() {\n\tlet s: string = \"unterminated;\n}";
+    let error = Lexer::new(src).find_map(Result::err).expect("expected lexer error");
+
+    let report = Diagnostic::from(&error).render("string.pc", src, Style::Plain);
+    let expected = "\
+error: unterminated string literal
+ --> string.pc:2:18
+  |
+2 | \tlet s: string = \"unterminated;
+  | \t                ^^^^^^^^^^^^^^ string starts here
+  |
+  = help: add a closing `\"` at the end of the string
+";
+    assert_eq!(expected, report);
+}
+
+#[test]
+fn unclosed_interpolation_report_test() {
+    let src = "fn main() {\n    println(\"{\");\n}";
+    let error = Lexer::new(src).find_map(Result::err).expect("expected lexer error");
+
+    let report = Diagnostic::from(&error).render("brace.pc", src, Style::Plain);
+    let expected = "\
+error: unclosed `{` in a string literal
+ --> brace.pc:2:14
+  |
+2 |     println(\"{\");
+  |              ^ this bracket starts an embedded expression which is never closed
+  |
+  = help: write `\\{` to put a `{` into the string, or close the expression with `}`
+";
+    assert_eq!(expected, report);
+}
+
+#[test]
+fn colored_report_test() {
+    let src = "fn main() { 5 6 }";
+    let error = ASTParser::new(Lexer::new(src)).parse().expect_err("expected wrong token").remove(0);
+
+    let plain = Diagnostic::from(&error).render("main.pc", src, Style::Plain);
+    let colored = Diagnostic::from(&error).render("main.pc", src, Style::Colored);
+    assert!(!plain.contains('\x1b'));
+    assert!(colored.starts_with("\x1b[1;31merror\x1b[0m"));
+    assert_eq!(plain, strip_ansi(&colored));
+}
+
+#[test]
+fn warning_report_test() {
+    let src = "fn main() {\n    let x: int = 1;\n    let x: int = 2;\n}";
+    let ast = ASTParser::new(Lexer::new(src)).parse().expect("expected abstract syntax tree");
+    let (_, errors) = resolver::resolve(&ast);
+
+    let diagnostic = Diagnostic::from(&errors[0]);
+    assert_eq!(Severity::Warning, diagnostic.severity());
+    let expected = "\
+warning: variable `x` shadows a previous declaration
+ --> main.pc:3:9
+  |
+3 |     let x: int = 2;
+  |         ^^^^^^ `x` is declared again here
+  |
+  = help: the previous declaration of `x` is on line 2
+";
+    assert_eq!(expected, diagnostic.render("main.pc", src, Style::Plain));
+    assert!(diagnostic.render("main.pc", src, Style::Colored).starts_with("\x1b[1;33mwarning\x1b[0m"));
+}
+
+#[test]
+fn invalid_escape_report_test() {
+    let src = "let s: string = \"tab\\q\";";
+    let error = Lexer::new(src).find_map(Result::err).expect("expected lexer error");
+
+    let report = Diagnostic::from(&error).render("escape.pc", src, Style::Plain);
+    assert!(report.starts_with("error: unknown escape sequence `\\q`\n --> escape.pc:1:21\n"), "{}", report);
+    assert!(report.contains("  |                     ^^\n"), "{}", report);
+}
+
+#[test]
+fn malformed_number_report_test() {
+    let src = "let x: int = 0b102;";
+    let error = Lexer::new(src).find_map(Result::err).expect("expected lexer error");
+
+    let report = Diagnostic::from(&error).render("number.pc", src, Style::Plain);
+    assert!(report.starts_with("error: malformed number literal\n --> number.pc:1:14\n"), "{}", report);
+    assert!(report.contains("^^^^^ a binary number only contains the digits 0 and 1"), "{}", report);
+}
+
+#[test]
+fn integer_out_of_range_report_test() {
+    let src = "let x: int = 0x1_0000_0000_0000_0000;";
+    let error = ASTParser::new(Lexer::new(src)).parse().expect_err("expected out of range integer").remove(0);
+
+    let report = Diagnostic::from(&error).render("range.pc", src, Style::Plain);
+    assert!(report.starts_with("error: integer literal `0x1_0000_0000_0000_0000` is out of range\n --> range.pc:1:14\n"), "{}", report);
+    assert!(report.contains("an `int` is a number from -9223372036854775808 to 9223372036854775807"), "{}", report);
+}
+
+#[test]
+fn native_as_value_report_test() {
+    let src = "fn main() { let f: fn(int, int): int = max; }";
+    let ast = ASTParser::new(Lexer::new(src)).parse().expect("expected abstract syntax tree");
+    let (_, errors) = resolver::resolve(&ast);
+
+    let report = Diagnostic::from(&errors[0]).render("native.pc", src, Style::Plain);
+    assert!(report.starts_with("error: cannot find variable `max` in this scope\n"), "{}", report);
+    assert!(report.contains("wrap it in a lambda like `fn(a: int, b: int): int { max(a, b) }`"), "{}", report);
+}
+
+#[test]
+fn duplicate_report_test() {
+    let src = "fn main() { let a: int = true; b = 1; }";
+    let ast = ASTParser::new(Lexer::new(src)).parse().expect("expected abstract syntax tree");
+    let (_, problems) = resolver::resolve(&ast);
+    let errors = type_checker::check(&ast).expect_err("expected type errors");
+
+    let resolved: Vec<Diagnostic> = problems.iter().map(Diagnostic::from).collect();
+    let checked: Vec<Diagnostic> = errors.iter().map(Diagnostic::from).collect();
+    assert_eq!(1, resolved.len());
+    assert_eq!(2, checked.len());
+    // the type checker reports the undefined variable like the resolver, but not the mismatch
+    assert!(!resolved[0].duplicates(&checked[0]));
+    assert!(resolved[0].duplicates(&checked[1]));
+}
+
+/// removes all ANSI escape sequences from the text
+fn strip_ansi(text: &str) -> String {
+    let mut stripped = String::new();
+    let mut escaped = false;
+    for c in text.chars() {
+        match (escaped, c) {
+            (false, '\x1b') => escaped = true,
+            (true, 'm') => escaped = false,
+            (false, c) => stripped.push(c),
+            _ => {},
+        }
+    }
+    stripped
+}
@@ -0,0 +1,747 @@
+use crate::frontend::lexer::Lexer;
+use crate::frontend::parser::ast_parser::ASTParser;
+use crate::frontend::syntax::DataType;
+use crate::frontend::syntax::ast::{BinOp, LAMBDA};
+use crate::backend::interpreter::{self, RuntimeInterpreter, RuntimeError};
+use crate::backend::value::Value;
+use crate::backend::{bytecode, vm};
+
+#[test]
+fn fibonacci_test() {
+    let src = "\
+    fn fibonacci(n: int): int {
+        if n == 1 or n == 2 {
+            return 1;
+        }
+        return fibonacci(n - 1) + fibonacci(n - 2);
+    }
+
+    fn main(): int {
+        return fibonacci(15);
+    }";
+
+    assert_eq!(Ok(Value::Integer(610)), run(src));
+}
+
+#[test]
+fn loops_test() {
+    let src = "\
+    fn main(): int {
+        let sum: int = 0;
+        let i: int = 0;
+        while i < 10 {
+            i = i + 1;
+            if i == 5 {
+                continue;
+            }
+            sum = sum + i;
+        }
+        loop {
+            sum = sum * 2;
+            if sum > 1000 {
+                break;
+            }
+        }
+        return sum;
+    }";
+
+    assert_eq!(Ok(Value::Integer(1600)), run(src));
+}
+
+#[test]
+fn scopes_and_strings_test() {
+    let src = "\
+    fn greet(name: string): string {
+        let greeting: string = \"hello \";
+        if true {
+            let greeting: string = \"bye \";
+        }
+        return greeting + name;
+    }
+
+    fn main(): string {
+        return greet(\"world\");
+    }";
+
+    assert_eq!(Ok(Value::String("hello world".into())), run(src));
+}
+
+#[test]
+fn logic_test() {
+    let src = "\
+    fn main(): boolean {
+        let x: float = -1.5 * 2.0;
+        let valid: boolean = !(x > 0.0) and x != -3.5;
+        return valid or undefined();
+    }";
+
+    assert_eq!(Ok(Value::Boolean(true)), run(src));
+}
+
+#[test]
+fn void_main_test() {
+    let src = "\
+    fn main() {
+        return;
+    }";
+
+    assert_eq!(Ok(Value::Void), run(src));
+}
+
+#[test]
+fn runtime_errors_test() {
+    let missing_main = "fn test() {}";
+    assert_eq!(Err(RuntimeError::MissingMainFunction), run(missing_main));
+
+    let division = "fn main(): int { return 1 / 0; }";
+    assert_eq!(Err(RuntimeError::DivisionByZero), run(division));
+
+    let mixed_types = "fn main(): float { return 1 + 2.0; }";
+    assert_eq!(Err(RuntimeError::InvalidOperands(BinOp::Plus, "int", "float")), run(mixed_types));
+
+    let wrong_binding = "fn main() { let a: int = true; }";
+    assert_eq!(Err(RuntimeError::TypeMismatch(DataType::Integer, "boolean")), run(wrong_binding));
+
+    let arguments = "fn f(a: int) {} fn main() { f(1, 2); }";
+    assert_eq!(Err(RuntimeError::WrongArgumentCount("f".into(), 1, 2)), run(arguments));
+
+    let undefined = "fn main() { x = 5; }";
+    assert_eq!(Err(RuntimeError::UndefinedVariable("x".into())), run(undefined));
+
+    let recursion = "fn main() { main(); }";
+    interpreter::with_interpreter_stack(|| assert_eq!(Err(RuntimeError::StackOverflow), run(recursion)));
+
+    let missing_return = "fn main(): int { }";
+    assert_eq!(Err(RuntimeError::MissingReturnValue("main".into())), run(missing_return));
+}
+
+#[test]
+fn deep_recursion_test() {
+    let sum = |n: i64| format!("\
+    fn sum(n: int): int {{
+        if n == 0 {{
+            return 0;
+        }}
+        return n + sum(n - 1);
+    }}
+    fn main(): int {{ return sum({}); }}", n);
+    interpreter::with_interpreter_stack(|| {
+        assert_eq!(Ok(Value::Integer(200 * 201 / 2)), run(&sum(200)));
+        assert_eq!(Ok(Value::Integer(9000 * 9001 / 2)), run(&sum(9000)));
+    });
+    // the smaller stack of the test thread ends the recursion early instead of overflowing
+    let interpreted = RuntimeInterpreter::new(ASTParser::new(Lexer::new(&sum(9000))).parse().unwrap()).start();
+    assert_eq!(Err(RuntimeError::StackOverflow), interpreted);
+}
+
+#[test]
+fn for_loop_test() {
+    let src = "\
+    fn main(): int {
+        let sum: int = 0;
+        for i: int in 0..10 {
+            if i == 3 {
+                continue;
+            }
+            if i == 8 {
+                break;
+            }
+            sum = sum + i;
+        }
+        for i: int in 10..0 step -3 {
+            # changing the loop variable does not change the iterations
+            i = i * 100;
+            sum = sum + i;
+        }
+        for i: int in 0..3 {
+            for j: int in i..3 {
+                sum = sum + 1000;
+            }
+        }
+        for i: int in 5..5 {
+            sum = 0;
+        }
+        return sum;
+    }";
+
+    assert_eq!(Ok(Value::Integer(25 + 2200 + 6000)), run(src));
+
+    // the loop ends when the next value of the counter does not fit into an int
+    let near_max = "\
+    fn main(): int {
+        let count: int = 0;
+        for i: int in 9223372036854775806..9223372036854775807 step 5 {
+            count = count + 1;
+        }
+        for i: int in -9223372036854775807..-9223372036854775808 step -2 {
+            count = count + 10;
+        }
+        return count;
+    }";
+    assert_eq!(Ok(Value::Integer(11)), run(near_max));
+}
+
+#[test]
+fn operator_chain_test() {
+    let src = "\
+    fn main(): int {
+        let a: int = 1 + 2 * 3 - 4 / 2;
+        let b: int = 20 - 5 - 3 - 2;
+        let c: int = 100 / 10 / 5 * 3;
+        let d: boolean = a == 5 and b == 10 and c == 6 and true;
+        if false or false or d {
+            return a * 100 + b * 10 + c;
+        }
+        return 0;
+    }";
+
+    assert_eq!(Ok(Value::Integer(606)), run(src));
+}
+
+#[test]
+fn modulo_and_comparison_test() {
+    let src = "\
+    fn main(): int {
+        let count: int = 0;
+        for i: int in 0..30 {
+            if i % 3 == 0 && i % 5 != 0 || i >= 28 {
+                count = count + 1;
+            }
+        }
+        if 7.5 % 2.0 <= 1.5 && -7 % 3 == -1 {
+            return count;
+        }
+        return 0;
+    }";
+    // i in {3,6,9,12,18,21,24,27} plus 28 and 29
+    assert_eq!(Ok(Value::Integer(10)), run(src));
+
+    let by_zero = "fn main(): int { return 5 % 0; }";
+    assert_eq!(Err(RuntimeError::DivisionByZero), run(by_zero));
+}
+
+#[test]
+fn for_loop_errors_test() {
+    let zero_step = "fn main() { for i: int in 0..10 step 0 {} }";
+    assert_eq!(Err(RuntimeError::ZeroStep), run(zero_step));
+
+    let float_bound = "fn main() { for i: int in 0..2.5 {} }";
+    assert_eq!(Err(RuntimeError::TypeMismatch(DataType::Integer, "float")), run(float_bound));
+
+    let float_variable = "fn main() { for x: float in 0..2 {} }";
+    assert_eq!(Err(RuntimeError::TypeMismatch(DataType::Float, "int")), run(float_variable));
+}
+
+#[test]
+fn globals_test() {
+    let src = "\
+    const BASE: int = 10;
+    let total: int = BASE * 2;
+    let label: string = describe();
+
+    fn describe(): string {
+        return \"total\";
+    }
+
+    fn add(n: int) {
+        total = total + n;
+    }
+
+    fn main(): int {
+        add(BASE);
+        add(5);
+        if label == \"total\" {
+            return total;
+        }
+        return 0;
+    }";
+
+    assert_eq!(Ok(Value::Integer(35)), run(src));
+}
+
+#[test]
+fn uninitialized_global_test() {
+    let src = "\
+    let first: int = second();
+    let value: int = 1;
+    fn second(): int { return value; }
+    fn main() {}";
+
+    assert_eq!(Err(RuntimeError::UninitializedGlobal("value".into())), run(src));
+
+    let assigned = "\
+    let first: int = set();
+    let value: int = 1;
+    fn set(): int { value = 2; return 0; }
+    fn main() {}";
+    assert_eq!(Err(RuntimeError::UninitializedGlobal("value".into())), run(assigned));
+}
+
+#[test]
+fn global_initializer_locals_test() {
+    let src = "\
+    let g: int = { let y: int = 2; y * 3 };
+    let h: int = { let a: int = g; let b: int = a + 1; a * b };
+    fn main(): int { return g + h; }";
+    assert_eq!(Ok(Value::Integer(6 + 42)), run(src));
+}
+
+#[test]
+fn shadowing_test() {
+    let src = "\
+    fn count(n: int): int {
+        let n: int = n * 2;
+        let total: int = 0;
+        while n > 0 {
+            let inc: int = 1;
+            if n > 5 {
+                let inc: int = 2;
+                total = total + inc;
+            }
+            total = total + inc;
+            n = n - 1;
+        }
+        return total;
+    }
+
+    fn main(): int {
+        return count(4);
+    }";
+
+    assert_eq!(Ok(Value::Integer(14)), run(src));
+}
+
+#[test]
+fn arrays_test() {
+    let src = "\
+    fn sum(values: [int]): int {
+        let total: int = 0;
+        for i: int in 0..values.len() {
+            total = total + values[i];
+        }
+        return total;
+    }
+
+    fn main(): int {
+        let numbers: [int] = [1, 2, 3];
+        numbers.push(4);
+        numbers[0] = 10;
+        let alias: [int] = numbers;
+        alias.push(5);
+        let last: int = numbers.pop();
+        let grid: [[int]] = [[1], []];
+        grid[1].push(7);
+        return sum(numbers) * 100 + last * 10 + grid[1][0];
+    }";
+
+    assert_eq!(Ok(Value::Integer(1957)), run(src));
+}
+
+#[test]
+fn array_errors_test() {
+    let out_of_bounds = "fn main(): int { let a: [int] = [1, 2]; return a[2]; }";
+    assert_eq!(Err(RuntimeError::IndexOutOfBounds(2, 2)), run(out_of_bounds));
+
+    let negative = "fn main(): int { let a: [int] = [1, 2]; return a[-1]; }";
+    assert_eq!(Err(RuntimeError::IndexOutOfBounds(-1, 2)), run(negative));
+
+    let store = "fn main() { let a: [int] = [1, 2]; a[5] = 3; }";
+    assert_eq!(Err(RuntimeError::IndexOutOfBounds(5, 2)), run(store));
+
+    let empty = "fn main() { let a: [int] = []; a.pop(); }";
+    assert_eq!(Err(RuntimeError::PopFromEmptyArray), run(empty));
+
+    let mixed = "fn main() { let a: [int] = [1, 2.5]; }";
+    assert_eq!(Err(RuntimeError::ArrayElementMismatch("int", "float")), run(mixed));
+
+    let not_array = "fn main(): int { let a: int = 1; return a[0]; }";
+    assert_eq!(Err(RuntimeError::NotIndexable("int")), run(not_array));
+}
+
+#[test]
+fn structs_test() {
+    let src = "\
+    struct Point { x: int, y: int }
+    struct Line { from: Point, to: Point }
+
+    fn move(p: Point, dx: int) {
+        p.x = p.x + dx;
+    }
+
+    fn main(): int {
+        let origin: Point = Point { y: 0, x: 0 };
+        let line: Line = Line { from: origin, to: Point { x: 3, y: 4 } };
+        move(origin, 2);
+        line.to.y = line.to.y * 10;
+        return line.from.x * 100 + line.to.y;
+    }";
+
+    assert_eq!(Ok(Value::Integer(240)), run(src));
+}
+
+#[test]
+fn methods_test() {
+    let src = "\
+    struct Counter { count: int, stride: int }
+
+    impl Counter {
+        fn new(stride: int): Counter {
+            return Counter { count: 0, stride: stride };
+        }
+
+        fn advance(self, times: int) {
+            for i: int in 0..times {
+                self.count = self.count + self.stride;
+            }
+        }
+
+        fn value(self): int {
+            return self.count;
+        }
+    }
+
+    fn main(): int {
+        let c: Counter = Counter::new(3);
+        c.advance(2);
+        Counter::advance(c, 1);
+        return c.value() * 10 + Counter::new(1).value();
+    }";
+
+    assert_eq!(Ok(Value::Integer(90)), run(src));
+}
+
+#[test]
+fn struct_errors_test() {
+    let wrong_field = "struct P { x: int } fn main() { let p: P = P { x: 1.5 }; }";
+    assert_eq!(Err(RuntimeError::TypeMismatch(DataType::Integer, "float")), run(wrong_field));
+
+    let missing = "struct P { x: int, y: int } fn main() { let p: P = P { x: 1 }; }";
+    assert_eq!(Err(RuntimeError::MissingField("P".into(), "y".into())), run(missing));
+
+    let unknown = "struct P { x: int } fn main(): int { let p: P = P { x: 1 }; return p.y; }";
+    assert_eq!(Err(RuntimeError::UndefinedField("P".into(), "y".into())), run(unknown));
+
+    let undefined = "fn main() { let p: int = Q { x: 1 }; }";
+    assert_eq!(Err(RuntimeError::UndefinedType("Q".into())), run(undefined));
+
+    let not_struct = "fn main() { let n: int = 1; n.x = 2; }";
+    assert_eq!(Err(RuntimeError::UndefinedField("int".into(), "x".into())), run(not_struct));
+
+    let no_method = "struct P { x: int } impl P { fn new(): P { return P { x: 1 }; } } fn main() { P::new().new(); }";
+    assert_eq!(Err(RuntimeError::UndefinedMethod("struct", "new".into())), run(no_method));
+
+    let arguments = "struct P { x: int } impl P { fn get(self): int { return self.x; } } fn main(): int { return P { x: 1 }.get(2); }";
+    assert_eq!(Err(RuntimeError::WrongArgumentCount("P::get".into(), 1, 2)), run(arguments));
+}
+
+#[test]
+fn enum_match_test() {
+    let src = "\
+    enum Shape { Circle(float), Rect(float, float), Empty }
+    enum Pair { Of(Shape, int) }
+
+    impl Shape {
+        fn area(self): float {
+            return match self {
+                Shape::Circle(r) => 3.0 * r * r,
+                Shape::Rect(w, h) => w * h,
+                Shape::Empty => 0.0,
+            };
+        }
+    }
+
+    fn describe(p: Pair): int {
+        return match p {
+            Pair::Of(Shape::Rect(_, h), 2) => 2000,
+            Pair::Of(Shape::Circle(_), n) => n * 100,
+            Pair::Of(_, -1) => 10,
+            _ => 1,
+        };
+    }
+
+    fn main(): float {
+        let shapes: [Shape] = [Shape::Circle(1.0), Shape::Rect(2.0, 3.0), Shape::Empty];
+        let total: float = 0.0;
+        for i: int in 0..shapes.len() {
+            total = total + shapes[i].area();
+        }
+        let code: int = describe(Pair::Of(Shape::Rect(1.0, 1.0), 2)) + describe(Pair::Of(Shape::Circle(2.0), 3))
+            + describe(Pair::Of(Shape::Empty, -1)) + describe(Pair::Of(Shape::Empty, 4));
+        match code {
+            2311 => {
+                total = total * 10.0;
+            }
+            _ => {}
+        }
+        let word: string = match \"b\" { \"a\" => \"first\", other => other + \"!\" };
+        if word == \"b!\" && match total > 0.0 { true => true, false => false } {
+            return total;
+        }
+        return 0.0;
+    }";
+
+    assert_eq!(Ok(Value::Float(90.0)), run(src));
+
+    let loop_src = "\
+    enum Step { Stop, Skip, Add(int) }
+    fn main(): int {
+        let steps: [Step] = [Step::Add(1), Step::Skip, Step::Add(10), Step::Stop, Step::Add(100)];
+        let total: int = 0;
+        for i: int in 0..steps.len() {
+            match steps[i] {
+                Step::Stop => { break; }
+                Step::Skip => { continue; }
+                Step::Add(n) => { total = total + n; }
+            }
+        }
+        return total;
+    }";
+    assert_eq!(Ok(Value::Integer(11)), run(loop_src));
+}
+
+#[test]
+fn match_errors_test() {
+    let no_arm = "fn main(): int { return match 3 { 1 => 1, 2 => 2 }; }";
+    assert_eq!(Err(RuntimeError::NoMatchingArm), run(no_arm));
+
+    let undefined = "enum E { A } fn main() { let e: E = E::B; }";
+    assert_eq!(Err(RuntimeError::UndefinedVariant("E".into(), "B".into())), run(undefined));
+
+    let fields = "enum E { A(int) } fn main() { let e: E = E::A(1, 2); }";
+    assert_eq!(Err(RuntimeError::WrongArgumentCount("E::A".into(), 1, 2)), run(fields));
+
+    let field_type = "enum E { A(int) } fn main() { let e: E = E::A(true); }";
+    assert_eq!(Err(RuntimeError::TypeMismatch(DataType::Integer, "boolean")), run(field_type));
+
+    let literal = "fn main(): int { return match 1.5 { 1 => 1, _ => 2 }; }";
+    assert_eq!(Err(RuntimeError::TypeMismatch(DataType::Integer, "float")), run(literal));
+
+    let other_enum = "enum E { A } enum F { B } fn main(): int { return match E::A { F::B => 1, _ => 2 }; }";
+    assert_eq!(Err(RuntimeError::TypeMismatch(DataType::Named("F".into()), "enum")), run(other_enum));
+
+    let pattern_fields = "enum E { A(int) } fn main(): int { return match E::A(1) { E::A(x, y) => x, _ => 2 }; }";
+    assert_eq!(Err(RuntimeError::WrongArgumentCount("E::A".into(), 1, 2)), run(pattern_fields));
+
+    let no_method = "enum E { A } fn main() { E::A.size(); }";
+    assert_eq!(Err(RuntimeError::UndefinedMethod("enum", "size".into())), run(no_method));
+
+    let compare = "enum E { A } fn main(): boolean { return E::A == E::A; }";
+    assert_eq!(Err(RuntimeError::InvalidOperands(BinOp::Eq, "enum", "enum")), run(compare));
+}
+
+#[test]
+fn global_match_test() {
+    let src = "\
+    enum P { Of(int, int) }
+    let g: int = 100 + match P::Of(3, 4) { P::Of(a, b) => a * 10 + b };
+    fn main(): int { return g; }";
+    assert_eq!(Ok(Value::Integer(134)), run(src));
+}
+
+#[test]
+fn closures_test() {
+    let src = "\
+    fn twice(f: fn(int): int, x: int): int {
+        return f(f(x));
+    }
+
+    fn adder(n: int): fn(int): int {
+        return fn(x: int): int { return x + n; };
+    }
+
+    fn square(x: int): int {
+        return x * x;
+    }
+
+    fn main(): int {
+        let add5: fn(int): int = adder(5);
+        let offset: int = 100;
+        let nested: fn(int): fn(): int = fn(a: int): fn(): int {
+            return fn(): int { return a + offset; };
+        };
+        offset = 0;
+        let ops: [fn(int): int] = [add5, square, adder(-1)];
+        let total: int = 0;
+        for i: int in 0..ops.len() {
+            total = total + ops[i](3);
+        }
+        return twice(add5, 1) + twice(square, 2) + nested(1)() + total;
+    }";
+    assert_eq!(Ok(Value::Integer(11 + 16 + 101 + 19)), run(src));
+
+    let recursive = "\
+    fn apply(f: fn(int): int, n: int): int { return f(n); }
+    fn fib(n: int): int { if n < 2 { return n; } return apply(fib, n - 1) + apply(fib, n - 2); }
+    fn main(): int { return fib(10); }";
+    assert_eq!(Ok(Value::Integer(55)), run(recursive));
+}
+
+#[test]
+fn closure_errors_test() {
+    let not_callable = "fn main(): int { let x: int = 1; return x(2); }";
+    assert_eq!(Err(RuntimeError::NotCallable("int")), run(not_callable));
+
+    let arguments = "fn main(): int { let f: fn(int): int = fn(x: int): int { return x; }; return f(1, 2); }";
+    assert_eq!(Err(RuntimeError::WrongArgumentCount(LAMBDA.into(), 1, 2)), run(arguments));
+
+    let missing_return = "fn main(): int { return fn(): int {}(); }";
+    assert_eq!(Err(RuntimeError::MissingReturnValue(LAMBDA.into())), run(missing_return));
+
+    let compare = "fn f() {} fn main(): boolean { return f == f; }";
+    assert_eq!(Err(RuntimeError::InvalidOperands(BinOp::Eq, "fn", "fn")), run(compare));
+}
+
+#[test]
+fn lambda_block_value_test() {
+    let src = "\
+    fn make(factor: int): fn(int): int {
+        fn(x: int): int { return x * factor; }
+    }
+    fn main(): int {
+        let add: fn(int): int = { fn(x: int): int { x + 1 } };
+        return add(make(2)(4));
+    }";
+    assert_eq!(Ok(Value::Integer(9)), run(src));
+}
+
+#[test]
+fn global_lambda_test() {
+    let src = "\
+    let f: fn(int): int = fn(x: int): int { return x + 1; };
+    fn main(): int { return f(1); }";
+    assert_eq!(Ok(Value::Integer(2)), run(src));
+}
+
+#[test]
+fn block_values_test() {
+    let src = "\
+    fn sign(x: int): int {
+        if x < 0 { -1 } else { if x == 0 { 0 } else { 1 } }
+    }
+
+    fn firstSquareAbove(limit: int): int {
+        let i: int = 0;
+        loop {
+            i = i + 1;
+            if i * i > limit {
+                break i * i;
+            }
+        }
+    }
+
+    fn main(): int {
+        let a: int = if sign(-5) < 0 { 10 } else { 20 };
+        let b: int = {
+            let c: int = 3;
+            c * c
+        };
+        let d: int = match sign(0) { 0 => { 1 + 1 }, _ => 0 };
+        let e: int = if a > 0 { 4 } else { return 0; };
+        for i: int in 0..3 {
+            if i == 1 { continue; }
+            { i; }
+        }
+        a + b + d + e + firstSquareAbove(20) + sign(7)
+    }";
+    assert_eq!(Ok(Value::Integer(10 + 9 + 2 + 4 + 25 + 1)), run(src));
+
+    // the value of the body is discarded if the function has no return type
+    let discarded = "fn f() { 5 } fn main(): int { f(); let x: int = loop { break 2; }; x }";
+    assert_eq!(Ok(Value::Integer(2)), run(discarded));
+}
+
+#[test]
+fn block_value_errors_test() {
+    let mismatch = "fn main(): int { let x: int = if false { 1 } else { \"a\" }; return x; }";
+    assert_eq!(Err(RuntimeError::TypeMismatch(DataType::Integer, "string")), run(mismatch));
+
+    let missing = "fn main(): int { if true { 1 }; }";
+    assert_eq!(Err(RuntimeError::MissingReturnValue("main".into())), run(missing));
+
+    let outside = "fn main() { break 1; }";
+    assert_eq!(Err(RuntimeError::BreakOutsideLoop), run(outside));
+}
+
+#[test]
+fn global_block_values_test() {
+    let src = "\
+    let sum: int = {
+        let total: int = 0;
+        for i: int in 1..5 {
+            total = total + i;
+        }
+        total
+    };
+    let sign: int = if sum > 5 { 1 } else { -1 };
+    let first: int = loop { break sum * 2; };
+    fn main(): int { return sum * 100 + sign * 10 + first; }";
+    assert_eq!(Ok(Value::Integer(10 * 100 + 10 + 20)), run(src));
+}
+
+#[test]
+fn standard_library_test() {
+    let src = "\
+    fn main(): int {
+        let words: [string] = split(\"  paper castle  \", \" \");
+        let longest: int = 0;
+        for i: int in 0..words.len() {
+            longest = max(longest, length(trim(words[i])));
+        }
+        let root: int = float_to_int(sqrt(int_to_float(pow(3, 4))));
+        let parsed: int = parse_int(substring(concat(\"x\", int_to_string(42)), 1, 3));
+        assert(parse_boolean(boolean_to_string(root == 9)), \"root is not 9\");
+        longest + root + parsed + abs(min(-1, 1))
+    }";
+    assert_eq!(Ok(Value::Integer(6 + 9 + 42 + 1)), run(src));
+
+    let floats = "fn main(): float { abs(-1.5) + min(1.0, 2.0) + max(1.0, 2.0) + pow(16.0, 0.5) }";
+    assert_eq!(Ok(Value::Float(1.5 + 1.0 + 2.0 + 4.0)), run(floats));
+
+    // a declared function hides the function of the standard library
+    let hidden = "fn abs(x: int): int { x } fn main(): int { abs(-1) }";
+    assert_eq!(Ok(Value::Integer(-1)), run(hidden));
+
+    let failed = "fn main() { assert(1 > 2, \"wrong order\"); }";
+    assert_eq!(Err(RuntimeError::AssertionFailed("wrong order".into())), run(failed));
+
+    let exit = "fn main(): int { exit(4); return 0; }";
+    assert_eq!(Err(RuntimeError::Exit(4)), run(exit));
+}
+
+#[test]
+fn interpolation_test() {
+    let src = "\
+    fn main(): string {
+        let x: int = 41;
+        let name: string = \"castle\";
+        \"x = {x + 1}, f = {1.5 * 2.0}, b = {x > 1}, {\"paper {name}\"} \\{}\"
+    }";
+    assert_eq!(Ok(Value::String("x = 42, f = 3.0, b = true, paper castle {}".into())), run(src));
+
+    let array = "fn main(): string { \"{[1]}\" }";
+    assert_eq!(Err(RuntimeError::NotInterpolatable("array")), run(array));
+
+    let void = "fn nothing() {} fn main(): string { \"{nothing()}\" }";
+    assert_eq!(Err(RuntimeError::NotInterpolatable("void")), run(void));
+}
+
+#[test]
+fn number_literals_test() {
+    let src = "fn main(): int { 0xFF + 0b1010 + 0o17 + 1_000 + float_to_int(2.5e2) }";
+    assert_eq!(Ok(Value::Integer(255 + 10 + 15 + 1000 + 250)), run(src));
+
+    let pattern = "fn main(): int { match -0x10 { -16 => 1, _ => 0 } }";
+    assert_eq!(Ok(Value::Integer(1)), run(pattern));
+
+    let smallest = "fn main(): int { let x: int = -9223372036854775808; x + 1 }";
+    assert_eq!(Ok(Value::Integer(i64::MIN + 1)), run(smallest));
+}
+
+/// runs the program with the RuntimeInterpreter and the vm, both have to return the same result
+fn run(src: &str) -> Result<Value,RuntimeError> {
+    let ast = ASTParser::new(Lexer::new(src)).parse().expect("expected abstract syntax tree");
+    let compiled = bytecode::compile(&ast).and_then(|program| vm::run(&program));
+    let interpreted = RuntimeInterpreter::new(ast).start();
+    assert_eq!(interpreted, compiled, "the vm returned a different result");
+    interpreted
+}
@@ -1,2 +1,11 @@
 pub mod tokenizer;
-pub mod ast_parser_test;
\ No newline at end of file
+pub mod ast_parser_test;
+pub mod interpreter_test;
+pub mod diagnostics_test;
+pub mod type_checker_test;
+pub mod resolver_test;
+pub mod vm_test;
+pub mod cli_test;
+pub mod printer_test;
+pub mod repl_test;
+pub mod stdlib_test;
@@ -0,0 +1,132 @@
+use crate::frontend::lexer::Lexer;
+use crate::frontend::parser::ast_parser::ASTParser;
+use crate::frontend::syntax::ast::AbstractSyntaxTree;
+use crate::frontend::syntax::printer::print;
+
+#[test]
+fn print_program_test() {
+    let src = "\
+fn fibonacci(n: int): int {
+    if n == 1 or n == 2 {
+        return 1;
+    } else {
+        let x: float = -(1.5 + 2.0) * 3.0;
+    }
+    return fibonacci(n - 1) + fibonacci(n - 2);
+}
+
+fn main() {
+    let name: string = \"castle\";
+    for i: int in 0..10 step 2 {
+        while !done {
+            continue;
+        }
+    }
+    loop {
+        break;
+    }
+    print(name, true);
+    return;
+}
+";
+    assert_eq!(src, print(&parse(src)));
+}
+
+#[test]
+fn parenthesis_test() {
+    let src = "fn main() { x = (a + b) * (c - (d - e)) - f - g / (h % i) == (j < k); y = a or (b and c) and (d or e); }";
+    let printed = print(&parse(src));
+    assert!(printed.contains("x = (a + b) * (c - (d - e)) - f - g / (h % i) == j < k;"), "{}", printed);
+    assert!(printed.contains("y = a or b and c and (d or e);"), "{}", printed);
+    assert_eq!(parse(src), parse(&printed));
+}
+
+#[test]
+fn arrays_test() {
+    let src = "fn main() { let a: [[int]] = [[1, 2], []]; a[0][1] = (a[1] = x)[0] + -a.len(); x = (b + c).push([]); }";
+    let printed = print(&parse(src));
+    assert!(printed.contains("let a: [[int]] = [[1, 2], []];"), "{}", printed);
+    assert!(printed.contains("a[0][1] = (a[1] = x)[0] + -a.len();"), "{}", printed);
+    assert!(printed.contains("x = (b + c).push([]);"), "{}", printed);
+    assert_eq!(parse(src), parse(&printed));
+}
+
+#[test]
+fn structs_test() {
+    let src = "struct Line { from: Point, to: Point } fn main() { let l: Line = Line { from: a, to: Point { x: 1, y: 2 } }; l.to.x = -(l.from = b).y; }";
+    let printed = print(&parse(src));
+    assert!(printed.starts_with("struct Line {\n    from: Point,\n    to: Point,\n}\n"), "{}", printed);
+    assert!(printed.contains("let l: Line = Line { from: a, to: Point { x: 1, y: 2 } };"), "{}", printed);
+    assert!(printed.contains("l.to.x = -(l.from = b).y;"), "{}", printed);
+    assert_eq!(parse(src), parse(&printed));
+}
+
+#[test]
+fn impl_test() {
+    let src = "impl Point { fn new(x: int): Point { return Point { x: x }; } fn get(self): int { return self.x; } } fn main() { Point::new(1).get(); }";
+    let printed = print(&parse(src));
+    assert!(printed.starts_with("impl Point {\n    fn new(x: int): Point {\n"), "{}", printed);
+    assert!(printed.contains("    }\n\n    fn get(self): int {\n        return self.x;\n    }\n}\n"), "{}", printed);
+    assert!(printed.contains("Point::new(1).get();"), "{}", printed);
+    assert_eq!(parse(src), parse(&printed));
+}
+
+#[test]
+fn enum_match_test() {
+    let src = "enum Shape { Circle(float), Rect(float, float), Empty } fn main() { match s { Shape::Rect(w, _) => w, -1 => { return; } _ => 0.0 } }";
+    let printed = print(&parse(src));
+    assert!(printed.starts_with("enum Shape {\n    Circle(float),\n    Rect(float, float),\n    Empty,\n}\n"), "{}", printed);
+    assert!(printed.contains("    match s {\n        Shape::Rect(w, _) => w,\n        -1 => {\n            return;\n        }\n        _ => 0.0,\n    }\n"), "{}", printed);
+    assert_eq!(parse(src), parse(&printed));
+}
+
+#[test]
+fn lambda_test() {
+    let src = "fn main() { let f: fn(int): fn() = fn(x: int): fn() { return fn() {}; }; f(1)(); let y: int = (fn(): int { return 1; })(); }";
+    let printed = print(&parse(src));
+    assert!(printed.contains("    let f: fn(int): fn() = fn(x: int): fn() {\n        return fn() {"), "{}", printed);
+    assert!(printed.contains("    f(1)();\n"), "{}", printed);
+    assert!(printed.contains("let y: int = (fn(): int {"), "{}", printed);
+    assert_eq!(parse(src), parse(&printed));
+}
+
+#[test]
+fn block_values_test() {
+    let src = "fn main(): int { let x: int = if true { 1 } else { 2 }; loop { break x; } if x > 1 { x = 2; } { x } }";
+    let printed = print(&parse(src));
+    assert!(printed.contains("let x: int = if true {\n        1\n    } else {\n        2\n    };"), "{}", printed);
+    assert!(printed.contains("        break x;\n    }\n    if x > 1 {"), "{}", printed);
+    assert!(printed.ends_with("    {\n        x\n    }\n}\n"), "{}", printed);
+    assert_eq!(parse(src), parse(&printed));
+}
+
+#[test]
+fn string_escapes_test() {
+    let src = r#"fn main() { let s: string = "a\n\t\"b\"\\ \u{1}"; let r: string = r"C:\dir"; }"#;
+    let printed = print(&parse(src));
+    assert!(printed.contains(r#"let s: string = "a\n\t\"b\"\\ \u{1}";"#), "{}", printed);
+    assert!(printed.contains(r#"let r: string = "C:\\dir";"#), "{}", printed);
+    assert_eq!(parse(src), parse(&printed));
+}
+
+#[test]
+fn interpolation_test() {
+    let src = r#"fn main() { let s: string = "a\{ {x + 1}\n{"b {y}"}{f(1).len()}"; }"#;
+    let printed = print(&parse(src));
+    assert!(printed.contains(r#"let s: string = "a\{ {x + 1}\n{"b {y}"}{f(1).len()}";"#), "{}", printed);
+    assert_eq!(parse(src), parse(&printed));
+}
+
+#[test]
+fn number_literals_test() {
+    let src = "fn main() { let f: float = 1_0.5e2 + 1e-9; let x: int = 0xFF; match x { -0b1 => {} _ => {} } }";
+    let printed = print(&parse(src));
+    assert!(printed.contains("let f: float = 1050.0 + 1e-9;"), "{}", printed);
+    assert!(printed.contains("let x: int = 255;"), "{}", printed);
+    assert!(printed.contains("-1 => {"), "{}", printed);
+    assert_eq!(parse(src), parse(&printed));
+}
+
+fn parse(src: &str) -> AbstractSyntaxTree {
+    ASTParser::new(Lexer::new(src)).parse().expect("expected abstract syntax tree")
+}
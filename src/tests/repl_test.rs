@@ -0,0 +1,130 @@
+use crate::frontend::syntax::DataType;
+use crate::frontend::semantic::type_checker::Type;
+use crate::backend::interpreter::RuntimeError;
+use crate::backend::value::Value;
+use crate::repl::{is_incomplete, InputError, Session};
+
+#[test]
+fn session_test() {
+    let mut session = Session::new();
+    assert_eq!(Value::Integer(3), evaluate(&mut session, "1 + 2"));
+    assert_eq!(Value::Void, evaluate(&mut session, "fn square(n: int): int { return n * n; }"));
+    assert_eq!(Value::Void, evaluate(&mut session, "let x: int = square(4);"));
+    assert_eq!(Value::Void, evaluate(&mut session, "x = x + 1"));
+    assert_eq!(Value::Integer(17), evaluate(&mut session, "x"));
+    assert_eq!(Value::Void, evaluate(&mut session, "for i: int in 0..3 { x = x + i; }"));
+    assert_eq!(Value::Integer(20), evaluate(&mut session, "x;"));
+
+    // the variables of the session are globals, so functions can use them
+    assert_eq!(Value::Void, evaluate(&mut session, "fn f(): int { return x; }"));
+    assert_eq!(Value::Integer(20), evaluate(&mut session, "f()"));
+    // a function declared again replaces the earlier function
+    evaluate(&mut session, "fn square(n: int): int { return n * n * n; }");
+    assert_eq!(Value::Integer(8), evaluate(&mut session, "square(2)"));
+
+    assert_eq!(Ok(Type::Data(DataType::Boolean)), session.type_of("x > 2").map_err(|_| ()));
+    assert!(session.type_of("let y: int = 1;").is_err());
+}
+
+#[test]
+fn session_globals_test() {
+    let mut session = Session::new();
+    evaluate(&mut session, "let b: int = 2;");
+    evaluate(&mut session, "const C: int = 3;");
+    evaluate(&mut session, "fn g(): int { return b * C; }");
+    assert_eq!(Value::Integer(6), evaluate(&mut session, "g()"));
+    evaluate(&mut session, "fn set(value: int) { b = value; }");
+    evaluate(&mut session, "set(5)");
+    assert_eq!(Value::Integer(15), evaluate(&mut session, "g()"));
+    assert!(matches!(session.evaluate("fn h() { C = 1; }"), Err(InputError::Invalid(_))));
+}
+
+#[test]
+fn failed_input_test() {
+    let mut session = Session::new();
+    evaluate(&mut session, "let x: int = 5;");
+
+    // the declaration of y fails, so y is not declared afterwards
+    assert!(matches!(session.evaluate("let y: int = x / 0;"), Err(InputError::Runtime(RuntimeError::DivisionByZero))));
+    assert!(matches!(session.evaluate("y"), Err(InputError::Invalid(_))));
+    assert!(matches!(session.evaluate("let y: int = true;"), Err(InputError::Invalid(_))));
+    assert!(matches!(session.evaluate("y"), Err(InputError::Invalid(_))));
+    assert!(matches!(session.evaluate("x = ;"), Err(InputError::Invalid(_))));
+    assert!(matches!(session.evaluate("break;"), Err(InputError::Invalid(_))));
+
+    evaluate(&mut session, "let y: int = x * 2;");
+    assert_eq!(Value::Integer(15), evaluate(&mut session, "x + y"));
+}
+
+#[test]
+fn structs_test() {
+    let mut session = Session::new();
+    evaluate(&mut session, "struct Point { x: int, y: int }");
+    evaluate(&mut session, "let p: Point = Point { x: 1, y: 2 };");
+    evaluate(&mut session, "p.x = p.y * 10");
+    assert_eq!(Value::Integer(22), evaluate(&mut session, "p.x + p.y"));
+    assert_eq!("Point { x: 20, y: 2 }", evaluate(&mut session, "p").to_string());
+    assert_eq!(Ok(Type::Data(DataType::Named("Point".into()))), session.type_of("p").map_err(|_| ()));
+    assert!(session.type_of("struct Q { x: int }").is_err());
+}
+
+#[test]
+fn impl_test() {
+    let mut session = Session::new();
+    evaluate(&mut session, "struct Point { x: int, y: int }");
+    evaluate(&mut session, "impl Point { fn origin(): Point { return Point { x: 0, y: 0 }; } }");
+    evaluate(&mut session, "let p: Point = Point::origin();");
+    evaluate(&mut session, "impl Point { fn sum(self): int { return self.x + self.y; } }");
+    evaluate(&mut session, "p.y = 4");
+    assert_eq!(Value::Integer(4), evaluate(&mut session, "p.sum()"));
+    assert!(session.type_of("p.length()").is_err());
+}
+
+#[test]
+fn enum_test() {
+    let mut session = Session::new();
+    evaluate(&mut session, "enum Light { Red, Green, Blink(int) }");
+    evaluate(&mut session, "let l: Light = Light::Blink(3);");
+    assert_eq!("Light::Blink(3)", evaluate(&mut session, "l").to_string());
+    let next = "match l { Light::Red => Light::Green, Light::Green => Light::Red, Light::Blink(n) => Light::Blink(n - 1) }";
+    assert_eq!("Light::Blink(2)", evaluate(&mut session, next).to_string());
+    assert_eq!(Ok(Type::Data(DataType::Named("Light".into()))), session.type_of(next).map_err(|_| ()));
+    assert!(session.type_of("match l { Light::Red => 1 }").is_err());
+}
+
+#[test]
+fn lambda_test() {
+    let mut session = Session::new();
+    evaluate(&mut session, "let stride: int = 2;");
+    evaluate(&mut session, "let next: fn(int): int = fn(x: int): int { return x + stride; };");
+    assert_eq!(Value::Integer(5), evaluate(&mut session, "next(3)"));
+    assert_eq!(Value::Integer(7), evaluate(&mut session, "fn(f: fn(int): int): int { return f(f(3)); }(next)"));
+    assert_eq!("fn <lambda>(int): int", evaluate(&mut session, "next").to_string());
+    let fn_type = DataType::Function(vec![DataType::Integer], Some(Box::new(DataType::Integer)));
+    assert_eq!(Ok(Type::Data(fn_type)), session.type_of("next").map_err(|_| ()));
+}
+
+#[test]
+fn block_value_test() {
+    let mut session = Session::new();
+    evaluate(&mut session, "let limit: int = 3;");
+    assert_eq!(Value::Integer(1), evaluate(&mut session, "if limit > 2 { 1 } else { 2 }"));
+    evaluate(&mut session, "let i: int = 0;");
+    assert_eq!(Value::Integer(4), evaluate(&mut session, "loop { i = i + 1; if i > limit { break i; } }"));
+}
+
+#[test]
+fn incomplete_input_test() {
+    assert!(is_incomplete("fn main() {\n"));
+    assert!(is_incomplete("if a {\n x = f(1,\n"));
+    assert!(is_incomplete("let s: string = \"first line\n"));
+    assert!(is_incomplete("let s: string = \"{f(\n"));
+    assert!(!is_incomplete("fn main() {\n}\n"));
+    assert!(is_incomplete("let a: [int] = [1,\n"));
+    assert!(!is_incomplete("x = 1 @ 2"));
+    assert!(!is_incomplete(")"));
+}
+
+fn evaluate(session: &mut Session, src: &str) -> Value {
+    session.evaluate(src).unwrap_or_else(|e| panic!("{} failed: {:?}", src, e))
+}
@@ -0,0 +1,247 @@
+use crate::frontend::lexer::Lexer;
+use crate::frontend::parser::ast_parser::ASTParser;
+use crate::frontend::semantic::resolver::{self, Resolution, ResolveError, ResolveErrorKind, SymbolKind};
+use crate::frontend::syntax::ast::*;
+use crate::frontend::syntax::span::Span;
+
+#[test]
+fn resolve_variables_test() {
+    let src = "\
+    fn add(a: int, b: int): int {
+        let sum: int = a + b;
+        return sum;
+    }";
+
+    let (ast, resolution, errors) = resolve(src);
+    assert!(errors.is_empty());
+
+    let (body, args) = match &ast.nodes[0].kind {
+        StatementKind::Expression(Expression { kind: ExpressionKind::FnDecl(_, body, Some(args), _), .. }) => (body, args),
+        _ => panic!("expected function declaration"),
+    };
+    let (sum, initializer) = match &body.statements[0].kind {
+        StatementKind::Declaration(binding, expr) => (binding, expr),
+        _ => panic!("expected declaration"),
+    };
+    let (left, right) = match &initializer.kind {
+        ExpressionKind::BinaryOp(left, BinOp::Plus, right) => (left, right),
+        _ => panic!("expected addition"),
+    };
+    let returned = match &body.statements[1].kind {
+        StatementKind::Expression(Expression { kind: ExpressionKind::Return(Some(value)), .. }) => value,
+        _ => panic!("expected return"),
+    };
+
+    assert_eq!(resolution.symbol(args[0].id), resolution.symbol(left.id));
+    assert_eq!(resolution.symbol(args[1].id), resolution.symbol(right.id));
+    assert_eq!(resolution.symbol(sum.id), resolution.symbol(returned.id));
+    assert_ne!(resolution.symbol(args[0].id), resolution.symbol(args[1].id));
+    assert_eq!(vec![Some(0), Some(1), Some(2)], vec![
+        resolution.slot(args[0].id),
+        resolution.slot(args[1].id),
+        resolution.slot(sum.id),
+    ]);
+
+    let add = resolution.function("add").expect("expected function add");
+    assert_eq!("add", resolution.definition(add).name);
+    assert_eq!(SymbolKind::Function(3), resolution.definition(add).kind);
+}
+
+#[test]
+fn reuse_slots_test() {
+    let src = "\
+    fn main() {
+        let a: int = 1;
+        if true {
+            let b: int = 2;
+            let c: int = 3;
+        }
+        while false {
+            let d: int = 4;
+        }
+        let e: int = 5;
+    }";
+
+    let (_, resolution, errors) = resolve(src);
+    assert!(errors.is_empty());
+    let main = resolution.function("main").expect("expected main function");
+    assert_eq!(SymbolKind::Function(3), resolution.definition(main).kind);
+}
+
+#[test]
+fn resolve_errors_test() {
+    let src = "\
+    fn main() {
+        let x: int = y;
+        x = missing();
+        z = 1;
+        let x: int = 2;
+        if true {
+            let x: int = 3;
+        }
+    }
+
+    fn main() {}";
+
+    let (_, _, errors) = resolve(src);
+    assert_eq!(vec![
+        ResolveErrorKind::DuplicateFunction("main".into(), Span::new(0, 159, 1, 1)),
+        ResolveErrorKind::UndefinedVariable("y".into()),
+        ResolveErrorKind::UndefinedFunction("missing".into()),
+        ResolveErrorKind::UndefinedVariable("z".into()),
+        ResolveErrorKind::ShadowedVariable("x".into(), Span::new(24, 30, 2, 13)),
+        ResolveErrorKind::ShadowedVariable("x".into(), Span::new(86, 92, 5, 13)),
+    ], errors.into_iter().map(|e| e.kind).collect::<Vec<_>>());
+}
+
+#[test]
+fn standard_library_test() {
+    // the functions of the standard library are found without a symbol
+    let (_, _, errors) = resolve("fn main() { println(trim(read_line())); }");
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn unresolved_names_test() {
+    let src = "fn main() { let a: int = b; }";
+
+    let (ast, resolution, _) = resolve(src);
+    let initializer = match &ast.nodes[0].kind {
+        StatementKind::Expression(Expression { kind: ExpressionKind::FnDecl(_, body, _, _), .. }) => {
+            match &body.statements[0].kind {
+                StatementKind::Declaration(_, expr) => expr.clone(),
+                _ => panic!("expected declaration"),
+            }
+        },
+        _ => panic!("expected function declaration"),
+    };
+    assert_eq!(None, resolution.symbol(initializer.id));
+    assert_eq!(None, resolution.function("b"));
+}
+
+#[test]
+fn resolve_globals_test() {
+    let src = "\
+    let first: int = 1;
+    const SECOND: int = 2;
+    fn main() {
+        first = SECOND;
+    }";
+
+    let (ast, resolution, errors) = resolve(src);
+    assert!(errors.is_empty());
+    let bindings: Vec<&VariableBinding> = ast.nodes[..2].iter()
+        .map(|stmt| match &stmt.kind {
+            StatementKind::Declaration(binding, _) | StatementKind::Constant(binding, _) => binding,
+            _ => panic!("expected declaration"),
+        })
+        .collect();
+    let (target, value) = match &ast.nodes[2].kind {
+        StatementKind::Expression(Expression { kind: ExpressionKind::FnDecl(_, body, _, _), .. }) => match &body.statements[0].kind {
+            StatementKind::Expression(assignment @ Expression { kind: ExpressionKind::Assignment(_, value), .. }) => (assignment, value),
+            _ => panic!("expected assignment"),
+        },
+        _ => panic!("expected function declaration"),
+    };
+
+    assert_eq!(resolution.symbol(bindings[0].id), resolution.symbol(target.id));
+    assert_eq!(resolution.symbol(bindings[1].id), resolution.symbol(value.id));
+    assert_eq!((Some(0), Some(1)), (resolution.global(target.id), resolution.global(value.id)));
+    assert_eq!(None, resolution.slot(target.id));
+    assert_eq!(2, resolution.global_count());
+    let main = resolution.function("main").expect("expected main function");
+    assert_eq!(SymbolKind::Function(0), resolution.definition(main).kind);
+}
+
+#[test]
+fn resolve_global_errors_test() {
+    let src = "\
+    let a: int = b;
+    let b: int = 1;
+    const C: int = b + 1;
+    let b: int = 2;
+    fn main() {
+        C = 3;
+        const D: int = -C * 2;
+        const E: int = one();
+    }
+    fn one(): int { return 1; }";
+
+    let (_, _, errors) = resolve(src);
+    assert_eq!(vec![
+        ResolveErrorKind::UndefinedVariable("b".into()),
+        ResolveErrorKind::NonConstantValue,
+        ResolveErrorKind::DuplicateGlobal("b".into(), Span::new(24, 30, 2, 9)),
+        ResolveErrorKind::AssignmentToConstant("C".into(), Span::new(46, 52, 3, 11)),
+        ResolveErrorKind::NonConstantValue,
+    ], errors.into_iter().map(|e| e.kind).collect::<Vec<_>>());
+}
+
+#[test]
+fn duplicate_parameter_test() {
+    let src = "fn f(a: int, a: int) {} fn main() { let g: fn(int, int) = fn(b: int, b: int) {}; }";
+
+    let (_, _, errors) = resolve(src);
+    assert_eq!(vec![
+        ResolveError { kind: ResolveErrorKind::DuplicateParameter("a".into(), Span::new(5, 11, 1, 6)), span: Span::new(13, 19, 1, 14) },
+        ResolveError { kind: ResolveErrorKind::DuplicateParameter("b".into(), Span::new(61, 67, 1, 62)), span: Span::new(69, 75, 1, 70) },
+    ], errors);
+
+    // a parameter may have the name of a variable around a lambda, that is only a shadowed variable
+    let (_, _, errors) = resolve("fn main() { let a: int = 1; let f: fn(int) = fn(a: int) {}; }");
+    assert!(matches!(&errors[..], [ResolveError { kind: ResolveErrorKind::ShadowedVariable(..), .. }]), "{:?}", errors);
+}
+
+#[test]
+fn resolve_captures_test() {
+    let src = "\
+    fn main() {
+        let a: int = 1;
+        let f: fn(int): int = fn(x: int): int { return x + a; };
+        a = 2;
+    }";
+
+    let (ast, resolution, errors) = resolve(src);
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    let body = match &ast.nodes[0].kind {
+        StatementKind::Expression(Expression { kind: ExpressionKind::FnDecl(_, body, _, _), .. }) => body,
+        _ => panic!("expected function declaration"),
+    };
+    let a = match &body.statements[0].kind {
+        StatementKind::Declaration(binding, _) => binding,
+        _ => panic!("expected declaration"),
+    };
+    let (lambda, captured) = match &body.statements[1].kind {
+        StatementKind::Declaration(_, lambda @ Expression { kind: ExpressionKind::Lambda(_, _, lambda_body), .. }) => {
+            match &lambda_body.statements[0].kind {
+                StatementKind::Expression(Expression { kind: ExpressionKind::Return(Some(value)), .. }) => match &value.kind {
+                    ExpressionKind::BinaryOp(_, BinOp::Plus, right) => (lambda, right),
+                    _ => panic!("expected addition"),
+                },
+                _ => panic!("expected return"),
+            }
+        },
+        _ => panic!("expected lambda"),
+    };
+
+    // the lambda reads a copy of a which is stored behind its argument
+    let id = resolution.symbol(lambda.id).expect("expected lambda symbol");
+    let captures = resolution.captures(id);
+    assert_eq!(1, captures.len());
+    assert_eq!(resolution.symbol(a.id), Some(captures[0].outer));
+    assert_eq!(resolution.symbol(captured.id), Some(captures[0].inner));
+    assert_eq!(Some(1), resolution.slot(captured.id));
+    assert_eq!(SymbolKind::Function(2), resolution.definition(id).kind);
+
+    let src = "fn main() { let a: int = 1; let f: fn() = fn() { let g: fn() = fn() { a = 2; }; }; }";
+    let (_, _, errors) = resolve(src);
+    assert_eq!(vec![ResolveErrorKind::AssignmentToCapture("a".into(), Span::new(16, 22, 1, 17))],
+               errors.into_iter().map(|e| e.kind).collect::<Vec<_>>());
+}
+
+fn resolve(src: &str) -> (AbstractSyntaxTree, Resolution, Vec<ResolveError>) {
+    let ast = ASTParser::new(Lexer::new(src)).parse().expect("expected abstract syntax tree");
+    let (resolution, errors) = resolver::resolve(&ast);
+    (ast, resolution, errors)
+}
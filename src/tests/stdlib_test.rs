@@ -0,0 +1,88 @@
+use crate::frontend::syntax::DataType;
+use crate::frontend::semantic::natives::Native;
+use crate::backend::interpreter::RuntimeError;
+use crate::backend::value::Value;
+
+#[test]
+fn lookup_test() {
+    assert_eq!(Some(Native::Println), Native::lookup("println"));
+    assert_eq!(Some(Native::ParseInt), Native::lookup("parse_int"));
+    assert_eq!(None, Native::lookup("parseInt"));
+    assert_eq!(vec![(vec![DataType::String, DataType::Integer, DataType::Integer], Some(DataType::String))],
+               Native::Substring.signatures());
+    assert_eq!(vec![(vec![DataType::Integer], Some(DataType::Integer)), (vec![DataType::Float], Some(DataType::Float))],
+               Native::Abs.signatures());
+    assert_eq!(0, Native::ReadLine.arity());
+}
+
+#[test]
+fn string_functions_test() {
+    assert_eq!(Ok(Value::Integer(5)), call(Native::Length, vec![string("häuse")]));
+    assert_eq!(Ok(string("äus")), call(Native::Substring, vec![string("häuse"), Value::Integer(1), Value::Integer(4)]));
+    assert_eq!(Ok(string("")), call(Native::Substring, vec![string("abc"), Value::Integer(3), Value::Integer(3)]));
+    assert_eq!(Err(RuntimeError::InvalidRange(2, 1, 3)), call(Native::Substring, vec![string("abc"), Value::Integer(2), Value::Integer(1)]));
+    assert_eq!(Err(RuntimeError::InvalidRange(0, 4, 3)), call(Native::Substring, vec![string("abc"), Value::Integer(0), Value::Integer(4)]));
+    assert_eq!(Ok(string("paper castle")), call(Native::Concat, vec![string("paper "), string("castle")]));
+    assert_eq!(Ok(string("a b")), call(Native::Trim, vec![string(" \ta b\n")]));
+
+    let split = call(Native::Split, vec![string("a,b,,c"), string(",")]).expect("expected parts").to_string();
+    assert_eq!("[a, b, , c]", split);
+    let characters = call(Native::Split, vec![string("abc"), string("")]).expect("expected characters").to_string();
+    assert_eq!("[a, b, c]", characters);
+}
+
+#[test]
+fn number_functions_test() {
+    assert_eq!(Ok(Value::Integer(3)), call(Native::Abs, vec![Value::Integer(-3)]));
+    assert_eq!(Err(RuntimeError::IntegerOverflow), call(Native::Abs, vec![Value::Integer(i64::MIN)]));
+    assert_eq!(Ok(Value::Integer(-2)), call(Native::Min, vec![Value::Integer(-2), Value::Integer(7)]));
+    assert_eq!(Ok(Value::Integer(7)), call(Native::Max, vec![Value::Integer(-2), Value::Integer(7)]));
+    assert_eq!(Ok(Value::Integer(1024)), call(Native::Pow, vec![Value::Integer(2), Value::Integer(10)]));
+    assert_eq!(Ok(Value::Integer(1)), call(Native::Pow, vec![Value::Integer(5), Value::Integer(0)]));
+    assert_eq!(Err(RuntimeError::NegativeExponent(-1)), call(Native::Pow, vec![Value::Integer(2), Value::Integer(-1)]));
+    assert_eq!(Err(RuntimeError::IntegerOverflow), call(Native::Pow, vec![Value::Integer(2), Value::Integer(64)]));
+    assert_eq!(Ok(Value::Float(1.5)), call(Native::Sqrt, vec![Value::Float(2.25)]));
+
+    assert_eq!(Ok(Value::Float(1.5)), call(Native::Abs, vec![Value::Float(-1.5)]));
+    assert_eq!(Ok(Value::Float(1.0)), call(Native::Min, vec![Value::Float(1.0), Value::Float(2.0)]));
+    assert_eq!(Ok(Value::Float(2.0)), call(Native::Max, vec![Value::Float(1.0), Value::Float(2.0)]));
+    assert_eq!(Ok(Value::Float(4.0)), call(Native::Pow, vec![Value::Float(16.0), Value::Float(0.5)]));
+    assert_eq!(Err(RuntimeError::TypeMismatch(DataType::Integer, "float")), call(Native::Pow, vec![Value::Integer(2), Value::Float(0.5)]));
+}
+
+#[test]
+fn conversions_test() {
+    assert_eq!(Ok(Value::Float(3.0)), call(Native::IntToFloat, vec![Value::Integer(3)]));
+    assert_eq!(Ok(Value::Integer(-2)), call(Native::FloatToInt, vec![Value::Float(-2.9)]));
+    assert_eq!(Err(RuntimeError::InvalidConversion("NaN".into(), "int")), call(Native::FloatToInt, vec![Value::Float(f64::NAN)]));
+    assert_eq!(Err(RuntimeError::InvalidConversion("1e300".into(), "int")), call(Native::FloatToInt, vec![Value::Float(1e300)]));
+    assert_eq!(Ok(string("42")), call(Native::IntToString, vec![Value::Integer(42)]));
+    assert_eq!(Ok(string("2.0")), call(Native::FloatToString, vec![Value::Float(2.0)]));
+    assert_eq!(Ok(string("true")), call(Native::BooleanToString, vec![Value::Boolean(true)]));
+    assert_eq!(Ok(Value::Integer(-17)), call(Native::ParseInt, vec![string(" -17\n")]));
+    assert_eq!(Err(RuntimeError::InvalidConversion("1.5".into(), "int")), call(Native::ParseInt, vec![string("1.5")]));
+    assert_eq!(Ok(Value::Float(0.25)), call(Native::ParseFloat, vec![string("0.25")]));
+    assert_eq!(Ok(Value::Boolean(false)), call(Native::ParseBoolean, vec![string("false")]));
+    assert_eq!(Err(RuntimeError::InvalidConversion("yes".into(), "boolean")), call(Native::ParseBoolean, vec![string("yes")]));
+}
+
+#[test]
+fn assert_and_exit_test() {
+    assert_eq!(Ok(Value::Void), call(Native::Assert, vec![Value::Boolean(true), string("unused")]));
+    assert_eq!(Err(RuntimeError::AssertionFailed("x is odd".into())), call(Native::Assert, vec![Value::Boolean(false), string("x is odd")]));
+    assert_eq!(Err(RuntimeError::Exit(3)), call(Native::Exit, vec![Value::Integer(3)]));
+}
+
+#[test]
+fn invalid_arguments_test() {
+    assert_eq!(Err(RuntimeError::WrongArgumentCount("trim".into(), 1, 0)), call(Native::Trim, vec![]));
+    assert_eq!(Err(RuntimeError::TypeMismatch(DataType::String, "int")), call(Native::Length, vec![Value::Integer(1)]));
+}
+
+fn call(native: Native, args: Vec<Value>) -> Result<Value, RuntimeError> {
+    native.call(args)
+}
+
+fn string(s: &str) -> Value {
+    Value::String(s.into())
+}
@@ -1,87 +1,135 @@
 
 use crate::frontend::lexer::{Lexer, LexerError};
 use crate::frontend::syntax::token::{TokenType, Token};
+use crate::frontend::syntax::span::Span;
 
 #[test]
 fn tokenizer_operator_test(){
     let src = "==";
-    let equal = Lexer::tokenize(src.into()).0.collect();
-    let expected = vec![Token::new(TokenType::OperatorEqual,"==".into(),0),eof()];
+    let equal = tokenize(src);
+    let expected = vec![Token::new(TokenType::OperatorEqual,"==".into(),Span::new(0,2,1,1)),eof(src)];
     assert_eq!(equal,expected);
 
     let src = " =";
-    let assign = Lexer::tokenize(src.into()).0.collect();
-    let expected = vec![Token::new(TokenType::Assign,"=".into(),0),eof()];
+    let assign = tokenize(src);
+    let expected = vec![Token::new(TokenType::Assign,"=".into(),Span::new(1,2,1,2)),eof(src)];
     assert_eq!(expected,assign);
 
     let src = "+";
-    let plus = Lexer::tokenize(src.into()).0.collect();
-    let expected = vec![Token::new(TokenType::OperatorPlus,"+".into(),0),eof()];
+    let plus = tokenize(src);
+    let expected = vec![Token::new(TokenType::OperatorPlus,"+".into(),Span::new(0,1,1,1)),eof(src)];
     assert_eq!(plus,expected);
 
     let src = "-";
-    let minus = Lexer::tokenize(src.into()).0.collect();
-    let expected = vec![Token::new(TokenType::OperatorMinus,"-".into(),0),eof()];
+    let minus = tokenize(src);
+    let expected = vec![Token::new(TokenType::OperatorMinus,"-".into(),Span::new(0,1,1,1)),eof(src)];
     assert_eq!(minus,expected);
 
     let src = "*";
-    let multi = Lexer::tokenize(src.into()).0.collect();
-    let expected = vec![Token::new(TokenType::OperatorMultiplication,"*".into(),0),eof()];
+    let multi = tokenize(src);
+    let expected = vec![Token::new(TokenType::OperatorMultiplication,"*".into(),Span::new(0,1,1,1)),eof(src)];
     assert_eq!(multi,expected);
 
     let src = "/";
-    let divide = Lexer::tokenize(src.into()).0.collect();
-    let expected = vec![Token::new(TokenType::OperatorDivide,"/".into(),0),eof()];
+    let divide = tokenize(src);
+    let expected = vec![Token::new(TokenType::OperatorDivide,"/".into(),Span::new(0,1,1,1)),eof(src)];
     assert_eq!(divide,expected);
 
     let src = " <   ";
-    let less = Lexer::tokenize(src.into()).0.collect();
-    let expected = vec![Token::new(TokenType::OperatorLessThen,"<".into(),0),eof()];
+    let less = tokenize(src);
+    let expected = vec![Token::new(TokenType::OperatorLessThen,"<".into(),Span::new(1,2,1,2)),eof(src)];
     assert_eq!(less,expected);
 
     let src = "  > ";
-    let greater_then = Lexer::tokenize(src.into()).0.collect();
-    let expected = vec![Token::new(TokenType::OperatorGreaterThen,">".into(),0),eof()];
+    let greater_then = tokenize(src);
+    let expected = vec![Token::new(TokenType::OperatorGreaterThen,">".into(),Span::new(2,3,1,3)),eof(src)];
     assert_eq!(greater_then,expected);
 }
 
+#[test]
+fn tokenizer_two_char_operator_test(){
+    expect_token("<=",TokenType::OperatorLessOrEqual);
+    expect_token(">=",TokenType::OperatorGreaterOrEqual);
+    expect_token("!=",TokenType::OperatorNotEqual);
+    expect_token("&&",TokenType::And);
+    expect_token("||",TokenType::Or);
+    expect_token("%",TokenType::OperatorModulo);
+
+    let src = "a<=b>=c<d";
+    let tokens = tokenize(src);
+    let expected = vec![
+        Token::new(TokenType::Identifier,"a".into(),Span::new(0,1,1,1)),
+        Token::new(TokenType::OperatorLessOrEqual,"<=".into(),Span::new(1,3,1,2)),
+        Token::new(TokenType::Identifier,"b".into(),Span::new(3,4,1,4)),
+        Token::new(TokenType::OperatorGreaterOrEqual,">=".into(),Span::new(4,6,1,5)),
+        Token::new(TokenType::Identifier,"c".into(),Span::new(6,7,1,7)),
+        Token::new(TokenType::OperatorLessThen,"<".into(),Span::new(7,8,1,8)),
+        Token::new(TokenType::Identifier,"d".into(),Span::new(8,9,1,9)),
+        eof(src)
+    ];
+    assert_eq!(expected,tokens);
+
+    let src = "< =";
+    let tokens = tokenize(src);
+    let expected = vec![
+        Token::new(TokenType::OperatorLessThen,"<".into(),Span::new(0,1,1,1)),
+        Token::new(TokenType::Assign,"=".into(),Span::new(2,3,1,3)),
+        eof(src)
+    ];
+    assert_eq!(expected,tokens);
+
+    let src = "a & b";
+    let result = Lexer::new(src).collect::<Result<Vec<_>,_>>();
+    assert_eq!(Err(LexerError::UnknownCharacter('&',Span::new(2,3,1,3))), result);
+}
+
 
 #[test]
 fn tokenizer_separator_test(){
 
     let curved_bracket_open = "{";
-    let tokens = Lexer::tokenize(curved_bracket_open.into()).0.collect();
-    let expected = vec![Token::new(TokenType::SeparatorCurvedBracketOpen,curved_bracket_open.to_string(),0),eof()];
+    let tokens = tokenize(curved_bracket_open);
+    let expected = vec![Token::new(TokenType::SeparatorCurvedBracketOpen,curved_bracket_open.to_string(),Span::new(0,1,1,1)),eof(curved_bracket_open)];
     assert_eq!(expected,tokens);
 
     let curved_bracket_closed = "}";
-    let tokens = Lexer::tokenize(curved_bracket_closed.into()).0.collect();
-    let expected = vec![Token::new(TokenType::SeparatorCurvedBracketClosed,curved_bracket_closed.to_string(),0),eof()];
+    let tokens = tokenize(curved_bracket_closed);
+    let expected = vec![Token::new(TokenType::SeparatorCurvedBracketClosed,curved_bracket_closed.to_string(),Span::new(0,1,1,1)),eof(curved_bracket_closed)];
+    assert_eq!(expected,tokens);
+
+    let square_bracket_open = "[";
+    let tokens = tokenize(square_bracket_open);
+    let expected = vec![Token::new(TokenType::SeparatorSquareBracketOpen,square_bracket_open.to_string(),Span::new(0,1,1,1)),eof(square_bracket_open)];
+    assert_eq!(expected,tokens);
+
+    let square_bracket_closed = "]";
+    let tokens = tokenize(square_bracket_closed);
+    let expected = vec![Token::new(TokenType::SeparatorSquareBracketClosed,square_bracket_closed.to_string(),Span::new(0,1,1,1)),eof(square_bracket_closed)];
     assert_eq!(expected,tokens);
 
     let bracket_open = "(";
-    let tokens = Lexer::tokenize(bracket_open.into()).0.collect();
-    let expected = vec![Token::new(TokenType::SeparatorBracketOpen,bracket_open.to_string(),0),eof()];
+    let tokens = tokenize(bracket_open);
+    let expected = vec![Token::new(TokenType::SeparatorBracketOpen,bracket_open.to_string(),Span::new(0,1,1,1)),eof(bracket_open)];
     assert_eq!(expected,tokens);
 
     let bracket_closed = ")";
-    let tokens = Lexer::tokenize(bracket_closed.into()).0.collect();
-    let expected = vec![Token::new(TokenType::SeparatorBracketClose,bracket_closed.to_string(),0),eof()];
+    let tokens = tokenize(bracket_closed);
+    let expected = vec![Token::new(TokenType::SeparatorBracketClose,bracket_closed.to_string(),Span::new(0,1,1,1)),eof(bracket_closed)];
     assert_eq!(expected,tokens);
 
     let semicolon = ";";
-    let tokens = Lexer::tokenize(semicolon.into()).0.collect();
-    let expected = vec![Token::new(TokenType::SeparatorSemiColon,semicolon.to_string(),0),eof()];
+    let tokens = tokenize(semicolon);
+    let expected = vec![Token::new(TokenType::SeparatorSemiColon,semicolon.to_string(),Span::new(0,1,1,1)),eof(semicolon)];
     assert_eq!(expected,tokens);
 
     let comma = ",";
-    let tokens = Lexer::tokenize(comma.into()).0.collect();
-    let expected = vec![Token::new(TokenType::SeparatorComma, comma.to_string(), 0),eof()];
+    let tokens = tokenize(comma);
+    let expected = vec![Token::new(TokenType::SeparatorComma, comma.to_string(), Span::new(0,1,1,1)),eof(comma)];
     assert_eq!(expected,tokens);
 
     let colon = " : ";
-    let tokens = Lexer::tokenize(colon.into()).0.collect();
-    let expected = vec![Token::new(TokenType::SeparatorColon,":".into(),0),eof()];
+    let tokens = tokenize(colon);
+    let expected = vec![Token::new(TokenType::SeparatorColon,":".into(),Span::new(1,2,1,2)),eof(colon)];
     assert_eq!(tokens,expected);
 }
 
@@ -89,52 +137,152 @@ fn tokenizer_separator_test(){
 fn tokenizer_numbers_test(){
 
     let float = "5.3";
-    let tokens = Lexer::tokenize(float.into()).0.collect();
-    let expected = vec![Token::new(TokenType::LiteralFloat,float.to_string(),0),eof()];
+    let tokens = tokenize(float);
+    let expected = vec![Token::new(TokenType::LiteralFloat,float.to_string(),Span::new(0,3,1,1)),eof(float)];
     assert_eq!(expected,tokens);
 
     let invalid_float = ".9";
-    let tokens = Lexer::tokenize(invalid_float.into()).0.collect();
-    let dot = Token::new(TokenType::SeparatorDot,".".into(),0);
-    let nine = Token::new(TokenType::LiteralInteger,"9".into(),0);
-    let expected = vec![dot,nine,eof()];
+    let tokens = tokenize(invalid_float);
+    let dot = Token::new(TokenType::SeparatorDot,".".into(),Span::new(0,1,1,1));
+    let nine = Token::new(TokenType::LiteralInteger,"9".into(),Span::new(1,2,1,2));
+    let expected = vec![dot,nine,eof(invalid_float)];
     assert_eq!(expected,tokens);
 
     let invalid_float2 = "9.";
-    let tokens = Lexer::tokenize(invalid_float2.into()).0.collect();
+    let tokens = tokenize(invalid_float2);
     let expected = vec![
-        Token::new(TokenType::LiteralFloat,"9.".into(),0),
-        eof()
+        Token::new(TokenType::LiteralFloat,"9.".into(),Span::new(0,2,1,1)),
+        eof(invalid_float2)
     ];
     assert_eq!(expected,tokens);
 
     let invalid_float3 = "9,0";
-    let tokens = Lexer::tokenize(invalid_float3.into()).0.collect();
-    let t9 = Token::new(TokenType::LiteralInteger,"9".into(),0);
-    let t_sep = Token::new(TokenType::SeparatorComma, ",".into(), 0);
-    let t0 = Token::new(TokenType::LiteralInteger,"0".into(),0);
-    let expected = vec![t9,t_sep,t0,eof()];
+    let tokens = tokenize(invalid_float3);
+    let t9 = Token::new(TokenType::LiteralInteger,"9".into(),Span::new(0,1,1,1));
+    let t_sep = Token::new(TokenType::SeparatorComma, ",".into(), Span::new(1,2,1,2));
+    let t0 = Token::new(TokenType::LiteralInteger,"0".into(),Span::new(2,3,1,3));
+    let expected = vec![t9,t_sep,t0,eof(invalid_float3)];
     assert_eq!(expected,tokens);
 
 
     let valid_float2 = "1.23421323";
-    let tokens = Lexer::tokenize(valid_float2.into()).0.collect();
-    let expected = vec![Token::new(TokenType::LiteralFloat,valid_float2.into(),0),eof()];
+    let tokens = tokenize(valid_float2);
+    let expected = vec![Token::new(TokenType::LiteralFloat,valid_float2.into(),Span::new(0,10,1,1)),eof(valid_float2)];
     assert_eq!(expected,tokens);
 
     let integer = "6";
-    let tokens = Lexer::tokenize(integer.into()).0.collect();
-    let expected = vec![Token::new(TokenType::LiteralInteger,integer.into(),0),eof()];
+    let tokens = tokenize(integer);
+    let expected = vec![Token::new(TokenType::LiteralInteger,integer.into(),Span::new(0,1,1,1)),eof(integer)];
     assert_eq!(expected,tokens);
 
     let identifier = "b6";
-    let tokens = Lexer::tokenize(identifier.into()).0.collect();
-    let expected = vec![Token::new(TokenType::Identifier,identifier.into(),0),eof()];
+    let tokens = tokenize(identifier);
+    let expected = vec![Token::new(TokenType::Identifier,identifier.into(),Span::new(0,2,1,1)),eof(identifier)];
     assert_eq!(expected,tokens);
 
     let nan = "6b";
-    let tokens = Lexer::tokenize(nan.into()).0.collect();
-    let expected = vec![Token::new(TokenType::LiteralInteger,"6b".into(),0),eof()];
+    let tokens = Lexer::new(nan).collect::<Result<Vec<_>,_>>();
+    let expected = Err(LexerError::MalformedNumber("a decimal number only contains the digits 0 to 9",Span::new(0,2,1,1)));
+    assert_eq!(expected,tokens);
+}
+
+#[test]
+fn number_formats_test(){
+    expect_token("0xFF",TokenType::LiteralInteger);
+    expect_token("0b1010",TokenType::LiteralInteger);
+    expect_token("0o17",TokenType::LiteralInteger);
+    expect_token("1_000_000",TokenType::LiteralInteger);
+    expect_token("0xdead_beef",TokenType::LiteralInteger);
+    expect_token("1e-9",TokenType::LiteralFloat);
+    expect_token("2.5E+3",TokenType::LiteralFloat);
+    expect_token("1e10",TokenType::LiteralFloat);
+    expect_token("3.141_592",TokenType::LiteralFloat);
+
+    // the sign of an exponent belongs to the number, the minus behind a hexadecimal number does not
+    let kinds: Vec<TokenType> = tokenize("0xE-1 1..2").iter().map(Token::kind).collect();
+    assert_eq!(vec![
+        TokenType::LiteralInteger, TokenType::OperatorMinus, TokenType::LiteralInteger,
+        TokenType::LiteralInteger, TokenType::SeparatorRange, TokenType::LiteralInteger, TokenType::EoF,
+    ], kinds);
+
+    let malformed = vec![
+        ("12abc", "a decimal number only contains the digits 0 to 9", 5),
+        ("1.2.3", "a decimal number only contains the digits 0 to 9", 5),
+        ("0b102", "a binary number only contains the digits 0 and 1", 5),
+        ("0o8", "an octal number only contains the digits 0 to 7", 3),
+        ("0xFG", "a hexadecimal number only contains the digits 0 to 9 and a to f", 4),
+        ("0x", "expected digits", 2),
+        ("1e", "expected digits", 2),
+        ("1e+", "expected digits", 3),
+        ("1.e5", "expected digits behind the decimal point", 4),
+        ("1__0", "`_` has to stand between two digits", 4),
+        ("1_", "`_` has to stand between two digits", 2),
+        ("0x_1", "`_` has to stand between two digits", 4),
+        ("1._5", "`_` has to stand between two digits", 4),
+    ];
+    for (src, reason, end) in malformed {
+        let expected = Err(LexerError::MalformedNumber(reason, Span::new(0, end, 1, 1)));
+        assert_eq!(expected, Lexer::new(src).collect::<Result<Vec<_>,_>>(), "{}", src);
+    }
+}
+
+#[test]
+fn tokenizer_match_arm_test(){
+    let arm = "_ => x == 1";
+    let tokens = tokenize(arm);
+    let expected = vec![
+        Token::new(TokenType::Underscore,"_".into(),Span::new(0,1,1,1)),
+        Token::new(TokenType::SeparatorArrow,"=>".into(),Span::new(2,4,1,3)),
+        Token::new(TokenType::Identifier,"x".into(),Span::new(5,6,1,6)),
+        Token::new(TokenType::OperatorEqual,"==".into(),Span::new(7,9,1,8)),
+        Token::new(TokenType::LiteralInteger,"1".into(),Span::new(10,11,1,11)),
+        eof(arm)
+    ];
+    assert_eq!(expected,tokens);
+}
+
+#[test]
+fn tokenizer_path_test(){
+    let path = "Point::new";
+    let tokens = tokenize(path);
+    let expected = vec![
+        Token::new(TokenType::Identifier,"Point".into(),Span::new(0,5,1,1)),
+        Token::new(TokenType::SeparatorPath,"::".into(),Span::new(5,7,1,6)),
+        Token::new(TokenType::Identifier,"new".into(),Span::new(7,10,1,8)),
+        eof(path)
+    ];
+    assert_eq!(expected,tokens);
+}
+
+#[test]
+fn tokenizer_range_test(){
+    let range = "0..10";
+    let tokens = tokenize(range);
+    let expected = vec![
+        Token::new(TokenType::LiteralInteger,"0".into(),Span::new(0,1,1,1)),
+        Token::new(TokenType::SeparatorRange,"..".into(),Span::new(1,3,1,2)),
+        Token::new(TokenType::LiteralInteger,"10".into(),Span::new(3,5,1,4)),
+        eof(range)
+    ];
+    assert_eq!(expected,tokens);
+
+    let float_range = "1.5..n";
+    let tokens = tokenize(float_range);
+    let expected = vec![
+        Token::new(TokenType::LiteralFloat,"1.5".into(),Span::new(0,3,1,1)),
+        Token::new(TokenType::SeparatorRange,"..".into(),Span::new(3,5,1,4)),
+        Token::new(TokenType::Identifier,"n".into(),Span::new(5,6,1,6)),
+        eof(float_range)
+    ];
+    assert_eq!(expected,tokens);
+
+    let dots = "...";
+    let tokens = tokenize(dots);
+    let expected = vec![
+        Token::new(TokenType::SeparatorRange,"..".into(),Span::new(0,2,1,1)),
+        Token::new(TokenType::SeparatorDot,".".into(),Span::new(2,3,1,3)),
+        eof(dots)
+    ];
     assert_eq!(expected,tokens);
 }
 
@@ -142,39 +290,78 @@ fn tokenizer_numbers_test(){
 fn tokenizer_literal_strings_test(){
 
     let invalid_string_not_closed = "\"this is an invalid string ";
-    let tokens = Lexer::tokenize(invalid_string_not_closed.into()).1.join().unwrap_or_else(|_| panic!("should never happen"));
-    let expected = Err(LexerError::UnexpectedEndOfString);
+    let tokens = Lexer::new(invalid_string_not_closed).collect::<Result<Vec<_>,_>>();
+    let expected = Err(LexerError::UnexpectedEndOfString(Span::new(0,27,1,1)));
     assert_eq!(expected,tokens);
 
     let new_string = wrap_with_quotes("this is a string");
-    let tokens = Lexer::tokenize(new_string.into()).0.collect();
-    let expected = vec![Token::new(TokenType::LiteralString, String::from("this is a string"),0),eof()];
+    let tokens = tokenize(&new_string);
+    let expected = vec![Token::new(TokenType::LiteralString, String::from("this is a string"),Span::new(0,18,1,1)),eof(&new_string)];
     assert_eq!(expected,tokens);
 
     let sentence = "this is a penguin emoticon 🐧 \n \n \n \t whdazhwsihawdhasiudhuiawuidh a;;;;;; ,, ++++ ++///";
     let s = wrap_with_quotes(sentence);
-    let tokens = Lexer::tokenize(s).0.collect();
-    let expected = vec![Token::new(TokenType::LiteralString, String::from(sentence),0),eof()];
+    let tokens = tokenize(&s);
+    let expected = vec![Token::new(TokenType::LiteralString, String::from(sentence),Span::new(0,92,1,1)),eof(&s)];
     assert_eq!(expected,tokens);
 }
 
+#[test]
+fn escape_sequences_test(){
+    let src = r#""line\n\ttab \"quoted\" back\\slash \u{1F427}\u{e4}""#;
+    let tokens = tokenize(src);
+    let expected = Token::new(TokenType::LiteralString, "line\n\ttab \"quoted\" back\\slash 🐧ä".into(), Span::new(0, src.len(), 1, 1));
+    assert_eq!(vec![expected, eof(src)], tokens);
+
+    let invalid = vec![
+        (r#""a\qb""#, LexerError::InvalidEscape('q', Span::new(2, 4, 1, 3))),
+        (r#""\u{110000}""#, LexerError::InvalidEscape('u', Span::new(1, 11, 1, 2))),
+        (r#""\u{}""#, LexerError::InvalidEscape('u', Span::new(1, 5, 1, 2))),
+        (r#""\u{1234567}""#, LexerError::InvalidEscape('u', Span::new(1, 12, 1, 2))),
+        (r#""\u{zz}""#, LexerError::InvalidEscape('u', Span::new(1, 4, 1, 2))),
+        (r#""\u41""#, LexerError::InvalidEscape('u', Span::new(1, 3, 1, 2))),
+        (r#""ends\"#, LexerError::UnexpectedEndOfString(Span::new(0, 6, 1, 1))),
+    ];
+    for (src, error) in invalid {
+        assert_eq!(Err(error), Lexer::new(src).collect::<Result<Vec<_>,_>>(), "{}", src);
+    }
+}
+
+#[test]
+fn raw_and_multi_line_strings_test(){
+    let src = r#"r"C:\new\t" "no \" end""#;
+    let tokens = tokenize(src);
+    assert_eq!(Token::new(TokenType::LiteralString, r"C:\new\t".into(), Span::new(0, 11, 1, 1)), tokens[0]);
+    assert_eq!(Token::new(TokenType::LiteralString, "no \" end".into(), Span::new(12, 23, 1, 13)), tokens[1]);
+    assert_eq!(Err(LexerError::UnexpectedEndOfString(Span::new(0, 5, 1, 1))), Lexer::new("r\"abc").collect::<Result<Vec<_>,_>>());
+    // an r which is not followed by a quote is an identifier
+    assert_eq!(TokenType::Identifier, tokenize("r + 1")[0].kind());
+
+    // the line break of a multi-line string is part of the value, a backslash removes it with the indentation
+    let src = "x = \"first\n  second \\\n    third\";\ny";
+    let tokens = tokenize(src);
+    assert_eq!(Token::new(TokenType::LiteralString, "first\n  second third".into(), Span::new(4, 32, 1, 5)), tokens[2]);
+    assert_eq!(Span::new(32, 33, 3, 11), tokens[3].span());
+    assert_eq!(Span::new(34, 35, 4, 1), tokens[4].span());
+}
+
 #[test]
 fn tokenizer_function_test(){
 
     let function_call = "fn onKey(CTRL){\n  exit(); \n}";
-    let tokens = Lexer::tokenize(function_call.into()).0.collect();
-
-    let keyword_fn = Token::new(TokenType::Fn,"fn".into(),0);
-    let function_name = Token::new(TokenType::Identifier,"onKey".into(),0);
-    let bracket_open = Token::new(TokenType::SeparatorBracketOpen,"(".into(),0);
-    let ctrl = Token::new(TokenType::Identifier,"CTRL".into(),0);
-    let bracket_closed = Token::new(TokenType::SeparatorBracketClose,")".into(),0);
-    let c_bracket_open = Token::new(TokenType::SeparatorCurvedBracketOpen,"{".into(),0);
-    let exit_fn = Token::new(TokenType::Identifier,"exit".into(),0);
-    let bracket_open_inner = Token::new(TokenType::SeparatorBracketOpen,"(".into(),0);
-    let bracket_closed_inner = Token::new(TokenType::SeparatorBracketClose,")".into(),0);
-    let semicolon = Token::new(TokenType::SeparatorSemiColon,";".into(),0);
-    let c_bracket_closed = Token::new(TokenType::SeparatorCurvedBracketClosed,"}".into(),0);
+    let tokens = tokenize(function_call);
+
+    let keyword_fn = Token::new(TokenType::Fn,"fn".into(),Span::new(0,2,1,1));
+    let function_name = Token::new(TokenType::Identifier,"onKey".into(),Span::new(3,8,1,4));
+    let bracket_open = Token::new(TokenType::SeparatorBracketOpen,"(".into(),Span::new(8,9,1,9));
+    let ctrl = Token::new(TokenType::Identifier,"CTRL".into(),Span::new(9,13,1,10));
+    let bracket_closed = Token::new(TokenType::SeparatorBracketClose,")".into(),Span::new(13,14,1,14));
+    let c_bracket_open = Token::new(TokenType::SeparatorCurvedBracketOpen,"{".into(),Span::new(14,15,1,15));
+    let exit_fn = Token::new(TokenType::Identifier,"exit".into(),Span::new(18,22,2,3));
+    let bracket_open_inner = Token::new(TokenType::SeparatorBracketOpen,"(".into(),Span::new(22,23,2,7));
+    let bracket_closed_inner = Token::new(TokenType::SeparatorBracketClose,")".into(),Span::new(23,24,2,8));
+    let semicolon = Token::new(TokenType::SeparatorSemiColon,";".into(),Span::new(24,25,2,9));
+    let c_bracket_closed = Token::new(TokenType::SeparatorCurvedBracketClosed,"}".into(),Span::new(27,28,3,1));
 
     let expected = vec![
         keyword_fn,
@@ -188,7 +375,7 @@ fn tokenizer_function_test(){
         bracket_closed_inner,
         semicolon,
         c_bracket_closed,
-        eof()
+        eof(function_call)
     ];
 
     assert_eq!(expected,tokens);
@@ -202,40 +389,40 @@ fn tokenizer_function_test(){
             continue;\
         } return;\
     } }";
-    let tokens = Lexer::tokenize(function.into()).0.collect();
+    let tokens = tokenize(function);
 
     let expected = vec![
-        Token::new(TokenType::Fn,"fn".into(),0),
-        Token::new(TokenType::Identifier,"test".into(),0),
-        Token::new(TokenType::SeparatorBracketOpen,"(".into(),0),
-        Token::new(TokenType::SeparatorBracketClose,")".into(),0),
-        Token::new(TokenType::SeparatorCurvedBracketOpen,"{".into(),0),
-        Token::new(TokenType::Loop,"loop".into(),0),
-        Token::new(TokenType::SeparatorCurvedBracketOpen,"{".into(),0),
-        Token::new(TokenType::Let,"let".into(),0),
-        Token::new(TokenType::Identifier,"a".into(),0),
-        Token::new(TokenType::SeparatorColon,":".into(),0),
-        Token::new(TokenType::Boolean,"boolean".into(),0),
-        Token::new(TokenType::Assign,"=".into(),0),
-        Token::new(TokenType::BooleanFalse,"false".into(),0),
-        Token::new(TokenType::SeparatorSemiColon,";".into(),0),
-        Token::new(TokenType::If,"if".into(),0),
-        Token::new(TokenType::OperatorNegation,"!".into(),0),
-        Token::new(TokenType::Identifier,"a".into(),0),
-        Token::new(TokenType::SeparatorCurvedBracketOpen,"{".into(),0),
-        Token::new(TokenType::Break,"break".into(),0),
-        Token::new(TokenType::SeparatorSemiColon,";".into(),0),
-        Token::new(TokenType::SeparatorCurvedBracketClosed,"}".into(),0),
-        Token::new(TokenType::Else,"else".into(),0),
-        Token::new(TokenType::SeparatorCurvedBracketOpen,"{".into(),0),
-        Token::new(TokenType::Continue,"continue".into(),0),
-        Token::new(TokenType::SeparatorSemiColon,";".into(),0),
-        Token::new(TokenType::SeparatorCurvedBracketClosed,"}".into(),0),
-        Token::new(TokenType::Return,"return".into(),0),
-        Token::new(TokenType::SeparatorSemiColon,";".into(),0),
-        Token::new(TokenType::SeparatorCurvedBracketClosed,"}".into(),0),
-        Token::new(TokenType::SeparatorCurvedBracketClosed,"}".into(),0),
-        eof()
+        Token::new(TokenType::Fn,"fn".into(),Span::new(0,2,1,1)),
+        Token::new(TokenType::Identifier,"test".into(),Span::new(3,7,1,4)),
+        Token::new(TokenType::SeparatorBracketOpen,"(".into(),Span::new(7,8,1,8)),
+        Token::new(TokenType::SeparatorBracketClose,")".into(),Span::new(8,9,1,9)),
+        Token::new(TokenType::SeparatorCurvedBracketOpen,"{".into(),Span::new(9,10,1,10)),
+        Token::new(TokenType::Loop,"loop".into(),Span::new(11,15,1,12)),
+        Token::new(TokenType::SeparatorCurvedBracketOpen,"{".into(),Span::new(15,16,1,16)),
+        Token::new(TokenType::Let,"let".into(),Span::new(17,20,1,18)),
+        Token::new(TokenType::Identifier,"a".into(),Span::new(21,22,1,22)),
+        Token::new(TokenType::SeparatorColon,":".into(),Span::new(22,23,1,23)),
+        Token::new(TokenType::Boolean,"boolean".into(),Span::new(24,31,1,25)),
+        Token::new(TokenType::Assign,"=".into(),Span::new(32,33,1,33)),
+        Token::new(TokenType::BooleanFalse,"false".into(),Span::new(34,39,1,35)),
+        Token::new(TokenType::SeparatorSemiColon,";".into(),Span::new(39,40,1,40)),
+        Token::new(TokenType::If,"if".into(),Span::new(41,43,1,42)),
+        Token::new(TokenType::OperatorNegation,"!".into(),Span::new(44,45,1,45)),
+        Token::new(TokenType::Identifier,"a".into(),Span::new(45,46,1,46)),
+        Token::new(TokenType::SeparatorCurvedBracketOpen,"{".into(),Span::new(47,48,1,48)),
+        Token::new(TokenType::Break,"break".into(),Span::new(48,53,1,49)),
+        Token::new(TokenType::SeparatorSemiColon,";".into(),Span::new(53,54,1,54)),
+        Token::new(TokenType::SeparatorCurvedBracketClosed,"}".into(),Span::new(54,55,1,55)),
+        Token::new(TokenType::Else,"else".into(),Span::new(56,60,1,57)),
+        Token::new(TokenType::SeparatorCurvedBracketOpen,"{".into(),Span::new(61,62,1,62)),
+        Token::new(TokenType::Continue,"continue".into(),Span::new(62,70,1,63)),
+        Token::new(TokenType::SeparatorSemiColon,";".into(),Span::new(70,71,1,71)),
+        Token::new(TokenType::SeparatorCurvedBracketClosed,"}".into(),Span::new(71,72,1,72)),
+        Token::new(TokenType::Return,"return".into(),Span::new(73,79,1,74)),
+        Token::new(TokenType::SeparatorSemiColon,";".into(),Span::new(79,80,1,80)),
+        Token::new(TokenType::SeparatorCurvedBracketClosed,"}".into(),Span::new(80,81,1,81)),
+        Token::new(TokenType::SeparatorCurvedBracketClosed,"}".into(),Span::new(82,83,1,83)),
+        eof(function)
     ];
 
     assert_eq!(expected,tokens);
@@ -244,23 +431,58 @@ fn tokenizer_function_test(){
 #[test]
 fn identifier_test() {
     let src = "let xðłð@łðæſ = 5;";
-    let tokens = Lexer::tokenize(src.into()).1.join().unwrap_or_else(|_| panic!("should never happen"));
-    let expected = Err(LexerError::UnknownCharacter('@'));
+    let tokens = Lexer::new(src).collect::<Result<Vec<_>,_>>();
+    let expected = Err(LexerError::UnknownCharacter('@',Span::new(11,12,1,9)));
     assert_eq!(expected,tokens);
 
     let src = "let ä = 5;";
-    let tokens = Lexer::tokenize(src.into()).0.collect();
+    let tokens = tokenize(src);
+    let expected = vec![
+        Token::new(TokenType::Let,"let".into(),Span::new(0,3,1,1)),
+        Token::new(TokenType::Identifier,"ä".into(),Span::new(4,6,1,5)),
+        Token::new(TokenType::Assign,"=".into(),Span::new(7,8,1,7)),
+        Token::new(TokenType::LiteralInteger,"5".into(),Span::new(9,10,1,9)),
+        Token::new(TokenType::SeparatorSemiColon,";".into(),Span::new(10,11,1,10)),
+        eof(src)
+    ];
+    assert_eq!(expected,tokens);
+
+    // underscores are part of a name, only a single underscore is the wildcard
+    let src = "read_line _unused __ _";
+    let tokens = tokenize(src);
     let expected = vec![
-        Token::new(TokenType::Let,"let".into(),0),
-        Token::new(TokenType::Identifier,"ä".into(),0),
-        Token::new(TokenType::Assign,"=".into(),0),
-        Token::new(TokenType::LiteralInteger,"5".into(),0),
-        Token::new(TokenType::SeparatorSemiColon,";".into(),0),
-        eof()
+        Token::new(TokenType::Identifier,"read_line".into(),Span::new(0,9,1,1)),
+        Token::new(TokenType::Identifier,"_unused".into(),Span::new(10,17,1,11)),
+        Token::new(TokenType::Identifier,"__".into(),Span::new(18,20,1,19)),
+        Token::new(TokenType::Underscore,"_".into(),Span::new(21,22,1,22)),
+        eof(src)
     ];
     assert_eq!(expected,tokens);
 }
 
+#[test]
+fn token_position_test() {
+    let src = "let ä: int = 5;\n\n  # comment\n  x = \"a\nb\";";
+    let tokens = tokenize(src);
+    let spans: Vec<Span> = tokens.iter().map(|t| t.span()).collect();
+    let expected = vec![
+        Span::new(0,3,1,1),
+        Span::new(4,6,1,5),
+        Span::new(6,7,1,6),
+        Span::new(8,11,1,8),
+        Span::new(12,13,1,12),
+        Span::new(14,15,1,14),
+        Span::new(15,16,1,15),
+        Span::new(32,33,4,3),
+        Span::new(34,35,4,5),
+        Span::new(36,41,4,7),
+        Span::new(41,42,5,3),
+        Span::new(42,42,5,4),
+    ];
+    assert_eq!(expected,spans);
+    assert_eq!((32,33),(tokens[7].start(),tokens[7].end()));
+}
+
 #[test]
 fn keywords_test(){
     expect_token("fn",TokenType::Fn);
@@ -275,9 +497,23 @@ fn keywords_test(){
     expect_token("for",TokenType::For);
     expect_token("For",TokenType::Identifier);
 
+    expect_token("in",TokenType::In);
+    expect_token("In",TokenType::Identifier);
+
+    expect_token("step",TokenType::Step);
+    expect_token("Step",TokenType::Identifier);
+
     expect_token("loop",TokenType::Loop);
     expect_token("Loop",TokenType::Identifier);
 
+    expect_token("const",TokenType::Const);
+    expect_token("struct",TokenType::Struct);
+    expect_token("impl",TokenType::Impl);
+    expect_token("self",TokenType::SelfValue);
+    expect_token("enum",TokenType::Enum);
+    expect_token("match",TokenType::Match);
+    expect_token("Const",TokenType::Identifier);
+
     expect_token("break",TokenType::Break);
     expect_token("Break",TokenType::Identifier);
 
@@ -310,9 +546,51 @@ fn keywords_test(){
 
 }
 
+#[test]
+fn interpolated_strings_test(){
+    let src = r#""a {x + 1} b {f("c")}!""#;
+    let tokens = tokenize(src);
+    let expected = vec![
+        Token::new(TokenType::InterpolationStart,"a ".into(),Span::new(0,4,1,1)),
+        Token::new(TokenType::Identifier,"x".into(),Span::new(4,5,1,5)),
+        Token::new(TokenType::OperatorPlus,"+".into(),Span::new(6,7,1,7)),
+        Token::new(TokenType::LiteralInteger,"1".into(),Span::new(8,9,1,9)),
+        Token::new(TokenType::InterpolationMiddle," b ".into(),Span::new(9,14,1,10)),
+        Token::new(TokenType::Identifier,"f".into(),Span::new(14,15,1,15)),
+        Token::new(TokenType::SeparatorBracketOpen,"(".into(),Span::new(15,16,1,16)),
+        Token::new(TokenType::LiteralString,"c".into(),Span::new(16,19,1,17)),
+        Token::new(TokenType::SeparatorBracketClose,")".into(),Span::new(19,20,1,20)),
+        Token::new(TokenType::InterpolationEnd,"!".into(),Span::new(20,23,1,21)),
+        eof(src)
+    ];
+    assert_eq!(expected,tokens);
+
+    // brackets of a block inside the embedded expression do not end it, escaped brackets are text
+    let kinds: Vec<TokenType> = tokenize(r#""{ {1} }\{\}" "{"a{2}"}""#).iter().map(Token::kind).collect();
+    assert_eq!(vec![
+        TokenType::InterpolationStart, TokenType::SeparatorCurvedBracketOpen, TokenType::LiteralInteger,
+        TokenType::SeparatorCurvedBracketClosed, TokenType::InterpolationEnd,
+        TokenType::InterpolationStart, TokenType::InterpolationStart, TokenType::LiteralInteger,
+        TokenType::InterpolationEnd, TokenType::InterpolationEnd, TokenType::EoF,
+    ], kinds);
+    assert_eq!("{}", tokenize(r#""\{\}""#)[0].value());
+
+    assert_eq!(Err(LexerError::UnclosedInterpolation(Span::new(3,4,1,4))), Lexer::new("\"a {x").collect::<Result<Vec<_>,_>>());
+    // the second quote of "{" starts a string inside of the embedded expression, the error points at the bracket
+    assert_eq!(Err(LexerError::UnclosedInterpolation(Span::new(1,2,1,2))), Lexer::new("\"{\"").collect::<Result<Vec<_>,_>>());
+    assert_eq!(Err(LexerError::UnclosedInterpolation(Span::new(5,6,1,6))), Lexer::new("\"{1} {\" x").collect::<Result<Vec<_>,_>>());
+    assert_eq!(Err(LexerError::UnexpectedEndOfString(Span::new(0,8,1,1))), Lexer::new("\"a {x} b").collect::<Result<Vec<_>,_>>());
+}
+
+/// reads every token of src and fails if the lexer returns an error
+fn tokenize(src: &str) -> Vec<Token> {
+    Lexer::new(src).collect::<Result<_,_>>().expect("expected tokens")
+}
+
 fn expect_token(value: &str,kind: TokenType) {
-    let result = Lexer::tokenize(value.into()).0.collect();
-    let expected = vec![Token::new(kind, value.into(), 0),eof()];
+    let result = tokenize(value);
+    let span = Span::new(0, value.len(), 1, 1);
+    let expected = vec![Token::new(kind, value.into(), span),eof(value)];
     assert_eq!(expected, result);
 }
 
@@ -327,6 +605,9 @@ fn wrap_with_quotes(s: &str) -> String {
     string
 }
 
-fn eof() -> Token{
-    Token::new(TokenType::EoF,"".into(),0)
+/// creates the EoF token which is placed at the end of the source code
+fn eof(src: &str) -> Token{
+    let line = src.matches('\n').count() + 1;
+    let column = src.rsplit('\n').next().map(|l| l.chars().count()).unwrap_or(0) + 1;
+    Token::new(TokenType::EoF,"".into(),Span::new(src.len(),src.len(),line,column))
 }
@@ -0,0 +1,499 @@
+use crate::frontend::lexer::Lexer;
+use crate::frontend::parser::ast_parser::ASTParser;
+use crate::frontend::semantic::type_checker::{self, Type, TypeError, TypeErrorKind};
+use crate::frontend::syntax::DataType;
+use crate::frontend::syntax::ast::{BinOp, UnOp, LAMBDA};
+use crate::frontend::syntax::span::Span;
+
+#[test]
+fn valid_program_test() {
+    let src = "\
+    fn fibonacci(n: int): int {
+        if n == 1 or n == 2 {
+            return 1;
+        }
+        return fibonacci(n - 1) + fibonacci(n - 2);
+    }
+
+    fn sign(x: float): int {
+        if x < 0.0 {
+            return -1;
+        } else {
+            return 1;
+        }
+    }
+
+    fn forever(): string {
+        loop {
+            return \"done\";
+        }
+    }
+
+    fn main() {
+        let result: int = fibonacci(10) * sign(-2.5);
+        let text: string = forever() + \"!\";
+        while !(result > 100) {
+            result = result + 1;
+        }
+    }";
+
+    assert_eq!(Ok(()), check(src));
+}
+
+#[test]
+fn declarations_and_assignments_test() {
+    let src = "\
+    fn main() {
+        let a: int = true;
+        let b: float = 1.0;
+        b = \"text\";
+        c = 1;
+    }";
+
+    let kinds = error_kinds(src);
+    assert_eq!(vec![
+        TypeErrorKind::Mismatch(DataType::Integer, Type::Data(DataType::Boolean)),
+        TypeErrorKind::Mismatch(DataType::Float, Type::Data(DataType::String)),
+        TypeErrorKind::UndefinedVariable("c".into()),
+    ], kinds);
+}
+
+#[test]
+fn function_calls_test() {
+    let src = "\
+    fn add(a: int, b: int): int {
+        return a + b;
+    }
+
+    fn main() {
+        add(1);
+        add(1, 2.0);
+        let v: int = log();
+        let w: int = nothing();
+    }
+
+    fn nothing() {}";
+
+    let kinds = error_kinds(src);
+    assert_eq!(vec![
+        TypeErrorKind::WrongArgumentCount("add".into(), 2, 1),
+        TypeErrorKind::Mismatch(DataType::Integer, Type::Data(DataType::Float)),
+        TypeErrorKind::UndefinedFunction("log".into()),
+        TypeErrorKind::Mismatch(DataType::Integer, Type::Void),
+    ], kinds);
+}
+
+#[test]
+fn operators_and_conditions_test() {
+    let src = "\
+    fn main() {
+        let a: int = 1 + 2.0;
+        let b: boolean = -true;
+        let c: boolean = !5;
+        let d: boolean = true < false;
+        let e: boolean = undefined and 1;
+        if 1 {}
+        while \"yes\" {}
+    }";
+
+    let kinds = error_kinds(src);
+    assert_eq!(vec![
+        TypeErrorKind::InvalidOperands(BinOp::Plus, Type::Data(DataType::Integer), Type::Data(DataType::Float)),
+        TypeErrorKind::InvalidOperand(UnOp::Minus, Type::Data(DataType::Boolean)),
+        TypeErrorKind::InvalidOperand(UnOp::Negation, Type::Data(DataType::Integer)),
+        TypeErrorKind::InvalidOperands(BinOp::Lt, Type::Data(DataType::Boolean), Type::Data(DataType::Boolean)),
+        TypeErrorKind::UndefinedVariable("undefined".into()),
+        TypeErrorKind::Mismatch(DataType::Boolean, Type::Data(DataType::Integer)),
+        TypeErrorKind::Mismatch(DataType::Boolean, Type::Data(DataType::String)),
+    ], kinds);
+}
+
+#[test]
+fn return_paths_test() {
+    let src = "\
+    fn onlyIf(x: int): int {
+        if x > 0 {
+            return 1;
+        }
+    }
+
+    fn breaking(): int {
+        loop {
+            break;
+        }
+    }
+
+    fn emptyReturn(): float {
+        return;
+    }
+
+    fn voidFunction() {
+        return 5;
+    }
+
+    fn wrongType(): string {
+        return 1;
+    }
+
+    fn main() {
+        break;
+        continue;
+    }";
+
+    let kinds = error_kinds(src);
+    assert_eq!(vec![
+        TypeErrorKind::MissingReturn("onlyIf".into(), DataType::Integer),
+        TypeErrorKind::MissingReturn("breaking".into(), DataType::Integer),
+        TypeErrorKind::MissingReturnValue(DataType::Float),
+        TypeErrorKind::UnexpectedReturnValue("voidFunction".into()),
+        TypeErrorKind::Mismatch(DataType::String, Type::Data(DataType::Integer)),
+        TypeErrorKind::BreakOutsideLoop,
+        TypeErrorKind::ContinueOutsideLoop,
+    ], kinds);
+}
+
+#[test]
+fn for_loop_test() {
+    let src = "\
+    fn main() {
+        for i: int in 0..10 step 2 {
+            let half: int = i / 2;
+            continue;
+        }
+        for x: float in 0.5..\"end\" step true {
+            let y: float = x;
+        }
+        let after: int = i;
+    }";
+
+    let kinds = error_kinds(src);
+    assert_eq!(vec![
+        TypeErrorKind::Mismatch(DataType::Integer, Type::Data(DataType::Float)),
+        TypeErrorKind::Mismatch(DataType::Integer, Type::Data(DataType::String)),
+        TypeErrorKind::Mismatch(DataType::Integer, Type::Data(DataType::Boolean)),
+        TypeErrorKind::Mismatch(DataType::Integer, Type::Data(DataType::Float)),
+        TypeErrorKind::UndefinedVariable("i".into()),
+    ], kinds);
+}
+
+#[test]
+fn arrays_test() {
+    let src = "\
+    fn main() {
+        let a: [int] = [1, 2];
+        let b: [float] = [];
+        let c: int = a[0] + a.len();
+        a.push(1.5);
+        let d: [int] = [1, true];
+        let e: int = c[0];
+        a.size();
+        a.pop(1);
+        b = a;
+        a[0] = \"x\";
+        let f: boolean = a == a;
+    }";
+
+    let int_array = Type::Data(DataType::Array(Box::new(DataType::Integer)));
+    let kinds = error_kinds(src);
+    assert_eq!(vec![
+        TypeErrorKind::Mismatch(DataType::Integer, Type::Data(DataType::Float)),
+        TypeErrorKind::ArrayElementMismatch(Type::Data(DataType::Integer), Type::Data(DataType::Boolean)),
+        TypeErrorKind::NotIndexable(Type::Data(DataType::Integer)),
+        TypeErrorKind::UndefinedMethod(int_array.clone(), "size".into()),
+        TypeErrorKind::WrongArgumentCount("pop".into(), 0, 1),
+        TypeErrorKind::Mismatch(DataType::Array(Box::new(DataType::Float)), int_array.clone()),
+        TypeErrorKind::Mismatch(DataType::Integer, Type::Data(DataType::String)),
+        TypeErrorKind::InvalidOperands(BinOp::Eq, int_array.clone(), int_array),
+    ], kinds);
+}
+
+#[test]
+fn structs_test() {
+    let src = "\
+    fn main() {
+        let p: Point = Point { x: 1.0, y: 2 };
+        let q: Point = Point { x: 1.0, z: 2.0 };
+        let r: Line = Point { x: 1.0, y: 2.0 };
+        let x: int = p.x;
+        p.z = 1.0;
+        let n: int = 1;
+        n.x = 2;
+        let b: boolean = p == p;
+    }
+    struct Point { x: float, y: float }";
+
+    let point = Type::Data(DataType::Named("Point".into()));
+    let kinds = error_kinds(src);
+    assert_eq!(vec![
+        TypeErrorKind::Mismatch(DataType::Float, Type::Data(DataType::Integer)),
+        TypeErrorKind::UndefinedField(point.clone(), "z".into()),
+        TypeErrorKind::MissingField("Point".into(), "y".into()),
+        TypeErrorKind::UndefinedType("Line".into()),
+        TypeErrorKind::Mismatch(DataType::Named("Line".into()), point.clone()),
+        TypeErrorKind::Mismatch(DataType::Integer, Type::Data(DataType::Float)),
+        TypeErrorKind::UndefinedField(point.clone(), "z".into()),
+        TypeErrorKind::UndefinedField(Type::Data(DataType::Integer), "x".into()),
+        TypeErrorKind::InvalidOperands(BinOp::Eq, point.clone(), point),
+    ], kinds);
+}
+
+#[test]
+fn methods_test() {
+    let src = "\
+    struct Point { x: float, y: float }
+    impl Point {
+        fn new(x: float, y: float): Point {
+            return Point { x: x, y: y };
+        }
+        fn length(self): float {
+            return self.x * self.x + self.y * self.y;
+        }
+    }
+    fn main() {
+        let p: Point = Point::new(1.0, 2);
+        let l: int = p.length();
+        p.length(1.0);
+        p.new(1.0, 2.0);
+        p.area();
+        Point::length(p);
+    }";
+
+    let point = Type::Data(DataType::Named("Point".into()));
+    assert_eq!(vec![
+        TypeErrorKind::Mismatch(DataType::Float, Type::Data(DataType::Integer)),
+        TypeErrorKind::Mismatch(DataType::Integer, Type::Data(DataType::Float)),
+        TypeErrorKind::WrongArgumentCount("length".into(), 0, 1),
+        TypeErrorKind::UndefinedMethod(point.clone(), "new".into()),
+        TypeErrorKind::UndefinedMethod(point, "area".into()),
+    ], error_kinds(src));
+}
+
+#[test]
+fn enum_match_test() {
+    let valid = "\
+    enum Shape { Circle(float), Rect(float, float), Empty }
+    impl Shape {
+        fn area(self): float {
+            return match self {
+                Shape::Circle(r) => 3.0 * r * r,
+                Shape::Rect(w, h) => w * h,
+                Shape::Empty => 0.0,
+            };
+        }
+    }
+    fn sign(n: int): int {
+        match n {
+            0 => { return 0; }
+            _ => { return 1; }
+        }
+    }
+    fn main() {
+        let s: Shape = Shape::Rect(1.0, 2.0);
+        let a: float = s.area();
+        let b: boolean = match true { true => false, false => true };
+    }";
+    assert_eq!(Ok(()), check(valid));
+
+    let src = "\
+    enum Shape { Circle(float), Rect(float, float), Empty }
+    enum Option { Some(Shape), None }
+    fn main() {
+        let s: Shape = Shape::Circle(1);
+        Shape::Rect(1.0);
+        Shape::Square(1.0);
+        match s { Shape::Circle(r) => r, Shape::Empty => 0.0 };
+        match Option::Some(s) { Option::Some(Shape::Rect(_, _)) => 1, Option::None => 2 };
+        match true { true => 1 };
+        match 1 { 1 => 1, 2 => 2 };
+        match s { Shape::Circle(r) => r, _ => 1 };
+        match s { 1 => 1, Shape::Rect(w) => 2, Option::None => 3, Shape::Square => 4, _ => 5 };
+    }";
+    let shape = DataType::Named("Shape".into());
+    assert_eq!(vec![
+        TypeErrorKind::Mismatch(DataType::Float, Type::Data(DataType::Integer)),
+        TypeErrorKind::WrongArgumentCount("Shape::Rect".into(), 2, 1),
+        TypeErrorKind::UndefinedVariant("Shape".into(), "Square".into()),
+        TypeErrorKind::NonExhaustiveMatch("Shape::Rect(_, _)".into()),
+        TypeErrorKind::NonExhaustiveMatch("Option::Some(Shape::Circle(_))".into()),
+        TypeErrorKind::NonExhaustiveMatch("false".into()),
+        TypeErrorKind::NonExhaustiveMatch("_".into()),
+        TypeErrorKind::BranchMismatch(Type::Data(DataType::Float), Type::Data(DataType::Integer)),
+        TypeErrorKind::Mismatch(shape.clone(), Type::Data(DataType::Integer)),
+        TypeErrorKind::WrongArgumentCount("Shape::Rect".into(), 2, 1),
+        TypeErrorKind::Mismatch(shape, Type::Data(DataType::Named("Option".into()))),
+        TypeErrorKind::UndefinedVariant("Shape".into(), "Square".into()),
+    ], error_kinds(src));
+}
+
+#[test]
+fn lambda_test() {
+    let valid = "\
+    fn apply(f: fn(int): int, x: int): int {
+        return f(x);
+    }
+    fn double(x: int): int {
+        return x * 2;
+    }
+    fn main(): int {
+        let offset: int = 1;
+        let add: fn(int): int = fn(x: int): int { return x + offset; };
+        let pick: fn(boolean): fn(int): int = fn(first: boolean): fn(int): int {
+            if first {
+                return add;
+            }
+            return double;
+        };
+        return apply(pick(true), 2) + pick(false)(3);
+    }";
+    assert_eq!(Ok(()), check(valid));
+
+    let src = "\
+    fn main() {
+        let x: int = 1;
+        let f: fn(int): int = fn(x: float): int { return 1; };
+        let g: fn(): string = fn(): string { return 1; };
+        let h: fn(): int = fn(): int {};
+        let n: int = f(true);
+        f(1, 2);
+        x(1);
+        main == main;
+        let u: fn(Missing) = fn(m: Missing) {};
+    }";
+    let f = DataType::Function(vec![DataType::Integer], Some(Box::new(DataType::Integer)));
+    let main = Type::Data(DataType::Function(vec![], None));
+    assert_eq!(vec![
+        TypeErrorKind::Mismatch(f, Type::Data(DataType::Function(vec![DataType::Float], Some(Box::new(DataType::Integer))))),
+        TypeErrorKind::Mismatch(DataType::String, Type::Data(DataType::Integer)),
+        TypeErrorKind::MissingReturn(LAMBDA.into(), DataType::Integer),
+        TypeErrorKind::Mismatch(DataType::Integer, Type::Data(DataType::Boolean)),
+        TypeErrorKind::WrongArgumentCount("f".into(), 1, 2),
+        TypeErrorKind::NotCallable(Type::Data(DataType::Integer)),
+        TypeErrorKind::InvalidOperands(BinOp::Eq, main.clone(), main),
+        TypeErrorKind::UndefinedType("Missing".into()),
+        TypeErrorKind::UndefinedType("Missing".into()),
+    ], error_kinds(src));
+}
+
+#[test]
+fn error_location_test() {
+    let src = "fn main() {\n    let x: int = \"five\";\n}";
+
+    let errors = check(src).expect_err("expected type mismatch");
+    assert_eq!(vec![TypeError {
+        kind: TypeErrorKind::Mismatch(DataType::Integer, Type::Data(DataType::String)),
+        span: Span::new(29, 35, 2, 18),
+    }], errors);
+}
+
+#[test]
+fn block_values_test() {
+    let valid = "\
+    fn abs(x: int): int {
+        if x < 0 { -x } else { x }
+    }
+    fn main(): int {
+        let a: int = { let b: int = abs(-2); b * 2 };
+        let c: string = match a { 4 => \"four\", _ => { \"other\" } };
+        let d: float = loop { if a > 0 { break 1.5; } break 2.0; };
+        let e: int = if a > 0 { 1 } else { return 0; };
+        a + e
+    }";
+    assert_eq!(Ok(()), check(valid));
+
+    let src = "\
+    fn value(): int {
+        \"text\"
+    }
+    fn main() {
+        let a: int = if true { 1 } else { 1.5 };
+        let b: int = loop { break 1; break true; };
+        while true { break 1; }
+        let c: int = if true { 1 };
+    }";
+    assert_eq!(vec![
+        TypeErrorKind::Mismatch(DataType::Integer, Type::Data(DataType::String)),
+        TypeErrorKind::BranchMismatch(Type::Data(DataType::Integer), Type::Data(DataType::Float)),
+        TypeErrorKind::BranchMismatch(Type::Data(DataType::Integer), Type::Data(DataType::Boolean)),
+        TypeErrorKind::BreakWithValue,
+        TypeErrorKind::Mismatch(DataType::Integer, Type::Void),
+    ], error_kinds(src));
+}
+
+#[test]
+fn standard_library_test() {
+    assert_eq!(Ok(()), check("fn main() { let n: int = parse_int(read_line()); println(int_to_string(n)); }"));
+
+    let src = "\
+    fn main() {
+        print(1);
+        let s: int = trim(\"a\");
+        let x: float = pow(2, 3, 4);
+        let y: float = pow(2, 0.5);
+        exit();
+    }";
+    assert_eq!(vec![
+        TypeErrorKind::Mismatch(DataType::String, Type::Data(DataType::Integer)),
+        TypeErrorKind::Mismatch(DataType::Integer, Type::Data(DataType::String)),
+        TypeErrorKind::WrongArgumentCount("pow".into(), 2, 3),
+        TypeErrorKind::Mismatch(DataType::Float, Type::Data(DataType::Integer)),
+        TypeErrorKind::Mismatch(DataType::Integer, Type::Data(DataType::Float)),
+        TypeErrorKind::Mismatch(DataType::Float, Type::Data(DataType::Integer)),
+        TypeErrorKind::WrongArgumentCount("exit".into(), 1, 0),
+    ], error_kinds(src));
+    assert_eq!(Ok(()), check("fn main() { let x: float = abs(-1.5) + min(1.0, 2.0) + max(1.0, 2.0) + pow(2.0, 0.5); }"));
+}
+
+#[test]
+fn interpolation_test() {
+    assert_eq!(Ok(()), check("fn main() { let x: int = 1; println(\"{x} {1.5} {x > 0} {\"s\"}\"); }"));
+
+    let src = "\
+    fn nothing() {}
+    fn main() {
+        let n: int = \"{1}\";
+        println(\"{[1]} {nothing()} {undefined}\");
+    }";
+    assert_eq!(vec![
+        TypeErrorKind::Mismatch(DataType::Integer, Type::Data(DataType::String)),
+        TypeErrorKind::NotInterpolatable(Type::Data(DataType::Array(Box::new(DataType::Integer)))),
+        TypeErrorKind::NotInterpolatable(Type::Void),
+        TypeErrorKind::UndefinedVariable("undefined".into()),
+    ], error_kinds(src));
+}
+
+#[test]
+fn duplicate_type_test() {
+    let structs = "struct P { x: int }\nstruct P { y: int }\nfn main() {}";
+    let enums = "enum E { A }\nenum E { B }\nfn main() {}";
+    let mixed = "struct T { x: int }\nenum T { A }\nfn main() {}";
+
+    assert_eq!(Err(vec![TypeError {
+        kind: TypeErrorKind::DuplicateType("P".into(), Span::new(0, 19, 1, 1)),
+        span: Span::new(20, 39, 2, 1),
+    }]), check(structs));
+    assert_eq!(Err(vec![TypeError {
+        kind: TypeErrorKind::DuplicateType("E".into(), Span::new(0, 12, 1, 1)),
+        span: Span::new(13, 25, 2, 1),
+    }]), check(enums));
+    assert_eq!(Err(vec![TypeError {
+        kind: TypeErrorKind::DuplicateType("T".into(), Span::new(0, 19, 1, 1)),
+        span: Span::new(20, 32, 2, 1),
+    }]), check(mixed));
+}
+
+#[test]
+fn missing_main_test() {
+    assert_eq!(Err(vec![TypeError { kind: TypeErrorKind::MissingMainFunction, span: Span::new(0, 0, 1, 1) }]),
+               check("fn test(): int { 1 }"));
+    // a method with the name main is no entry point
+    assert_eq!(vec![TypeErrorKind::MissingMainFunction], error_kinds("struct P { x: int } impl P { fn main() {} }"));
+}
+
+fn check(src: &str) -> Result<(), Vec<TypeError>> {
+    let ast = ASTParser::new(Lexer::new(src)).parse().expect("expected abstract syntax tree");
+    type_checker::check(&ast)
+}
+
+fn error_kinds(src: &str) -> Vec<TypeErrorKind> {
+    check(src).expect_err("expected type errors").into_iter().map(|e| e.kind).collect()
+}
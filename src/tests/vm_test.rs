@@ -0,0 +1,163 @@
+use std::time::Instant;
+
+use crate::frontend::lexer::Lexer;
+use crate::frontend::parser::ast_parser::ASTParser;
+use crate::frontend::syntax::DataType;
+use crate::frontend::syntax::ast::{AbstractSyntaxTree, BinOp};
+use crate::backend::bytecode::{self, Instruction, Program};
+use crate::backend::interpreter::{self, RuntimeInterpreter, RuntimeError};
+use crate::backend::value::Value;
+use crate::backend::vm;
+
+#[test]
+fn compile_function_test() {
+    let src = "\
+    fn main(): int {
+        let a: int = 2;
+        while a < 10 {
+            a = a * 2;
+        }
+        return a;
+    }";
+
+    let program = compile(src).expect("expected compiled program");
+    assert_eq!(vec![Value::Integer(2), Value::Integer(10)], program.constants);
+    assert_eq!(vec![RuntimeError::MissingReturnValue("main".into())], program.errors);
+    assert_eq!(vec![
+        Instruction::Constant(0),
+        Instruction::Store(0),
+        Instruction::Load(0),
+        Instruction::Constant(1),
+        Instruction::CompareInt(BinOp::Lt),
+        Instruction::JumpIfFalse(11),
+        Instruction::Load(0),
+        Instruction::Constant(0),
+        Instruction::MulInt,
+        Instruction::Store(0),
+        Instruction::Jump(2),
+        Instruction::Load(0),
+        Instruction::Return,
+        Instruction::Trap(0),
+    ], program.functions[program.entry].code);
+}
+
+#[test]
+fn nested_loops_test() {
+    let src = "\
+    fn main(): string {
+        let text: string = \"\";
+        let i: int = 0;
+        loop {
+            i = i + 1;
+            if i > 3 {
+                break;
+            }
+            let j: int = 0;
+            while true {
+                j = j + 1;
+                if j == 2 {
+                    continue;
+                }
+                if j > 3 {
+                    break;
+                }
+                text = text + \"x\";
+            }
+            text = text + \"|\";
+        }
+        return text;
+    }";
+
+    assert_eq!(Ok(Value::String("xx|xx|xx|".into())), run(src));
+}
+
+#[test]
+fn traps_test() {
+    let lazy = "fn main(): boolean { return false and undefined(); }";
+    assert_eq!(Ok(Value::Boolean(false)), run(lazy));
+
+    let boolean_order = "fn main(): boolean { return true < false; }";
+    assert_eq!(Err(RuntimeError::InvalidOperands(BinOp::Lt, "boolean", "boolean")), run(boolean_order));
+
+    let void_value = "fn nothing() {} fn main(): int { let a: int = nothing(); return a; }";
+    assert_eq!(Err(RuntimeError::TypeMismatch(DataType::Integer, "void")), run(void_value));
+
+    let overflow = "fn main(): int { return 9223372036854775807 + 1; }";
+    assert_eq!(Err(RuntimeError::IntegerOverflow), run(overflow));
+
+    let break_outside = "fn main() { break; }";
+    assert_eq!(Err(RuntimeError::BreakOutsideLoop), run(break_outside));
+
+    let returned_value = "fn log() { return 1; } fn main() { log(); }";
+    assert_eq!(Err(RuntimeError::UnexpectedReturnValue("log".into())), run(returned_value));
+
+    let main_arguments = "fn main(a: int) {}";
+    assert_eq!(Err(RuntimeError::WrongArgumentCount("main".into(), 1, 0)), run(main_arguments));
+}
+
+#[test]
+fn deep_recursion_test() {
+    let sum = |n: i64| format!("\
+    fn sum(n: int): int {{
+        if n == 0 {{
+            return 0;
+        }}
+        return n + sum(n - 1);
+    }}
+    fn main(): int {{ return sum({}); }}", n);
+    // the call frames of the vm are on the heap, so it allows deeper calls than the interpreter
+    let depth = interpreter::MAX_CALL_DEPTH * 5;
+    let expected = (depth * (depth + 1) / 2) as i64;
+    assert_eq!(Ok(Value::Integer(expected)), compile(&sum(depth as i64)).and_then(|program| vm::run(&program)));
+    let overflow = compile(&sum(vm::MAX_FRAMES as i64)).and_then(|program| vm::run(&program));
+    assert_eq!(Err(RuntimeError::StackOverflow), overflow);
+}
+
+/// Compares the execution time of the RuntimeInterpreter with the vm.
+/// Run it with "cargo test --release -- --ignored --nocapture"
+#[test]
+#[ignore]
+fn fibonacci_benchmark() {
+    let src = "\
+    fn fibonacci(n: int): int {
+        if n == 1 or n == 2 {
+            return 1;
+        }
+        return fibonacci(n - 1) + fibonacci(n - 2);
+    }
+
+    fn main(): int {
+        return fibonacci(27);
+    }";
+
+    let ast = parse(src);
+    let start = Instant::now();
+    let interpreted = RuntimeInterpreter::new(ast.clone()).start();
+    let interpreter_time = start.elapsed();
+
+    let start = Instant::now();
+    let compiled = bytecode::compile(&ast).and_then(|program| vm::run(&program));
+    let vm_time = start.elapsed();
+
+    println!("interpreter: {:?}, vm: {:?}, speedup: {:.1}x",
+             interpreter_time, vm_time, interpreter_time.as_secs_f64() / vm_time.as_secs_f64());
+    assert_eq!(Ok(Value::Integer(196418)), compiled);
+    assert_eq!(interpreted, compiled);
+    assert!(vm_time < interpreter_time);
+}
+
+fn parse(src: &str) -> AbstractSyntaxTree {
+    ASTParser::new(Lexer::new(src)).parse().expect("expected abstract syntax tree")
+}
+
+fn compile(src: &str) -> Result<Program,RuntimeError> {
+    bytecode::compile(&parse(src))
+}
+
+/// runs the program with the vm and checks that the RuntimeInterpreter returns the same result
+fn run(src: &str) -> Result<Value,RuntimeError> {
+    let ast = parse(src);
+    let compiled = bytecode::compile(&ast).and_then(|program| vm::run(&program));
+    assert_eq!(RuntimeInterpreter::new(ast).start(), compiled);
+    compiled
+}